@@ -12,6 +12,7 @@
 //!   - [`TableLayout`][]: arranges its elements in columns and rows
 //!   - [`OrderedList`][] and [`UnorderedList`][]: arrange their elements sequentially with bullet
 //!     points
+//!   - [`DefinitionList`][]: arranges terms and their indented descriptions
 //! - Text:
 //!   - [`Text`][]: a single line of text
 //!   - [`Paragraph`][]: a wrapped and aligned paragraph of text
@@ -19,6 +20,8 @@
 //!   - [`FramedElement`][]: draws a frame around the wrapped element
 //!   - [`PaddedElement`][]: adds a padding to the wrapped element
 //!   - [`StyledElement`][]: sets a default style for the wrapped element and its children
+//!   - [`ConditionalElement`][]: only renders the wrapped element if a condition is met
+//!   - [`IdTracker`][]: records the positions of the wrapped element
 //! - Other:
 //!   - [`Image`][]: an image (requires the `images` feature)
 //!   - [`Break`][]: adds forced line breaks as a spacer
@@ -31,6 +34,7 @@
 //! [`TableLayout`]: struct.TableLayout.html
 //! [`OrderedList`]: struct.OrderedList.html
 //! [`UnorderedList`]: struct.UnorderedList.html
+//! [`DefinitionList`]: struct.DefinitionList.html
 //! [`Text`]: struct.Text.html
 //! [`Image`]: struct.Image.html
 //! [`Break`]: struct.Break.html
@@ -39,6 +43,8 @@
 //! [`FramedElement`]: struct.FramedElement.html
 //! [`PaddedElement`]: struct.PaddedElement.html
 //! [`StyledElement`]: struct.StyledElement.html
+//! [`ConditionalElement`]: struct.ConditionalElement.html
+//! [`IdTracker`]: struct.IdTracker.html
 
 #[cfg(feature = "images")]
 mod images;
@@ -46,6 +52,7 @@ mod images;
 use std::collections;
 use std::iter;
 use std::mem;
+use std::ops;
 
 use crate::error::{Error, ErrorKind};
 use crate::fonts;
@@ -53,9 +60,12 @@ use crate::render;
 use crate::style;
 use crate::style::Color;
 use crate::style::{LineStyle, Style, StyledString};
-use crate::utils::log;
+use crate::utils::LogLevel;
 use crate::wrap;
-use crate::{Alignment, Context, Element, Margins, Mm, Position, RenderResult, Size};
+use crate::{
+    Alignment, Borders, Context, Element, ElementPositions, Margins, Mm, PageBoundingBox, Position,
+    RenderResult, Size, TextDirection, VerticalAlignment,
+};
 
 #[cfg(feature = "images")]
 pub use images::Image;
@@ -78,6 +88,29 @@ impl IntoBoxedElement for Box<dyn Element> {
     }
 }
 
+/// Extracts the plain text content of an element.
+///
+/// This trait is implemented by the elements that contain text.  Use
+/// [`Element::as_plain_text_extractor`][] to access it for a boxed element, or
+/// [`Document::extract_text`][] to extract the text of a whole document.  The extracted text does
+/// not contain styling information, bullet points or hyphenation.
+///
+/// [`Element::as_plain_text_extractor`]: ../trait.Element.html#method.as_plain_text_extractor
+/// [`Document::extract_text`]: ../struct.Document.html#method.extract_text
+pub trait PlainTextExtractor {
+    /// Returns the plain text content of this element.
+    fn extract(&self) -> String;
+}
+
+fn extract_joined<'a>(elements: impl IntoIterator<Item = &'a Box<dyn Element>>) -> String {
+    elements
+        .into_iter()
+        .filter_map(|element| element.as_plain_text_extractor())
+        .map(PlainTextExtractor::extract)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 /// Arranges a list of elements sequentially.
 ///
 /// Currently, elements can only be arranged vertically.
@@ -96,6 +129,8 @@ impl IntoBoxedElement for Box<dyn Element> {
 /// ```
 /// use genpdf::elements;
 /// let layout = elements::LinearLayout::vertical()
+///     .margins(genpdf::Margins::vh(2, 0))
+///     .spacing(2.0)
 ///     .element(elements::Paragraph::new("Test1"))
 ///     .element(elements::Paragraph::new("Test2"));
 /// ```
@@ -124,8 +159,15 @@ impl LinearLayout {
 
     /// set margins
     /// margins is the distance between the text and the border
-    pub fn set_margins(&mut self, margins: Margins) {
+    pub fn set_margins(&mut self, margins: Margins) -> &mut Self {
         self.margins = Some(margins);
+        self
+    }
+
+    /// Sets the margins of this layout and returns the layout.
+    pub fn margins(mut self, margins: Margins) -> Self {
+        self.set_margins(margins);
+        self
     }
 
     /// returns the current margins
@@ -134,8 +176,20 @@ impl LinearLayout {
     }
 
     /// set list item margins
-    pub fn set_list_item_spacing(&mut self, spacing: f64) {
+    pub fn set_list_item_spacing(&mut self, spacing: f64) -> &mut Self {
         self.list_item_spacing = spacing;
+        self
+    }
+
+    /// Sets the spacing between the elements of this layout and returns the layout.
+    pub fn spacing(mut self, spacing: f64) -> Self {
+        self.set_list_item_spacing(spacing);
+        self
+    }
+
+    /// Returns the index of the element that is rendered next.
+    pub(crate) fn render_index(&self) -> usize {
+        self.render_idx
     }
 
     /// Adds the given element to this layout.
@@ -149,19 +203,129 @@ impl LinearLayout {
         self
     }
 
+    /// Returns the number of elements in this layout.
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Returns whether this layout does not contain any elements.
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Returns the elements of this layout.
+    pub fn elements(&self) -> &[Box<dyn Element>] {
+        &self.elements
+    }
+
+    /// Removes all elements from this layout.
+    pub fn clear(&mut self) {
+        self.elements.clear();
+        self.render_idx = 0;
+    }
+
+    /// Returns the element with the given index, or `None` if the index is out of bounds.
+    pub fn get(&self, index: usize) -> Option<&dyn Element> {
+        self.elements.get(index).map(AsRef::as_ref)
+    }
+
+    /// Returns a mutable reference to the element with the given index, or `None` if the index is
+    /// out of bounds.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut Box<dyn Element>> {
+        self.elements.get_mut(index)
+    }
+
+    /// Inserts the given element at the given index, shifting all elements after it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn insert<E: IntoBoxedElement>(&mut self, index: usize, element: E) {
+        self.elements.insert(index, element.into_boxed_element());
+        if index < self.render_idx {
+            self.render_idx += 1;
+        }
+    }
+
+    /// Removes the element with the given index and returns it, or returns `None` if the index is
+    /// out of bounds.
+    pub fn remove(&mut self, index: usize) -> Option<Box<dyn Element>> {
+        if index >= self.elements.len() {
+            return None;
+        }
+        if index < self.render_idx {
+            self.render_idx -= 1;
+        }
+        Some(self.elements.remove(index))
+    }
+
+    /// Replaces the element with the given index and returns the previous element, or returns
+    /// `None` and drops the given element if the index is out of bounds.
+    pub fn replace<E: IntoBoxedElement>(
+        &mut self,
+        index: usize,
+        element: E,
+    ) -> Option<Box<dyn Element>> {
+        self.elements
+            .get_mut(index)
+            .map(|slot| mem::replace(slot, element.into_boxed_element()))
+    }
+
+    /// Renders this layout and calls the given function before each element is rendered.
+    ///
+    /// The function receives the index of the next element, the element, the remaining area and
+    /// whether an element has already been rendered into the area.  If it returns `true`,
+    /// rendering stops and the element is moved to the next area.
+    pub(crate) fn render_with_break_check(
+        &mut self,
+        context: &Context,
+        area: render::Area<'_>,
+        style: Style,
+        mut break_before: impl FnMut(usize, &mut dyn Element, render::Area<'_>, bool) -> bool,
+    ) -> Result<RenderResult, Error> {
+        self.render_vertical(context, area, style, &mut break_before)
+    }
+
     fn render_vertical(
         &mut self,
         context: &Context,
         mut area: render::Area<'_>,
         style: Style,
+        break_before: &mut dyn FnMut(usize, &mut dyn Element, render::Area<'_>, bool) -> bool,
     ) -> Result<RenderResult, Error> {
         let mut result = RenderResult::default();
         if let Some(margins) = self.margins {
             area.add_margins(margins);
         }
+        let mut prev_space_after = Mm(0.0);
         while area.size().height > Mm(0.0) && self.render_idx < self.elements.len() {
+            let started = result.size.height > Mm(0.0);
+            if break_before(
+                self.render_idx,
+                self.elements[self.render_idx].as_mut(),
+                area.clone(),
+                started,
+            ) {
+                result.has_more = true;
+                return Ok(result);
+            }
+            // Collapse the space after the previous element with the space before this element
+            // so that only the larger one is used.  If the element does not render anything, the
+            // space after the previous element is kept.
+            let overlap = prev_space_after.min(self.elements[self.render_idx].space_before());
+            let mut element_area = area.clone();
+            element_area.add_offset(Position::new(0, Mm(0.0) - overlap));
             let element_result =
-                self.elements[self.render_idx].render(context, area.clone(), style)?;
+                self.elements[self.render_idx].render(context, element_area, style)?;
+            if element_result.size.height > Mm(0.0) {
+                area.add_offset(Position::new(0, Mm(0.0) - overlap));
+                result.size.height -= overlap;
+            }
+            if element_result.size != Size::new(0, 0) {
+                if let Some(name) = self.elements[self.render_idx].display_name() {
+                    context.record_element(context.page_index(), name);
+                }
+            }
             let mut left_offset = 0;
             let right_offset = element_result.size.height + Mm(self.list_item_spacing);
             if let Some(el_offset) = element_result.offset {
@@ -174,6 +338,9 @@ impl LinearLayout {
                 result.has_more = true;
                 return Ok(result);
             }
+            if element_result.size.height > Mm(0.0) {
+                prev_space_after = self.elements[self.render_idx].space_after();
+            }
             self.render_idx += 1;
         }
         result.has_more = self.render_idx < self.elements.len();
@@ -184,7 +351,48 @@ impl LinearLayout {
     }
 }
 
+impl PlainTextExtractor for LinearLayout {
+    fn extract(&self) -> String {
+        extract_joined(&self.elements)
+    }
+}
+
 impl Element for LinearLayout {
+    fn get_min_width(&mut self, style: Style, context: &Context) -> Mm {
+        let width = self
+            .elements
+            .iter_mut()
+            .map(|element| element.get_min_width(style, context))
+            .fold(Mm(0.0), Mm::max);
+        match self.margins {
+            Some(margins) => width + margins.left + margins.right,
+            None => width,
+        }
+    }
+
+    fn get_natural_width(&mut self, style: Style, context: &Context) -> Mm {
+        let width = self
+            .elements
+            .iter_mut()
+            .map(|element| element.get_natural_width(style, context))
+            .fold(Mm(0.0), Mm::max);
+        match self.margins {
+            Some(margins) => width + margins.left + margins.right,
+            None => width,
+        }
+    }
+
+    fn reset_render(&mut self) {
+        self.render_idx = 0;
+        for element in &mut self.elements {
+            element.reset_render();
+        }
+    }
+
+    fn as_plain_text_extractor(&self) -> Option<&dyn PlainTextExtractor> {
+        Some(self)
+    }
+
     fn render(
         &mut self,
         context: &Context,
@@ -192,7 +400,7 @@ impl Element for LinearLayout {
         style: Style,
     ) -> Result<RenderResult, Error> {
         // TODO: add horizontal layout
-        self.render_vertical(context, area, style)
+        self.render_vertical(context, area, style, &mut |_, _, _, _| false)
     }
 
     fn get_probable_height(
@@ -222,42 +430,189 @@ impl<E: IntoBoxedElement> iter::Extend<E> for LinearLayout {
 
 /// A single line of formatted text.
 ///
-/// This element renders a single styled string on a single line.  It does not wrap it if the
-/// string is longer than the line.  Therefore you should prefer [`Paragraph`][] over `Text` for
-/// most use cases.
+/// This element renders a single styled string on a single line.  Per default, it does not wrap
+/// it if the string is longer than the line.  You can change this behavior with
+/// [`set_overflow`][], but you should prefer [`Paragraph`][] over `Text` for most use cases.
 ///
 /// [`Paragraph`]: struct.Paragraph.html
+/// [`set_overflow`]: #method.set_overflow
 #[derive(Clone, Debug, Default)]
 pub struct Text {
     text: StyledString,
+    overflow: TextOverflow,
+    paragraph: Option<Paragraph>,
+    position: Option<Position>,
 }
 
 impl Text {
     /// Creates a new instance with the given styled string.
     pub fn new(text: impl Into<StyledString>) -> Text {
-        Text { text: text.into() }
+        Text {
+            text: text.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the behavior of this text if it is longer than the line.
+    pub fn set_overflow(&mut self, overflow: TextOverflow) {
+        self.overflow = overflow;
+    }
+
+    /// Sets the behavior of this text if it is longer than the line and returns the text.
+    pub fn with_overflow(mut self, overflow: TextOverflow) -> Self {
+        self.set_overflow(overflow);
+        self
+    }
+
+    /// Sets the position of this text within the area, measured from the upper left corner of the
+    /// area.
+    ///
+    /// Per default, the text is printed at the current cursor position.  If a position is set,
+    /// the text is printed at that position without taking up any space, so the following
+    /// elements are not moved.
+    pub fn set_position(&mut self, position: impl Into<Position>) {
+        self.position = Some(position.into());
+    }
+
+    /// Sets the position of this text within the area and returns the text.
+    ///
+    /// See [`set_position`][] for more information.
+    ///
+    /// [`set_position`]: #method.set_position
+    pub fn at_position(mut self, position: impl Into<Position>) -> Self {
+        self.set_position(position);
+        self
+    }
+
+    /// Sets whether this text is wrapped if it is longer than the line and returns the text.
+    ///
+    /// This is a shorthand for [`with_overflow`][] with [`TextOverflow::Wrap`][] or
+    /// [`TextOverflow::Visible`][].
+    ///
+    /// [`with_overflow`]: #method.with_overflow
+    /// [`TextOverflow::Wrap`]: enum.TextOverflow.html#variant.Wrap
+    /// [`TextOverflow::Visible`]: enum.TextOverflow.html#variant.Visible
+    pub fn with_wrapping(self, wrapping: bool) -> Self {
+        if wrapping {
+            self.with_overflow(TextOverflow::Wrap)
+        } else {
+            self.with_overflow(TextOverflow::Visible)
+        }
+    }
+
+    fn paragraph(&mut self) -> &mut Paragraph {
+        let text = &self.text;
+        self.paragraph
+            .get_or_insert_with(|| Paragraph::new(text.clone()))
+    }
+
+    /// Returns the prefix of the text that fits into the given width, followed by the given
+    /// suffix.
+    fn truncate(&self, style: Style, context: &Context, width: Mm, suffix: &str) -> String {
+        let font_cache = &context.font_cache;
+        if style.str_width(font_cache, &self.text.s) <= width {
+            return self.text.s.clone();
+        }
+        let mut s = self.text.s.clone();
+        while !s.is_empty() && style.str_width(font_cache, &format!("{}{}", s, suffix)) > width {
+            s.pop();
+        }
+        s.push_str(suffix);
+        s
+    }
+}
+
+/// The behavior of a [`Text`][] element that is longer than the line, see
+/// [`Text::set_overflow`][].
+///
+/// [`Text`]: struct.Text.html
+/// [`Text::set_overflow`]: struct.Text.html#method.set_overflow
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextOverflow {
+    /// The text is printed on a single line, even if it exceeds the area.
+    Visible,
+    /// The text is cut off at the end of the line.
+    Clip,
+    /// The text is cut off at the end of the line and an ellipsis is appended.
+    Ellipsis,
+    /// The text is wrapped like a [`Paragraph`][].
+    ///
+    /// [`Paragraph`]: struct.Paragraph.html
+    Wrap,
+}
+
+impl Default for TextOverflow {
+    fn default() -> TextOverflow {
+        TextOverflow::Visible
+    }
+}
+
+impl PlainTextExtractor for Text {
+    fn extract(&self) -> String {
+        self.text.s.clone()
     }
 }
 
 impl Element for Text {
+    fn as_plain_text_extractor(&self) -> Option<&dyn PlainTextExtractor> {
+        Some(self)
+    }
+
+    fn get_min_width(&mut self, mut style: Style, context: &Context) -> Mm {
+        match self.overflow {
+            TextOverflow::Visible => {
+                style.merge(self.text.style);
+                style.str_width(&context.font_cache, &self.text.s)
+            }
+            TextOverflow::Clip | TextOverflow::Ellipsis => Mm(0.0),
+            TextOverflow::Wrap => self.paragraph().get_min_width(style, context),
+        }
+    }
+
+    fn get_natural_width(&mut self, mut style: Style, context: &Context) -> Mm {
+        match self.overflow {
+            TextOverflow::Wrap => self.paragraph().get_natural_width(style, context),
+            _ => {
+                style.merge(self.text.style);
+                style.str_width(&context.font_cache, &self.text.s)
+            }
+        }
+    }
+
+    fn reset_render(&mut self) {
+        self.paragraph = None;
+    }
+
     fn render(
         &mut self,
         context: &Context,
-        area: render::Area<'_>,
+        mut area: render::Area<'_>,
         mut style: Style,
     ) -> Result<RenderResult, Error> {
         let mut result = RenderResult::default();
         style.merge(self.text.style);
-        if area.print_str(
-            &context.font_cache,
-            Position::default(),
-            style,
-            &self.text.s,
-        )? {
-            result.size = Size::new(
-                style.str_width(&context.font_cache, &self.text.s),
-                style.line_height(&context.font_cache),
-            );
+        let position = self.position.unwrap_or_default();
+        let width = area.size().width - position.x;
+        let s = match self.overflow {
+            TextOverflow::Visible => self.text.s.clone(),
+            TextOverflow::Clip => self.truncate(style, context, width, ""),
+            TextOverflow::Ellipsis => self.truncate(style, context, width, "…"),
+            TextOverflow::Wrap => {
+                area.add_offset(position);
+                let mut result = self.paragraph().render(context, area, style)?;
+                if self.position.is_some() {
+                    result.size = Size::default();
+                }
+                return Ok(result);
+            }
+        };
+        if area.print_str(&context.font_cache, position, style, &s)? {
+            if self.position.is_none() {
+                result.size = Size::new(
+                    style.str_width(&context.font_cache, &s),
+                    style.line_height(&context.font_cache),
+                );
+            }
         } else {
             result.has_more = true;
         }
@@ -268,9 +623,15 @@ impl Element for Text {
         &mut self,
         style: style::Style,
         context: &Context,
-        _area: render::Area<'_>,
+        area: render::Area<'_>,
     ) -> Mm {
-        style.line_height(&context.font_cache)
+        if self.position.is_some() {
+            Mm(0.0)
+        } else if self.overflow == TextOverflow::Wrap {
+            self.paragraph().get_probable_height(style, context, area)
+        } else {
+            style.line_height(&context.font_cache)
+        }
     }
 }
 
@@ -319,10 +680,17 @@ impl Element for Text {
 pub struct Paragraph {
     text: Vec<StyledString>,
     words: collections::VecDeque<StyledString>,
+    rendered_text: Vec<StyledString>,
+    rendered_word_count: usize,
     style_applied: bool,
     alignment: Alignment,
+    text_direction: TextDirection,
     style: style::Style,
     margins: Option<Margins>,
+    space_before: Mm,
+    space_after: Mm,
+    first_line_indent: Mm,
+    first_line_rendered: bool,
 }
 
 impl Paragraph {
@@ -365,6 +733,16 @@ impl Paragraph {
         self.style.is_underline()
     }
 
+    /// Sets the strikethrough effect for this style.
+    pub fn set_strikethrough(&mut self, strikethrough: bool) {
+        self.style.set_strikethrough(strikethrough);
+    }
+
+    /// Returns whether the strikethrough text effect is set.
+    pub fn is_strikethrough(&self) -> bool {
+        self.style.is_strikethrough()
+    }
+
     /// set font italic
     pub fn set_italic(&mut self, italic: bool) {
         self.style.set_italic(italic);
@@ -381,6 +759,54 @@ impl Paragraph {
         self.margins
     }
 
+    /// Sets the vertical space that is added before the first line of this paragraph.
+    ///
+    /// If this paragraph directly follows an element with a space after it in a
+    /// [`LinearLayout`][], only the larger of the two spaces is used.
+    ///
+    /// [`LinearLayout`]: struct.LinearLayout.html
+    pub fn set_space_before(&mut self, space: impl Into<Mm>) {
+        self.space_before = space.into();
+    }
+
+    /// Sets the vertical space that is added before the first line of this paragraph and returns
+    /// the paragraph.
+    pub fn with_space_before(mut self, space: impl Into<Mm>) -> Self {
+        self.set_space_before(space);
+        self
+    }
+
+    /// Sets the vertical space that is added after the last line of this paragraph.
+    ///
+    /// If this paragraph is directly followed by an element with a space before it in a
+    /// [`LinearLayout`][], only the larger of the two spaces is used.
+    ///
+    /// [`LinearLayout`]: struct.LinearLayout.html
+    pub fn set_space_after(&mut self, space: impl Into<Mm>) {
+        self.space_after = space.into();
+    }
+
+    /// Sets the vertical space that is added after the last line of this paragraph and returns
+    /// the paragraph.
+    pub fn with_space_after(mut self, space: impl Into<Mm>) -> Self {
+        self.set_space_after(space);
+        self
+    }
+
+    /// Sets the horizontal indent of the first line of this paragraph.
+    ///
+    /// If the paragraph is continued on the next page, the indent is not applied to the first
+    /// line of the continuation.
+    pub fn set_first_line_indent(&mut self, indent: impl Into<Mm>) {
+        self.first_line_indent = indent.into();
+    }
+
+    /// Sets the horizontal indent of the first line of this paragraph and returns the paragraph.
+    pub fn with_first_line_indent(mut self, indent: impl Into<Mm>) -> Self {
+        self.set_first_line_indent(indent);
+        self
+    }
+
     /// Sets the alignment of this paragraph.
     pub fn set_alignment(&mut self, alignment: Alignment) {
         self.alignment = alignment;
@@ -392,6 +818,25 @@ impl Paragraph {
         self
     }
 
+    /// Sets the text direction of this paragraph.
+    ///
+    /// See [`TextDirection`][] for the limitations of right-to-left text.
+    ///
+    /// [`TextDirection`]: ../enum.TextDirection.html
+    pub fn set_text_direction(&mut self, text_direction: TextDirection) {
+        self.text_direction = text_direction;
+    }
+
+    /// Sets the text direction of this paragraph and returns the paragraph.
+    ///
+    /// See [`TextDirection`][] for the limitations of right-to-left text.
+    ///
+    /// [`TextDirection`]: ../enum.TextDirection.html
+    pub fn with_text_direction(mut self, text_direction: TextDirection) -> Self {
+        self.set_text_direction(text_direction);
+        self
+    }
+
     /// Adds a string to the end of this paragraph.
     pub fn push(&mut self, s: impl Into<StyledString>) {
         self.text.push(s.into());
@@ -414,14 +859,104 @@ impl Paragraph {
         self
     }
 
+    /// Adds a non-breaking string to the end of this paragraph.
+    ///
+    /// The string is never split across lines, and no line break is inserted between consecutive
+    /// non-breaking strings.  See [`StyledString::non_breaking`][] for more information.
+    ///
+    /// [`StyledString::non_breaking`]: ../style/struct.StyledString.html#method.non_breaking
+    pub fn push_non_breaking(&mut self, s: impl Into<StyledString>) {
+        self.text.push(s.into().non_breaking());
+    }
+
+    /// Adds a non-breaking string with the given style to the end of this paragraph.
+    ///
+    /// See [`push_non_breaking`][] for more information.
+    ///
+    /// [`push_non_breaking`]: #method.push_non_breaking
+    pub fn push_non_breaking_styled(&mut self, s: impl Into<String>, style: impl Into<Style>) {
+        self.text.push(StyledString::new(s, style).non_breaking());
+    }
+
     fn get_offset(&self, width: Mm, max_width: Mm) -> Mm {
-        match self.alignment {
+        // For right-to-left text, the alignment is mirrored.
+        let alignment = match (self.text_direction, self.alignment) {
+            (TextDirection::Rtl, Alignment::Left) => Alignment::Right,
+            (TextDirection::Rtl, Alignment::Right) => Alignment::Left,
+            (_, alignment) => alignment,
+        };
+        match alignment {
             Alignment::Left => Mm::default(),
             Alignment::Center => (max_width - width) / 2.0,
             Alignment::Right => max_width - width,
         }
     }
 
+    /// Resets the render state of this paragraph so that it is rendered from the beginning on the
+    /// next call to [`Element::render`][].
+    ///
+    /// [`Element::render`]: ../trait.Element.html#tymethod.render
+    pub fn reset(&mut self) {
+        if !self.rendered_text.is_empty() {
+            self.text = mem::take(&mut self.rendered_text);
+        }
+        self.words.clear();
+        self.rendered_word_count = 0;
+        self.first_line_rendered = false;
+        self.style_applied = false;
+    }
+
+    /// Returns the number of words in this paragraph.
+    pub fn word_count(&self) -> usize {
+        if self.text.is_empty() {
+            wrap::count_words(&self.rendered_text)
+        } else {
+            wrap::count_words(&self.text)
+        }
+    }
+
+    /// Returns the number of lines that are needed to render this paragraph in the given area.
+    ///
+    /// Like [`Element::get_probable_height`][], this method only considers the content of this
+    /// paragraph that has not been rendered yet.
+    ///
+    /// [`Element::get_probable_height`]: ../trait.Element.html#tymethod.get_probable_height
+    pub fn line_count(
+        &mut self,
+        context: &Context,
+        style: Style,
+        mut area: render::Area<'_>,
+    ) -> usize {
+        self.apply_style(style);
+        if let Some(margins) = self.margins {
+            area.add_margins(margins);
+        }
+        let text = if self.words.is_empty() {
+            replace_variables(wrap::Words::new(self.text.clone()).collect(), context)
+        } else {
+            self.words.clone()
+        };
+        let text: Vec<_> = text.into_iter().collect();
+        wrap::count_lines(&text, context, Style::new(), area.size().width)
+    }
+
+    /// Returns the number of words that have already been rendered.
+    pub fn words_rendered(&self) -> usize {
+        self.rendered_word_count
+    }
+
+    /// Returns the height of a single line of this paragraph.
+    ///
+    /// The line height is calculated for the given document style merged with the style of this
+    /// paragraph, so it takes the font size and the line spacing set for this paragraph into
+    /// account.  This can be used to create a [`Break`][] that matches the line height of this
+    /// paragraph.
+    ///
+    /// [`Break`]: struct.Break.html
+    pub fn get_line_height(&self, style: Style, context: &Context) -> Mm {
+        style.and(self.style).line_height(&context.font_cache)
+    }
+
     fn apply_style(&mut self, doc_style: Style) {
         if !self.style_applied {
             for s in &mut self.text {
@@ -443,25 +978,156 @@ impl Paragraph {
     }
 }
 
-fn replace_page_number(
+/// Draws an underline for a string with the given style, starting at `left` with the given width,
+/// and returns the line style of the underline.
+///
+/// The position and thickness of the underline are taken from the style.  Per default, the
+/// underline has a thickness of 0.2 mm and is drawn at the bottom of the line.
+fn draw_underline(
+    area: &render::Area<'_>,
+    style: Style,
+    metrics: fonts::Metrics,
+    left: Mm,
+    width: Mm,
+) -> LineStyle {
+    let ls = LineStyle::new().with_thickness(style.underline_thickness().unwrap_or(Mm(0.2)));
+    let line_offset = ls.thickness() / 2.0;
+    let bottom = match style.underline_offset() {
+        Some(offset) => metrics.ascent + offset + line_offset,
+        None => metrics.line_height,
+    };
+    let bottom_points = vec![
+        Position::new(left, bottom - line_offset),
+        Position::new(left + width, bottom - line_offset),
+    ];
+    area.draw_line(bottom_points, ls);
+    ls
+}
+
+/// Draws a strikethrough line for a string with the given style, starting at `left` with the
+/// given width.
+///
+/// The line is drawn at half the ascent of the string above the baseline of the line with the
+/// given metrics, using the color of the style.
+fn draw_strikethrough(
+    area: &render::Area<'_>,
+    style: Style,
+    context: &Context,
+    metrics: fonts::Metrics,
+    left: Mm,
+    width: Mm,
+) {
+    let ls = LineStyle::new()
+        .with_thickness(Mm(0.2))
+        .with_color(style.color().unwrap_or(Color::Rgb(0, 0, 0)));
+    let y = metrics.ascent - style.metrics(&context.font_cache).ascent / 2.0;
+    let points = vec![Position::new(left, y), Position::new(left + width, y)];
+    area.draw_line(points, ls);
+}
+
+fn replace_variables(
     words: collections::VecDeque<StyledString>,
     context: &Context,
 ) -> collections::VecDeque<StyledString> {
     let mut words_copy = words.clone();
-    // loop words and replace #{page} with context.page_number & remove new lines
+    // loop words, replace #{page}, #{total_pages} and #{var_name} placeholders & remove new lines
     for i in 0..words.len() {
         let mut s = words[i].s.clone();
         s = s.replace("\n", "");
-        if s.contains(&"#{page}") {
-            let page = context.page_number;
-            s = s.replace(&"#{page}", &page.to_string());
+        if s.contains("#{") {
+            s = replace_placeholders(&s, context);
         }
-        words_copy[i].s = s.into();
+        words_copy[i].s = s;
     }
     words_copy
 }
 
+/// Replaces `#{page}` with the current page number, `#{total_pages}` with the total number of
+/// pages and `#{var_name}` with the user data value for `var_name`, or with an empty string if the
+/// value is not set.
+fn replace_placeholders(s: &str, context: &Context) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find("#{") {
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => break,
+        };
+        result.push_str(&rest[..start]);
+        let name = &rest[start + 2..end];
+        if name == "page" {
+            result.push_str(&context.page_number.to_string());
+        } else if name == "total_pages" {
+            if let Some(total_pages) = context.total_pages {
+                result.push_str(&total_pages.to_string());
+            }
+        } else {
+            result.push_str(context.get_var(name).unwrap_or(""));
+        }
+        rest = &rest[end + 1..];
+    }
+    result.push_str(rest);
+    result
+}
+
+impl PlainTextExtractor for Paragraph {
+    fn extract(&self) -> String {
+        self.text.iter().map(|s| s.s.as_str()).collect()
+    }
+}
+
 impl Element for Paragraph {
+    fn get_min_width(&mut self, style: Style, context: &Context) -> Mm {
+        self.apply_style(style);
+        let text = if self.words.is_empty() {
+            wrap::Words::new(self.text.clone()).collect()
+        } else {
+            self.words.clone()
+        };
+        let width = text
+            .iter()
+            .map(|s| s.style.str_width(&context.font_cache, s.s.trim_end()))
+            .fold(Mm(0.0), Mm::max);
+        match self.margins {
+            Some(margins) => width + margins.left + margins.right,
+            None => width,
+        }
+    }
+
+    fn get_natural_width(&mut self, style: Style, context: &Context) -> Mm {
+        self.apply_style(style);
+        let text: Vec<_> = if self.words.is_empty() {
+            wrap::Words::new(self.text.clone()).collect()
+        } else {
+            self.words.iter().cloned().collect()
+        };
+        let width = text
+            .iter()
+            .enumerate()
+            .map(|(i, s)| {
+                let word = if i + 1 == text.len() {
+                    s.s.trim_end()
+                } else {
+                    s.s.as_str()
+                };
+                s.style.str_width(&context.font_cache, word)
+            })
+            .sum::<Mm>()
+            + self.first_line_indent;
+        match self.margins {
+            Some(margins) => width + margins.left + margins.right,
+            None => width,
+        }
+    }
+
+    fn reset_render(&mut self) {
+        self.reset();
+    }
+
+    fn as_plain_text_extractor(&self) -> Option<&dyn PlainTextExtractor> {
+        Some(self)
+    }
+
     fn render(
         &mut self,
         context: &Context,
@@ -471,22 +1137,30 @@ impl Element for Paragraph {
         let mut result = RenderResult::default();
         self.apply_style(style);
 
-        if self.words.is_empty() {
+        let is_first = self.words.is_empty();
+        if is_first {
             if self.text.is_empty() {
                 return Ok(result);
             }
-            self.words = wrap::Words::new(mem::take(&mut self.text)).collect();
-            self.words = replace_page_number(self.words.clone(), context);
+            self.rendered_text = mem::take(&mut self.text);
+            self.words = wrap::Words::new(self.rendered_text.clone()).collect();
+            self.words = replace_variables(self.words.clone(), context);
+            area.add_offset(Position::new(0, self.space_before));
         }
 
         if let Some(margins) = self.margins {
             area.add_margins(margins);
         }
 
+        let indent = if self.first_line_rendered {
+            Mm(0.0)
+        } else {
+            self.first_line_indent
+        };
         let words = self.words.iter().map(Into::into);
         let mut rendered_len = 0;
-        let mut wrapper = wrap::Wrapper::new(words, context, area.size().width);
-        for (line, delta) in &mut wrapper {
+        let mut wrapper = wrap::Wrapper::new(words, context, area.size().width).with_indent(indent);
+        for (line_idx, (line, delta)) in (&mut wrapper).enumerate() {
             let width = line.iter().map(|s| s.width(&context.font_cache)).sum();
             // Calculate the maximum line height
             let metrics = line
@@ -494,32 +1168,39 @@ impl Element for Paragraph {
                 .map(|s| s.style.metrics(&context.font_cache))
                 .fold(fonts::Metrics::default(), |max, m| max.max(&m));
             let height = metrics.line_height;
-            let x = self.get_offset(width, area.size().width);
+            let x = if line_idx == 0 {
+                indent + self.get_offset(width, area.size().width - indent)
+            } else {
+                self.get_offset(width, area.size().width)
+            };
             let position = Position::new(x, 0);
 
             // println!("x {:?}", x);
             let mut line_width = Mm(0.0);
             if let Some(mut section) = area.text_section(&context.font_cache, position, metrics) {
+                let mut line = line;
+                if self.text_direction == TextDirection::Rtl {
+                    line.reverse();
+                    for s in &mut line {
+                        s.s = s.s.chars().rev().collect::<String>().into();
+                    }
+                }
                 for s in line {
                     section.print_str(&s.s, s.style)?;
                     let s_width = s.width(&context.font_cache);
                     // println!("s {:?}, {:?}", s.s, s.style);
                     if s.style.is_underline() {
-                        let ls = LineStyle::new().with_thickness(0.2);
+                        draw_underline(&area, s.style, metrics, x + line_width, s_width);
+                    }
+                    if s.style.is_strikethrough() {
                         let left = x + line_width;
-                        let line_offset = ls.thickness() / 2.0;
-                        let right = left + s_width;
-                        let bottom = metrics.line_height;
-                        let bottom_points = vec![
-                            Position::new(left, bottom - line_offset),
-                            Position::new(right, bottom - line_offset),
-                        ];
-                        area.draw_line(bottom_points, ls);
+                        draw_strikethrough(&area, s.style, context, metrics, left, s_width);
                     }
                     line_width += s_width;
                     rendered_len += s.s.len();
                 }
                 rendered_len -= delta;
+                self.first_line_rendered = true;
             } else {
                 result.has_more = true;
                 break;
@@ -552,19 +1233,35 @@ impl Element for Paragraph {
             if self.words[0].s.len() <= rendered_len {
                 rendered_len -= self.words[0].s.len();
                 self.words.pop_front();
+                self.rendered_word_count += 1;
             } else {
                 self.words[0].s.replace_range(..rendered_len, "");
                 rendered_len = 0;
             }
         }
 
+        // If no line fits into the area, the space before is dropped together with the page.
+        if is_first && result.size.height > Mm(0.0) {
+            result.size.height += self.space_before;
+        }
         if let Some(margins) = self.margins {
             result.size.width += margins.left + margins.right;
             result.size.height += margins.top + margins.bottom;
         }
+        if !result.has_more {
+            result.size.height += self.space_after;
+        }
         Ok(result)
     }
 
+    fn space_before(&self) -> Mm {
+        self.space_before
+    }
+
+    fn space_after(&self) -> Mm {
+        self.space_after
+    }
+
     fn get_probable_height(
         &mut self,
         style: style::Style,
@@ -574,9 +1271,10 @@ impl Element for Paragraph {
         self.apply_style(style);
         let mut height = Mm::default();
         let mut words = wrap::Words::new(self.text.clone()).collect();
-        words = replace_page_number(words, context);
+        words = replace_variables(words, context);
         let mut wrapper =
-            wrap::Wrapper::new(words.iter().map(Into::into), context, area.size().width);
+            wrap::Wrapper::new(words.iter().map(Into::into), context, area.size().width)
+                .with_indent(self.first_line_indent);
         for (line, _) in &mut wrapper {
             let metrics = line
                 .iter()
@@ -587,7 +1285,11 @@ impl Element for Paragraph {
         if let Some(margins) = self.margins {
             height += margins.top + margins.bottom;
         }
-        height
+        // The space before is only added before the first line, see render.
+        if self.words.is_empty() {
+            height += self.space_before;
+        }
+        height + self.space_after
     }
 }
 
@@ -618,29 +1320,68 @@ impl<T: Into<StyledString>> iter::FromIterator<T> for Paragraph {
 
 /// A line break.
 ///
-/// This element inserts a given number of empty lines.
+/// This element inserts a given number of empty lines or a vertical space with a fixed height.
 ///
 /// # Example
 ///
 /// ```
 /// // Draws 5 empty lines (calculating the line height using the current style)
 /// let b = genpdf::elements::Break::new(5);
+/// // Draws an empty space with a height of 12.5 mm
+/// let b = genpdf::elements::Break::from_mm(12.5);
 /// ```
 #[derive(Clone, Copy, Debug, Default)]
 pub struct Break {
     lines: f64,
+    height: Option<Mm>,
+    initial_lines: f64,
+    initial_height: Option<Mm>,
 }
 
 impl Break {
     /// Creates a new break with the given number of lines.
     pub fn new(lines: impl Into<f64>) -> Break {
+        let lines = lines.into();
+        Break {
+            lines,
+            height: None,
+            initial_lines: lines,
+            initial_height: None,
+        }
+    }
+
+    /// Creates a new break with the given height, independent of the line height of the current
+    /// style.
+    pub fn from_mm(height: impl Into<Mm>) -> Break {
+        let height = Some(height.into());
         Break {
-            lines: lines.into(),
+            lines: 0.0,
+            height,
+            initial_lines: 0.0,
+            initial_height: height,
+        }
+    }
+
+    /// Creates a new break without any height.
+    pub fn zero() -> Break {
+        Break::from_mm(Mm::ZERO)
+    }
+
+    /// Returns the height of this break for the given style.
+    fn height(&self, style: Style, context: &Context) -> Mm {
+        match self.height {
+            Some(height) => height,
+            None => style.line_height(&context.font_cache) * self.lines,
         }
     }
 }
 
 impl Element for Break {
+    fn reset_render(&mut self) {
+        self.lines = self.initial_lines;
+        self.height = self.initial_height;
+    }
+
     fn render(
         &mut self,
         context: &Context,
@@ -648,6 +1389,20 @@ impl Element for Break {
         style: Style,
     ) -> Result<RenderResult, Error> {
         let mut result = RenderResult::default();
+        if let Some(height) = self.height {
+            if height <= Mm::ZERO {
+                return Ok(result);
+            }
+            if height < area.size().height {
+                result.size.height = height;
+                self.height = Some(Mm::ZERO);
+            } else {
+                result.size.height = area.size().height;
+                self.height = Some(height - area.size().height);
+            }
+            return Ok(result);
+        }
+
         if self.lines <= 0.0 {
             return Ok(result);
         }
@@ -669,14 +1424,17 @@ impl Element for Break {
         context: &Context,
         area: render::Area<'_>,
     ) -> Mm {
-        let line_height = style.line_height(&context.font_cache);
-        let break_height = line_height * self.lines;
+        let break_height = self.height(style, context);
         if break_height < area.size().height {
             break_height
         } else {
             area.size().height
         }
     }
+    fn preferred_size(&self) -> Option<Size> {
+        // Breaks with a number of lines depend on the line height of the current style.
+        self.height.map(|height| Size::new(0, height))
+    }
 }
 
 /// A page break.
@@ -701,6 +1459,10 @@ impl PageBreak {
 }
 
 impl Element for PageBreak {
+    fn reset_render(&mut self) {
+        self.cont = false;
+    }
+
     fn render(
         &mut self,
         _context: &Context,
@@ -945,6 +1707,13 @@ impl Element for Line {
             _ => self.thickness(),
         }
     }
+    fn preferred_size(&self) -> Option<Size> {
+        // Lines without an explicit length span the full area, so we only know the thickness.
+        match self.orientation() {
+            "vertical" => self.height.map(|height| Size::new(self.thickness, height)),
+            _ => self.width.map(|width| Size::new(width, self.thickness)),
+        }
+    }
 }
 
 /// Adds a padding to the wrapped element.
@@ -967,6 +1736,12 @@ impl Element for Line {
 ///     .padded(genpdf::Margins::trbl(5, 2, 5, 10));
 /// ```
 ///
+/// Uniform padding:
+/// ```
+/// use genpdf::{elements, Element as _};
+/// let p = elements::Paragraph::new("text").padded_uniform(5);
+/// ```
+///
 /// [`Element::padded`]: ../trait.Element.html#method.padded
 #[derive(Clone, Debug, Default)]
 pub struct PaddedElement<E: Element> {
@@ -982,9 +1757,40 @@ impl<E: Element> PaddedElement<E> {
             padding: padding.into(),
         }
     }
+
+    /// Creates a new padded element with the same padding on all four sides.
+    pub fn uniform(element: E, padding: impl Into<Mm>) -> PaddedElement<E> {
+        PaddedElement::new(element, Margins::all(padding))
+    }
+
+    /// Creates a new padded element with the given padding on the left and on the right side.
+    pub fn horizontal(element: E, padding: impl Into<Mm>) -> PaddedElement<E> {
+        PaddedElement::new(element, Margins::horizontal(padding))
+    }
+
+    /// Creates a new padded element with the given padding on the top and on the bottom side.
+    pub fn vertical(element: E, padding: impl Into<Mm>) -> PaddedElement<E> {
+        PaddedElement::new(element, Margins::vertical(padding))
+    }
 }
 
 impl<E: Element> Element for PaddedElement<E> {
+    fn get_min_width(&mut self, style: Style, context: &Context) -> Mm {
+        self.element.get_min_width(style, context) + self.padding.left + self.padding.right
+    }
+
+    fn get_natural_width(&mut self, style: Style, context: &Context) -> Mm {
+        self.element.get_natural_width(style, context) + self.padding.left + self.padding.right
+    }
+
+    fn reset_render(&mut self) {
+        self.element.reset_render();
+    }
+
+    fn as_plain_text_extractor(&self) -> Option<&dyn PlainTextExtractor> {
+        self.element.as_plain_text_extractor()
+    }
+
     fn render(
         &mut self,
         context: &Context,
@@ -1056,6 +1862,36 @@ impl<E: Element> StyledElement<E> {
 }
 
 impl<E: Element> Element for StyledElement<E> {
+    fn get_min_width(&mut self, mut style: Style, context: &Context) -> Mm {
+        style.merge(self.style);
+        self.element.get_min_width(style, context)
+    }
+
+    fn get_natural_width(&mut self, mut style: Style, context: &Context) -> Mm {
+        style.merge(self.style);
+        self.element.get_natural_width(style, context)
+    }
+
+    fn reset_render(&mut self) {
+        self.element.reset_render();
+    }
+
+    fn as_plain_text_extractor(&self) -> Option<&dyn PlainTextExtractor> {
+        self.element.as_plain_text_extractor()
+    }
+
+    fn preferred_size(&self) -> Option<Size> {
+        self.element.preferred_size()
+    }
+
+    fn space_before(&self) -> Mm {
+        self.element.space_before()
+    }
+
+    fn space_after(&self) -> Mm {
+        self.element.space_after()
+    }
+
     fn render(
         &mut self,
         context: &Context,
@@ -1076,6 +1912,200 @@ impl<E: Element> Element for StyledElement<E> {
     }
 }
 
+type ConditionFn = Box<dyn Fn(&Context) -> bool>;
+type EstimateFn = Box<dyn Fn() -> bool>;
+
+/// Renders the wrapped element only if a condition is met.
+///
+/// The condition is evaluated every time the element is rendered.  If it returns `false`,
+/// nothing is rendered.  This makes it possible to build a template document once and to show or
+/// hide sections depending on the data or on the rendering context, for example the page number.
+///
+/// As the probable height is often calculated with a different context than the one used for
+/// rendering, it does not use the condition.  Instead, you can set a second condition that does
+/// not depend on the context with [`with_estimate`][].  If it is not set, the probable height is
+/// zero.
+///
+/// # Example
+///
+/// ```
+/// use genpdf::elements;
+/// let show_notes = false;
+/// let notes = elements::ConditionalElement::new(
+///     elements::Paragraph::new("Notes"),
+///     move |_| show_notes,
+/// )
+/// .with_estimate(move || show_notes);
+/// ```
+///
+/// [`with_estimate`]: #method.with_estimate
+pub struct ConditionalElement<E: Element> {
+    element: E,
+    condition: ConditionFn,
+    estimate: Option<EstimateFn>,
+}
+
+impl<E: Element> ConditionalElement<E> {
+    /// Creates a new conditional element that wraps the given element and only renders it if the
+    /// given condition returns `true`.
+    pub fn new<F>(element: E, condition: F) -> ConditionalElement<E>
+    where
+        F: Fn(&Context) -> bool + 'static,
+    {
+        ConditionalElement {
+            element,
+            condition: Box::new(condition),
+            estimate: None,
+        }
+    }
+
+    /// Sets the condition that is used to calculate the probable height of this element.
+    pub fn set_estimate<F>(&mut self, estimate: F)
+    where
+        F: Fn() -> bool + 'static,
+    {
+        self.estimate = Some(Box::new(estimate));
+    }
+
+    /// Sets the condition that is used to calculate the probable height of this element and
+    /// returns the element.
+    pub fn with_estimate<F>(mut self, estimate: F) -> Self
+    where
+        F: Fn() -> bool + 'static,
+    {
+        self.set_estimate(estimate);
+        self
+    }
+}
+
+impl<E: Element> Element for ConditionalElement<E> {
+    fn reset_render(&mut self) {
+        self.element.reset_render();
+    }
+
+    fn as_plain_text_extractor(&self) -> Option<&dyn PlainTextExtractor> {
+        self.element.as_plain_text_extractor()
+    }
+
+    fn render(
+        &mut self,
+        context: &Context,
+        area: render::Area<'_>,
+        style: Style,
+    ) -> Result<RenderResult, Error> {
+        if (self.condition)(context) {
+            self.element.render(context, area, style)
+        } else {
+            Ok(RenderResult::default())
+        }
+    }
+
+    fn get_probable_height(
+        &mut self,
+        style: style::Style,
+        context: &Context,
+        area: render::Area<'_>,
+    ) -> Mm {
+        match &self.estimate {
+            Some(estimate) if estimate() => self.element.get_probable_height(style, context, area),
+            _ => Mm(0.0),
+        }
+    }
+}
+
+/// Records the positions where the wrapped element is rendered.
+///
+/// This wrapper is created by [`Document::push_with_id`][].  Every time the wrapped element is
+/// rendered, a [`PageBoundingBox`][] is added to the [`ElementPositions`][] for the ID of this
+/// tracker.  The ID is also used as the display name for the [`PageElementRegistry`][].
+///
+/// [`Document::push_with_id`]: ../struct.Document.html#method.push_with_id
+/// [`PageElementRegistry`]: ../struct.PageElementRegistry.html
+/// [`PageBoundingBox`]: ../struct.PageBoundingBox.html
+/// [`ElementPositions`]: ../struct.ElementPositions.html
+pub struct IdTracker<E: Element> {
+    id: String,
+    element: E,
+    positions: ElementPositions,
+}
+
+impl<E: Element> IdTracker<E> {
+    /// Creates a new tracker that records the positions of the given element with the given ID.
+    pub fn new(id: impl Into<String>, element: E, positions: ElementPositions) -> IdTracker<E> {
+        IdTracker {
+            id: id.into(),
+            element,
+            positions,
+        }
+    }
+}
+
+impl<E: Element> Element for IdTracker<E> {
+    fn as_plain_text_extractor(&self) -> Option<&dyn PlainTextExtractor> {
+        self.element.as_plain_text_extractor()
+    }
+
+    fn get_min_width(&mut self, style: Style, context: &Context) -> Mm {
+        self.element.get_min_width(style, context)
+    }
+
+    fn get_natural_width(&mut self, style: Style, context: &Context) -> Mm {
+        self.element.get_natural_width(style, context)
+    }
+
+    fn reset_render(&mut self) {
+        self.element.reset_render();
+    }
+
+    fn preferred_size(&self) -> Option<Size> {
+        self.element.preferred_size()
+    }
+
+    fn space_before(&self) -> Mm {
+        self.element.space_before()
+    }
+
+    fn space_after(&self) -> Mm {
+        self.element.space_after()
+    }
+
+    fn display_name(&self) -> Option<&str> {
+        Some(&self.id)
+    }
+
+    fn render(
+        &mut self,
+        context: &Context,
+        area: render::Area<'_>,
+        style: Style,
+    ) -> Result<RenderResult, Error> {
+        let origin = area.origin();
+        let result = self.element.render(context, area, style)?;
+        if result.size != Size::new(0, 0) {
+            self.positions.record(
+                &self.id,
+                PageBoundingBox {
+                    page_index: context.page_index(),
+                    x: origin.x,
+                    y: origin.y,
+                    width: result.size.width,
+                    height: result.size.height,
+                },
+            );
+        }
+        Ok(result)
+    }
+
+    fn get_probable_height(
+        &mut self,
+        style: style::Style,
+        context: &Context,
+        area: render::Area<'_>,
+    ) -> Mm {
+        self.element.get_probable_height(style, context, area)
+    }
+}
+
 /// Adds a frame around the wrapped element.
 ///
 /// # Examples
@@ -1094,12 +2124,22 @@ impl<E: Element> Element for StyledElement<E> {
 /// let p = elements::Paragraph::new("text").framed(style::LineStyle::new());
 /// ```
 ///
+/// Only some borders:
+/// ```
+/// use genpdf::{elements, style, Borders};
+/// let mut borders = Borders::none();
+/// borders.bottom = Some(style::LineStyle::new().with_thickness(0.5));
+/// let p = elements::FramedElement::with_borders(elements::Paragraph::new("text"), borders);
+/// ```
+///
 /// [`Element::framed`]: ../trait.Element.html#method.framed
 #[derive(Clone, Debug, Default)]
 pub struct FramedElement<E: Element> {
     element: E,
     is_first: bool,
-    line_style: LineStyle,
+    borders: Borders,
+    inner_padding: Margins,
+    fill_color: Option<Color>,
 }
 
 impl<E: Element> FramedElement<E> {
@@ -1111,76 +2151,204 @@ impl<E: Element> FramedElement<E> {
     /// Creates a new framed element that wraps the given element,
     /// and with the given line style.
     pub fn with_line_style(element: E, line_style: impl Into<LineStyle>) -> FramedElement<E> {
+        FramedElement::with_borders(element, Borders::all(line_style))
+    }
+
+    /// Creates a new framed element that wraps the given element and only draws the given
+    /// borders.
+    pub fn with_borders(element: E, borders: Borders) -> FramedElement<E> {
         Self {
             is_first: true,
             element,
-            line_style: line_style.into(),
+            borders,
+            inner_padding: Margins::default(),
+            fill_color: None,
         }
     }
+
+    /// Creates a new framed element that wraps the given element, fills the frame with the given
+    /// color and draws the frame with the given line style.
+    pub fn with_fill(
+        element: E,
+        fill_color: Color,
+        line_style: impl Into<LineStyle>,
+    ) -> FramedElement<E> {
+        let mut framed = FramedElement::with_line_style(element, line_style);
+        framed.set_fill_color(fill_color);
+        framed
+    }
+
+    /// Sets the color that the area within the frame is filled with.
+    ///
+    /// The wrapped element is drawn on top of the fill.  As the fill is drawn first, its height is
+    /// calculated from the probable height of the wrapped element.  Per default, the frame is not
+    /// filled.
+    pub fn set_fill_color(&mut self, fill_color: Color) {
+        self.fill_color = Some(fill_color);
+    }
+
+    /// Sets the padding between the frame and the wrapped element.
+    ///
+    /// Unlike wrapping the element in a [`PaddedElement`][], the padding is drawn inside of the
+    /// frame.  Per default, no padding is added.
+    ///
+    /// [`PaddedElement`]: struct.PaddedElement.html
+    pub fn set_inner_padding(&mut self, inner_padding: impl Into<Margins>) {
+        self.inner_padding = inner_padding.into();
+    }
+
+    /// Sets the padding between the frame and the wrapped element and returns the framed element.
+    pub fn with_inner_padding(mut self, inner_padding: impl Into<Margins>) -> Self {
+        self.set_inner_padding(inner_padding);
+        self
+    }
 }
 
 impl<E: Element> Element for FramedElement<E> {
+    fn get_min_width(&mut self, style: Style, context: &Context) -> Mm {
+        self.element.get_min_width(style, context)
+            + self.inner_padding.left
+            + self.inner_padding.right
+    }
+
+    fn get_natural_width(&mut self, style: Style, context: &Context) -> Mm {
+        self.element.get_natural_width(style, context)
+            + self.inner_padding.left
+            + self.inner_padding.right
+    }
+
+    fn reset_render(&mut self) {
+        self.is_first = true;
+        self.element.reset_render();
+    }
+
+    fn as_plain_text_extractor(&self) -> Option<&dyn PlainTextExtractor> {
+        self.element.as_plain_text_extractor()
+    }
+
     fn render(
         &mut self,
         context: &Context,
         area: render::Area<'_>,
         style: Style,
     ) -> Result<RenderResult, Error> {
-        // if let Some(margins) = self.margins {
-        // area.add_margins(20);
-        // }
         // For the element area calculations, we have to take into account the full line thickness.
-        // For the frame area, we only need half because we specify the center of the line.
-        let line_thickness = self.line_style.thickness();
-        let line_offset = line_thickness / 2.0;
+        // For the frame, we only need half because we specify the center of the line.
+        let thickness = self.borders.thickness();
+        let padding = self.inner_padding;
 
-        // Calculate the areas in which to draw the element and the frame.
+        // Calculate the area in which to draw the element.
         let mut element_area = area.clone();
-        let mut frame_area = area.clone();
         element_area.add_margins(Margins::trbl(
             0,
-            line_thickness,
-            line_thickness,
-            line_thickness,
+            thickness.right + padding.right,
+            thickness.bottom + padding.bottom,
+            thickness.left + padding.left,
         ));
-        frame_area.add_margins(Margins::trbl(0, line_offset, 0, line_offset));
         if self.is_first {
-            element_area.add_margins(Margins::trbl(line_thickness, 0, 0, 0));
-            frame_area.add_margins(Margins::trbl(line_offset, 0, 0, 0));
+            element_area.add_margins(Margins::trbl(thickness.top + padding.top, 0, 0, 0));
+        }
+
+        // The fill has to be drawn before the element so that it does not cover it.  As the
+        // actual height is not known yet, we use the probable height of the element.
+        if let Some(fill_color) = self.fill_color {
+            let mut fill_height =
+                self.element
+                    .get_probable_height(style, context, element_area.clone())
+                    + thickness.bottom
+                    + padding.bottom;
+            if self.is_first {
+                fill_height += thickness.top + padding.top;
+            }
+            let fill_size = Size::new(area.size().width, fill_height.min(area.size().height));
+            area.fill_rect_at(Position::default(), fill_size, fill_color);
         }
 
-        // Draw the element.
         let mut result = self.element.render(context, element_area, style)?;
         result.size.width = area.size().width;
-        if result.has_more {
-            frame_area.set_height(result.size.height + line_offset);
-        } else {
-            frame_area.set_height(result.size.height + line_thickness);
+        let mut content_height = result.size.height;
+        if self.is_first {
+            result.size.height += thickness.top + padding.top;
+            content_height += padding.top;
+        }
+        if !result.has_more {
+            result.size.height += thickness.bottom + padding.bottom;
+            content_height += padding.bottom;
         }
 
-        // Draw the frame.
+        let width = result.size.width;
+        let height = result.size.height;
+
+        // If all edges share the same style, draw the frame as closed polylines so that the
+        // corners are joined.
+        if let Some(line_style) = self.borders.uniform_line_style() {
+            let line_thickness = line_style.thickness();
+            let line_offset = line_thickness / 2.0;
+            let mut frame_area = area.clone();
+            frame_area.add_margins(Margins::trbl(0, line_offset, 0, line_offset));
+            if self.is_first {
+                frame_area.add_margins(Margins::trbl(line_offset, 0, 0, 0));
+            }
+            if result.has_more {
+                frame_area.set_height(content_height + line_offset);
+            } else {
+                frame_area.set_height(content_height + line_thickness);
+            }
 
-        let top_left = Position::default();
-        let top_right = Position::new(frame_area.size().width, 0);
-        let bottom_left = Position::new(0, frame_area.size().height);
-        let bottom_right = Position::new(frame_area.size().width, frame_area.size().height);
+            let top_left = Position::default();
+            let top_right = Position::new(frame_area.size().width, 0);
+            let bottom_left = Position::new(0, frame_area.size().height);
+            let bottom_right = Position::new(frame_area.size().width, frame_area.size().height);
 
-        if self.is_first {
-            result.size.height += line_thickness;
-            frame_area.draw_line(
-                vec![bottom_right, top_right, top_left, bottom_left],
-                self.line_style,
+            if self.is_first {
+                frame_area.draw_line(
+                    vec![bottom_right, top_right, top_left, bottom_left],
+                    line_style,
+                );
+            }
+            if !result.has_more {
+                frame_area.draw_line(
+                    vec![top_left, bottom_left, bottom_right, top_right],
+                    line_style,
+                );
+            } else {
+                frame_area.draw_line(vec![top_left, bottom_left], line_style);
+                frame_area.draw_line(vec![top_right, bottom_right], line_style);
+            }
+
+            self.is_first = false;
+            return Ok(result);
+        }
+
+        // Otherwise, draw every edge separately.  The horizontal lines span the full width and the
+        // vertical lines span the full height so that the corners are closed.
+        if let Some(line_style) = self.borders.top.filter(|_| self.is_first) {
+            let y = thickness.top / 2.0;
+            area.draw_line(
+                vec![Position::new(0, y), Position::new(width, y)],
+                line_style,
             );
         }
-        if !result.has_more {
-            result.size.height += line_thickness;
-            frame_area.draw_line(
-                vec![top_left, bottom_left, bottom_right, top_right],
-                self.line_style,
+        if let Some(line_style) = self.borders.bottom.filter(|_| !result.has_more) {
+            let y = height - thickness.bottom / 2.0;
+            area.draw_line(
+                vec![Position::new(0, y), Position::new(width, y)],
+                line_style,
+            );
+        }
+        if let Some(line_style) = self.borders.left {
+            let x = thickness.left / 2.0;
+            area.draw_line(
+                vec![Position::new(x, 0), Position::new(x, height)],
+                line_style,
+            );
+        }
+        if let Some(line_style) = self.borders.right {
+            let x = width - thickness.right / 2.0;
+            area.draw_line(
+                vec![Position::new(x, 0), Position::new(x, height)],
+                line_style,
             );
-        } else {
-            frame_area.draw_line(vec![top_left, bottom_left], self.line_style);
-            frame_area.draw_line(vec![top_right, bottom_right], self.line_style);
         }
 
         self.is_first = false;
@@ -1192,9 +2360,17 @@ impl<E: Element> Element for FramedElement<E> {
         &mut self,
         style: style::Style,
         context: &Context,
-        area: render::Area<'_>,
+        mut area: render::Area<'_>,
     ) -> Mm {
+        area.add_margins(Margins::trbl(
+            0,
+            self.inner_padding.right,
+            0,
+            self.inner_padding.left,
+        ));
         self.element.get_probable_height(style, context, area)
+            + self.inner_padding.top
+            + self.inner_padding.bottom
     }
 }
 
@@ -1296,6 +2472,7 @@ pub struct UnorderedList {
     layout: LinearLayout,
     bullet: Option<String>,
     margins: Option<Margins>,
+    intra_item_spacing: Mm,
 }
 
 impl UnorderedList {
@@ -1305,15 +2482,27 @@ impl UnorderedList {
             layout: LinearLayout::vertical(),
             bullet: None,
             margins: None,
+            intra_item_spacing: Mm(0.0),
         }
     }
 
+    /// Returns the number of items in this list.
+    pub fn item_count(&self) -> usize {
+        self.layout.len()
+    }
+
+    /// Removes all items from this list.
+    pub fn clear(&mut self) {
+        self.layout.clear();
+    }
+
     /// Creates a new unordered list with the given bullet point symbol.
     pub fn with_bullet(bullet: impl Into<String>) -> UnorderedList {
         UnorderedList {
             layout: LinearLayout::vertical(),
             bullet: Some(bullet.into()),
             margins: None,
+            intra_item_spacing: Mm(0.0),
         }
     }
 
@@ -1334,12 +2523,46 @@ impl UnorderedList {
         self.layout.push(point);
     }
 
+    /// Adds an item consisting of multiple elements to this list.
+    ///
+    /// The elements are arranged vertically, separated by the intra-item spacing, see
+    /// [`set_intra_item_spacing`][].
+    ///
+    /// [`set_intra_item_spacing`]: #method.set_intra_item_spacing
+    pub fn push_item<I>(&mut self, elements: I)
+    where
+        I: IntoIterator,
+        I::Item: IntoBoxedElement,
+    {
+        let mut layout = LinearLayout::vertical().spacing(self.intra_item_spacing.0);
+        layout.extend(elements);
+        self.push(layout);
+    }
+
     /// Adds an element to this list and returns the list.
     pub fn element<E: Element + 'static>(mut self, element: E) -> Self {
         self.push(element);
         self
     }
 
+    /// Sets the inter-item spacing, i. e. the vertical gap between two items of this list.
+    pub fn set_inter_item_spacing(&mut self, spacing: impl Into<Mm>) {
+        self.layout.set_list_item_spacing(spacing.into().0);
+    }
+
+    /// Sets the intra-item spacing, i. e. the vertical gap between the elements of a single list
+    /// item.
+    ///
+    /// The intra-item spacing is only used for items that are added with [`push_item`][] after
+    /// calling this method.  The gap between two items is set with
+    /// [`set_inter_item_spacing`][].
+    ///
+    /// [`push_item`]: #method.push_item
+    /// [`set_inter_item_spacing`]: #method.set_inter_item_spacing
+    pub fn set_intra_item_spacing(&mut self, spacing: impl Into<Mm>) {
+        self.intra_item_spacing = spacing.into();
+    }
+
     /// get margins
     pub fn get_margins(&self) -> Option<Margins> {
         self.margins
@@ -1351,8 +2574,22 @@ impl UnorderedList {
     }
 }
 
+impl PlainTextExtractor for UnorderedList {
+    fn extract(&self) -> String {
+        self.layout.extract()
+    }
+}
+
 impl Element for UnorderedList {
-    fn render(
+    fn reset_render(&mut self) {
+        self.layout.reset_render();
+    }
+
+    fn as_plain_text_extractor(&self) -> Option<&dyn PlainTextExtractor> {
+        Some(self)
+    }
+
+    fn render(
         &mut self,
         context: &Context,
         mut area: render::Area<'_>,
@@ -1405,6 +2642,61 @@ impl<E: Element + 'static> iter::FromIterator<E> for UnorderedList {
     }
 }
 
+/// Formats the given number as an English ordinal number, for example `1st`, `2nd`, `3rd` or
+/// `11th`.
+pub fn format_ordinal(n: usize) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", n, suffix)
+}
+
+/// Formats the given number as a lowercase roman numeral, for example `iv`.
+///
+/// See [`format_roman_upper`][] for more information.
+///
+/// [`format_roman_upper`]: fn.format_roman_upper.html
+pub fn format_roman_lower(n: usize) -> String {
+    format_roman_upper(n).to_lowercase()
+}
+
+/// Formats the given number as an uppercase roman numeral, for example `IV`.
+///
+/// As there is no roman numeral for zero, zero is formatted as `0`.  Numbers greater than 3999
+/// are formatted with additional leading `M`s.
+pub fn format_roman_upper(mut n: usize) -> String {
+    const NUMERALS: &[(usize, &str)] = &[
+        (1000, "M"),
+        (900, "CM"),
+        (500, "D"),
+        (400, "CD"),
+        (100, "C"),
+        (90, "XC"),
+        (50, "L"),
+        (40, "XL"),
+        (10, "X"),
+        (9, "IX"),
+        (5, "V"),
+        (4, "IV"),
+        (1, "I"),
+    ];
+    if n == 0 {
+        return "0".to_owned();
+    }
+    let mut result = String::new();
+    for &(value, numeral) in NUMERALS {
+        while n >= value {
+            result.push_str(numeral);
+            n -= value;
+        }
+    }
+    result
+}
+
 /// An ordered list of elements with arabic numbers.
 ///
 /// # Examples
@@ -1436,6 +2728,15 @@ impl<E: Element + 'static> iter::FromIterator<E> for UnorderedList {
 ///     .element(elements::Paragraph::new("third"));
 /// ```
 ///
+/// Custom bullets:
+/// ```
+/// use genpdf::elements;
+/// let list = elements::OrderedList::new()
+///     .with_bullet_format(|number, _| format!("Step {}:", number))
+///     .element(elements::Paragraph::new("first"))
+///     .element(elements::Paragraph::new("second"));
+/// ```
+///
 /// Nested list using a [`LinearLayout`][]:
 /// ```
 /// use genpdf::elements;
@@ -1464,9 +2765,77 @@ pub struct OrderedList {
     element_spacing: Mm,
     bullet_display: Option<String>,
     prefix: Option<String>,
+    bullet_format: Option<BulletFormatFn>,
+    named_counter: Option<String>,
+    pending_items: Vec<PendingItem>,
     // parent_bullet_display: Option<String>,
 }
 
+type BulletFormatFn = Box<dyn Fn(usize, Option<&str>) -> String>;
+
+/// An item of an ordered list with a named counter that has not been rendered yet.
+///
+/// The number is relative to the counter and `None` for nested lists.
+struct PendingItem {
+    number: Option<usize>,
+    point: BulletPoint<BoxedElement>,
+}
+
+/// Wraps a boxed element so that it can be used as a generic element.
+struct BoxedElement(Box<dyn Element>);
+
+impl Element for BoxedElement {
+    fn render(
+        &mut self,
+        context: &Context,
+        area: render::Area<'_>,
+        style: Style,
+    ) -> Result<RenderResult, Error> {
+        self.0.render(context, area, style)
+    }
+
+    fn get_probable_height(
+        &mut self,
+        style: style::Style,
+        context: &Context,
+        area: render::Area<'_>,
+    ) -> Mm {
+        self.0.get_probable_height(style, context, area)
+    }
+
+    fn get_min_width(&mut self, style: Style, context: &Context) -> Mm {
+        self.0.get_min_width(style, context)
+    }
+
+    fn get_natural_width(&mut self, style: Style, context: &Context) -> Mm {
+        self.0.get_natural_width(style, context)
+    }
+
+    fn preferred_size(&self) -> Option<Size> {
+        self.0.preferred_size()
+    }
+
+    fn space_before(&self) -> Mm {
+        self.0.space_before()
+    }
+
+    fn space_after(&self) -> Mm {
+        self.0.space_after()
+    }
+
+    fn display_name(&self) -> Option<&str> {
+        self.0.display_name()
+    }
+
+    fn as_plain_text_extractor(&self) -> Option<&dyn PlainTextExtractor> {
+        self.0.as_plain_text_extractor()
+    }
+
+    fn reset_render(&mut self) {
+        self.0.reset_render();
+    }
+}
+
 impl OrderedList {
     /// Creates a new ordered list starting at 1.
     pub fn new() -> OrderedList {
@@ -1483,18 +2852,121 @@ impl OrderedList {
             element_spacing: Mm(0.0),
             bullet_display: None,
             prefix: None,
+            bullet_format: None,
+            named_counter: None,
+            pending_items: Vec::new(),
             // parent_bullet_display: None,
         }
     }
 
-    /// bullet_margins
+    /// Creates a new ordered list that continues the numbering of the counter with the given
+    /// name.
+    ///
+    /// The start number is read from the list counter of the [`Context`][] when the list is
+    /// rendered, and the counter is updated with the number of items of this list.  This makes it
+    /// possible to continue the numbering of a previous list with the same counter name.  Use
+    /// [`Document::reset_list_counter`][] to restart the numbering.
+    ///
+    /// Until the list is rendered, the numbers are relative to the counter:  The first item has
+    /// the number 1, and [`next_number`][] and [`set_next_number`][] refer to these relative
+    /// numbers.  When the list is rendered, the value of the counter minus one is added to them.
+    ///
+    /// [`next_number`]: #method.next_number
+    /// [`set_next_number`]: #method.set_next_number
+    /// [`Context`]: ../struct.Context.html
+    /// [`Document::reset_list_counter`]: ../struct.Document.html#method.reset_list_counter
+    pub fn with_named_counter(name: &str) -> OrderedList {
+        let mut list = OrderedList::new();
+        list.named_counter = Some(name.to_owned());
+        list
+    }
+
+    /// Numbers the items of this list using the named counter, see [`with_named_counter`][].
+    ///
+    /// [`with_named_counter`]: #method.with_named_counter
+    fn apply_named_counter(&mut self, context: &Context) {
+        if let Some(name) = self.named_counter.take() {
+            let offset = context.list_counter(&name) - 1;
+            for mut item in mem::take(&mut self.pending_items) {
+                if let Some(number) = item.number {
+                    item.point.set_bullet(self.format_bullet(number + offset));
+                }
+                self.layout.push(item.point);
+            }
+            self.number += offset;
+            context.set_list_counter(&name, self.number);
+        }
+    }
+
+    /// Returns the bullet for the item with the given number.
+    fn format_bullet(&self, number: usize) -> String {
+        match (&self.bullet_format, self.get_prefix()) {
+            (Some(format_fn), prefix) => format_fn(number, prefix.as_deref()),
+            (None, Some(mut prefix)) => {
+                if !prefix.ends_with('.') {
+                    prefix = format!("{}.", prefix);
+                }
+                format!("{}{}", prefix, number)
+            }
+            (None, None) => format!("{}.", number),
+        }
+    }
+
+    /// Returns the number of items in this list.
+    pub fn item_count(&self) -> usize {
+        self.layout.len() + self.pending_items.len()
+    }
+
+    /// Removes all items from this list.
+    ///
+    /// The numbering is not reset.  Use [`set_next_number`][] to restart the numbering.
+    ///
+    /// [`set_next_number`]: #method.set_next_number
+    pub fn clear(&mut self) {
+        self.layout.clear();
+        self.pending_items.clear();
+    }
+
+    /// Returns the number of the next item that is added to this list.
+    pub fn next_number(&self) -> usize {
+        self.number
+    }
+
+    /// Sets the number of the next item that is added to this list.
+    pub fn set_next_number(&mut self, number: usize) {
+        self.number = number;
+    }
+
+    /// Sets the intra-item spacing.
+    ///
+    /// This is the same as [`set_intra_item_spacing`][].
+    ///
+    /// [`set_intra_item_spacing`]: #method.set_intra_item_spacing
     pub fn set_element_spacing(&mut self, element_spacing: Mm) {
-        self.element_spacing = element_spacing;
+        self.set_intra_item_spacing(element_spacing);
+    }
+
+    /// Sets the inter-item spacing, i. e. the vertical gap between two items of this list.
+    pub fn set_inter_item_spacing(&mut self, spacing: impl Into<Mm>) {
+        self.layout.set_list_item_spacing(spacing.into().0);
+    }
+
+    /// Sets the intra-item spacing, i. e. the vertical gap between the elements of a single list
+    /// item.
+    ///
+    /// The intra-item spacing is only used for items that are added with [`push_item`][] after
+    /// calling this method.  The gap between two items is set with
+    /// [`set_inter_item_spacing`][].
+    ///
+    /// [`push_item`]: #method.push_item
+    /// [`set_inter_item_spacing`]: #method.set_inter_item_spacing
+    pub fn set_intra_item_spacing(&mut self, spacing: impl Into<Mm>) {
+        self.element_spacing = spacing.into();
     }
 
     /// set list_item_margin
     pub fn set_list_item_spacing(&mut self, spacing: f64) {
-        self.layout.set_list_item_spacing(spacing)
+        self.layout.set_list_item_spacing(spacing);
     }
 
     /// get list_item_margin
@@ -1513,6 +2985,42 @@ impl OrderedList {
         self.prefix.clone()
     }
 
+    /// Sets the function that formats the bullet of the list items.
+    ///
+    /// The function is called with the number and the prefix of the item and returns the bullet
+    /// text, for example `Step 1:`.  It is only applied to items that are added after calling this
+    /// method.  Per default, the bullet consists of the prefix and the number separated and
+    /// followed by a dot.
+    pub fn set_bullet_format<F>(&mut self, format_fn: F)
+    where
+        F: Fn(usize, Option<&str>) -> String + 'static,
+    {
+        self.bullet_format = Some(Box::new(format_fn));
+    }
+
+    /// Sets the function that formats the bullet of the list items and returns the list.
+    ///
+    /// See [`set_bullet_format`][] for more information.
+    ///
+    /// [`set_bullet_format`]: #method.set_bullet_format
+    pub fn with_bullet_format<F>(mut self, format_fn: F) -> Self
+    where
+        F: Fn(usize, Option<&str>) -> String + 'static,
+    {
+        self.set_bullet_format(format_fn);
+        self
+    }
+
+    /// Formats the bullets of the list items as English ordinal numbers, for example `1st` or
+    /// `2nd`, and returns the list.
+    ///
+    /// See [`format_ordinal`][] for more information.
+    ///
+    /// [`format_ordinal`]: fn.format_ordinal.html
+    pub fn with_ordinal_style(self) -> Self {
+        self.with_bullet_format(|number, _| format_ordinal(number))
+    }
+
     /// get bullet display
     pub fn get_bullet_display(&self) -> Option<String> {
         self.bullet_display.clone()
@@ -1520,6 +3028,15 @@ impl OrderedList {
 
     /// Push OrderedList/UnordredList to the list.
     pub fn push_list<E: Element + 'static>(&mut self, list: E) {
+        if self.named_counter.is_some() {
+            let mut point = BulletPoint::new(BoxedElement(Box::new(list)));
+            point.set_bullet("".to_string());
+            self.pending_items.push(PendingItem {
+                number: None,
+                point,
+            });
+            return;
+        }
         let mut point = BulletPoint::new(list);
         // point.indent = Mm(0.0); //point.indent / 2.0;
         // point.bullet_space = Mm(0.0);
@@ -1530,18 +3047,21 @@ impl OrderedList {
 
     /// Adds an element to this list.
     pub fn push<E: Element + 'static>(&mut self, element: E) {
-        let mut point = BulletPoint::new(element);
-        let bullet = match self.get_prefix() {
-            Some(mut prefix) => {
-                if !prefix.ends_with(".") {
-                    prefix = format!("{}.", prefix);
-                }
-                format!("{}{}", prefix, self.number)
-            }
-            None => format!("{}.", self.number),
-        };
-
+        let bullet = self.format_bullet(self.number);
         self.bullet_display = Some(bullet.to_owned());
+        if self.named_counter.is_some() {
+            // The bullet is updated with the final number once the list is rendered
+            let mut point = BulletPoint::new(BoxedElement(Box::new(element)));
+            point.set_bullet(bullet);
+            point.set_style(self.bullet_style);
+            self.pending_items.push(PendingItem {
+                number: Some(self.number),
+                point,
+            });
+            self.number += 1;
+            return;
+        }
+        let mut point = BulletPoint::new(element);
         point.set_bullet(bullet);
         point.set_style(self.bullet_style);
         // point.set_margins(margins);
@@ -1549,6 +3069,22 @@ impl OrderedList {
         self.number += 1;
     }
 
+    /// Adds an item consisting of multiple elements to this list.
+    ///
+    /// The elements are arranged vertically, separated by the intra-item spacing, see
+    /// [`set_intra_item_spacing`][].
+    ///
+    /// [`set_intra_item_spacing`]: #method.set_intra_item_spacing
+    pub fn push_item<I>(&mut self, elements: I)
+    where
+        I: IntoIterator,
+        I::Item: IntoBoxedElement,
+    {
+        let mut layout = LinearLayout::vertical().spacing(self.element_spacing.0);
+        layout.extend(elements);
+        self.push(layout);
+    }
+
     /// Adds an element to this list and returns the list.
     pub fn element<E: Element + 'static>(mut self, element: E) -> Self {
         self.push(element);
@@ -1576,13 +3112,28 @@ impl OrderedList {
     }
 }
 
+impl PlainTextExtractor for OrderedList {
+    fn extract(&self) -> String {
+        self.layout.extract()
+    }
+}
+
 impl Element for OrderedList {
+    fn reset_render(&mut self) {
+        self.layout.reset_render();
+    }
+
+    fn as_plain_text_extractor(&self) -> Option<&dyn PlainTextExtractor> {
+        Some(self)
+    }
+
     fn render(
         &mut self,
         context: &Context,
         mut area: render::Area<'_>,
         style: Style,
     ) -> Result<RenderResult, Error> {
+        self.apply_named_counter(context);
         if let Some(margins) = self.get_margins() {
             area.add_margins(margins);
         }
@@ -1600,7 +3151,15 @@ impl Element for OrderedList {
         context: &Context,
         area: render::Area<'_>,
     ) -> Mm {
-        let mut height = self.layout.get_probable_height(style, context, area);
+        // The named counter is only applied when the list is rendered, so we measure the pending
+        // items directly.
+        let mut height = self
+            .layout
+            .get_probable_height(style, context, area.clone());
+        for item in &mut self.pending_items {
+            height += item.point.get_probable_height(style, context, area.clone())
+                + Mm(self.layout.list_item_spacing);
+        }
         if let Some(margins) = self.get_margins() {
             height += margins.top + margins.bottom;
         }
@@ -1630,6 +3189,142 @@ impl<E: Element + 'static> iter::FromIterator<E> for OrderedList {
     }
 }
 
+/// A definition list of terms and their descriptions.
+///
+/// Each entry consists of a term that is printed in bold and flush-left, and a description
+/// element that is indented by the description indent, see [`set_description_indent`][].  This is
+/// similar to the `<dl>`, `<dt>` and `<dd>` elements in HTML.
+///
+/// # Examples
+///
+/// With setters:
+/// ```
+/// use genpdf::elements;
+/// let mut list = elements::DefinitionList::new();
+/// list.push("PDF", elements::Paragraph::new("Portable Document Format"));
+/// list.push("TTF", elements::Paragraph::new("TrueType Font"));
+/// ```
+///
+/// Chained:
+/// ```
+/// use genpdf::elements;
+/// let list = elements::DefinitionList::new()
+///     .with_description_indent(15)
+///     .entry("PDF", elements::Paragraph::new("Portable Document Format"))
+///     .entry("TTF", elements::Paragraph::new("TrueType Font"));
+/// ```
+///
+/// [`set_description_indent`]: #method.set_description_indent
+pub struct DefinitionList {
+    layout: LinearLayout,
+    description_indent: Mm,
+}
+
+impl DefinitionList {
+    /// Creates a new empty definition list with the default description indent of 10 mm.
+    pub fn new() -> DefinitionList {
+        DefinitionList {
+            layout: LinearLayout::vertical(),
+            description_indent: Mm::from(10),
+        }
+    }
+
+    /// Returns the number of entries in this list.
+    pub fn entry_count(&self) -> usize {
+        self.layout.len()
+    }
+
+    /// Removes all entries from this list.
+    pub fn clear(&mut self) {
+        self.layout.clear();
+    }
+
+    /// Sets the indent of the descriptions relative to the terms.
+    ///
+    /// The indent is only used for entries that are added with [`push`][] after calling this
+    /// method.
+    ///
+    /// [`push`]: #method.push
+    pub fn set_description_indent(&mut self, indent: impl Into<Mm>) {
+        self.description_indent = indent.into();
+    }
+
+    /// Sets the indent of the descriptions relative to the terms and returns the list.
+    pub fn with_description_indent(mut self, indent: impl Into<Mm>) -> Self {
+        self.set_description_indent(indent);
+        self
+    }
+
+    /// Sets the spacing between two entries of this list.
+    pub fn set_entry_spacing(&mut self, spacing: impl Into<Mm>) {
+        self.layout.set_list_item_spacing(spacing.into().0);
+    }
+
+    /// Adds an entry with the given term and description to this list.
+    pub fn push<E: Element + 'static>(&mut self, term: impl Into<StyledString>, description: E) {
+        let mut term = term.into();
+        term.style.set_bold(true);
+        let entry = LinearLayout::vertical()
+            .element(Paragraph::new(term))
+            .element(description.padded(Margins::trbl(0, 0, 0, self.description_indent)));
+        self.layout.push(entry);
+    }
+
+    /// Adds an entry with the given term and description to this list and returns the list.
+    pub fn entry<E: Element + 'static>(
+        mut self,
+        term: impl Into<StyledString>,
+        description: E,
+    ) -> Self {
+        self.push(term, description);
+        self
+    }
+}
+
+impl PlainTextExtractor for DefinitionList {
+    fn extract(&self) -> String {
+        self.layout.extract()
+    }
+}
+
+impl Element for DefinitionList {
+    fn reset_render(&mut self) {
+        self.layout.reset_render();
+    }
+
+    fn as_plain_text_extractor(&self) -> Option<&dyn PlainTextExtractor> {
+        Some(self)
+    }
+
+    fn render(
+        &mut self,
+        context: &Context,
+        area: render::Area<'_>,
+        style: Style,
+    ) -> Result<RenderResult, Error> {
+        self.layout.render(context, area, style)
+    }
+
+    fn get_probable_height(
+        &mut self,
+        style: style::Style,
+        context: &Context,
+        area: render::Area<'_>,
+    ) -> Mm {
+        self.layout.get_probable_height(style, context, area)
+    }
+
+    fn get_natural_width(&mut self, style: Style, context: &Context) -> Mm {
+        self.layout.get_natural_width(style, context)
+    }
+}
+
+impl Default for DefinitionList {
+    fn default() -> DefinitionList {
+        DefinitionList::new()
+    }
+}
+
 /// A bullet point in a list.
 ///
 /// This is a helper element for the [`OrderedList`][] and [`UnorderedList`][] types, but you can
@@ -1702,6 +3397,23 @@ impl<E: Element> BulletPoint<E> {
 }
 
 impl<E: Element> Element for BulletPoint<E> {
+    fn get_min_width(&mut self, style: Style, context: &Context) -> Mm {
+        self.indent + self.element.get_min_width(style, context)
+    }
+
+    fn get_natural_width(&mut self, style: Style, context: &Context) -> Mm {
+        self.indent + self.element.get_natural_width(style, context)
+    }
+
+    fn reset_render(&mut self) {
+        self.bullet_rendered = false;
+        self.element.reset_render();
+    }
+
+    fn as_plain_text_extractor(&self) -> Option<&dyn PlainTextExtractor> {
+        self.element.as_plain_text_extractor()
+    }
+
     fn render(
         &mut self,
         context: &Context,
@@ -1737,16 +3449,8 @@ impl<E: Element> Element for BulletPoint<E> {
             )?;
 
             if style.is_underline() {
-                let ls = LineStyle::new().with_thickness(0.2);
-                let left = x;
-                let right = left + bullet_width;
-                let line_offset = ls.thickness() / 2.0;
-                let bottom = style.metrics(&context.font_cache).line_height;
-                let bottom_points = vec![
-                    Position::new(left, bottom - line_offset),
-                    Position::new(right, bottom - line_offset),
-                ];
-                area.draw_line(bottom_points, ls);
+                let metrics = style.metrics(&context.font_cache);
+                let ls = draw_underline(&area, style, metrics, x, bullet_width);
                 result.size.height += ls.thickness();
             }
             self.bullet_rendered = true;
@@ -1797,6 +3501,13 @@ pub trait CellDecorator {
 
     /// Styles the cell with the given indizes thas has been rendered within the given area and the
     /// given row height and return the total row height.
+    ///
+    /// `borders` contains the borders of the cell if they have been overridden using
+    /// [`TableCell::with_borders`][].  In this case, they should be used instead of the borders
+    /// of the decorator.
+    ///
+    /// [`TableCell::with_borders`]: struct.TableCell.html#method.with_borders
+    #[allow(clippy::too_many_arguments)]
     fn decorate_cell(
         &mut self,
         column: usize,
@@ -1805,7 +3516,51 @@ pub trait CellDecorator {
         area: render::Area<'_>,
         row_height: Mm,
         bg_color: Option<style::Color>,
+        borders: Option<Borders>,
     ) -> Mm;
+
+    /// Prepares a cell that spans `span` columns, starting with the given column, and returns the
+    /// area for rendering the cell.
+    ///
+    /// `borders` contains the overridden borders of the cell, see [`decorate_cell`][].  The
+    /// default implementation ignores them and calls [`prepare_cell`][] for the first column.
+    ///
+    /// [`decorate_cell`]: #tymethod.decorate_cell
+    /// [`prepare_cell`]: #method.prepare_cell
+    fn prepare_spanning_cell<'p>(
+        &self,
+        column: usize,
+        span: usize,
+        row: usize,
+        area: render::Area<'p>,
+        borders: Option<Borders>,
+    ) -> render::Area<'p> {
+        let _ = (span, borders);
+        self.prepare_cell(column, row, area)
+    }
+
+    /// Styles a cell that spans `span` columns, starting with the given column, and returns the
+    /// total row height.
+    ///
+    /// `borders` contains the overridden borders of the cell, see [`decorate_cell`][].  The
+    /// default implementation calls [`decorate_cell`][] for the first column.
+    ///
+    /// [`decorate_cell`]: #tymethod.decorate_cell
+    #[allow(clippy::too_many_arguments)]
+    fn decorate_spanning_cell(
+        &mut self,
+        column: usize,
+        span: usize,
+        row: usize,
+        has_more: bool,
+        area: render::Area<'_>,
+        row_height: Mm,
+        bg_color: Option<style::Color>,
+        borders: Option<Borders>,
+    ) -> Mm {
+        let _ = span;
+        self.decorate_cell(column, row, has_more, area, row_height, bg_color, borders)
+    }
 }
 
 /// A cell decorator that draws frames around table cells.
@@ -1818,24 +3573,23 @@ pub trait CellDecorator {
 #[derive(Clone, Debug, Default)]
 pub struct FrameCellDecorator {
     inner: bool,
-    outer: bool,
+    outer: Borders,
     // cont: bool,
     line_style: LineStyle,
+    horizontal_style: Option<LineStyle>,
+    vertical_style: Option<LineStyle>,
     num_columns: usize,
     num_rows: usize,
     last_row: Option<usize>,
+    even_color: Option<style::Color>,
+    odd_color: Option<style::Color>,
 }
 
 impl FrameCellDecorator {
     /// Creates a new frame cell decorator with the given settings for inner, outer and
     /// continuation borders.
     pub fn new(inner: bool, outer: bool) -> FrameCellDecorator {
-        FrameCellDecorator {
-            inner,
-            outer,
-            // cont,
-            ..Default::default()
-        }
+        FrameCellDecorator::with_line_style(inner, outer, LineStyle::new())
     }
 
     /// Creates a new frame cell decorator with the given border settings, as well as a line style.
@@ -1845,58 +3599,148 @@ impl FrameCellDecorator {
         // cont: bool,
         line_style: impl Into<LineStyle>,
     ) -> FrameCellDecorator {
+        let line_style = line_style.into();
         Self {
             inner,
-            outer,
+            outer: if outer {
+                Borders::outer(line_style)
+            } else {
+                Borders::none()
+            },
             // cont,
-            line_style: line_style.into(),
+            line_style,
             ..Default::default()
         }
     }
 
-    fn print_left(&self, column: usize) -> bool {
-        if column == 0 {
-            self.outer
-        } else {
-            self.inner
+    /// Creates a new frame cell decorator with the given line style for the inner borders and
+    /// the given outer borders.
+    ///
+    /// If `inner` is `None`, no inner borders are drawn.  The outer borders can be set per edge,
+    /// see [`Borders`][].
+    ///
+    /// [`Borders`]: ../struct.Borders.html
+    pub fn with_borders(inner: Option<LineStyle>, outer: Borders) -> FrameCellDecorator {
+        Self {
+            inner: inner.is_some(),
+            outer,
+            line_style: inner.unwrap_or_default(),
+            ..Default::default()
         }
     }
 
-    fn print_right(&self, column: usize) -> bool {
-        if column + 1 == self.num_columns {
-            self.outer
-        } else {
-            false
+    /// Creates a new frame cell decorator that draws inner and outer borders with different line
+    /// styles.
+    pub fn with_dual_style(
+        inner: impl Into<LineStyle>,
+        outer: impl Into<LineStyle>,
+    ) -> FrameCellDecorator {
+        FrameCellDecorator::with_borders(Some(inner.into()), Borders::outer(outer))
+    }
+
+    /// Sets the line style for the horizontal borders, i. e. the top and bottom borders of the
+    /// cells, and returns the decorator.
+    ///
+    /// The style replaces the style of the inner horizontal borders and of the outer top and
+    /// bottom borders, if they are drawn.
+    pub fn with_horizontal_style(mut self, line_style: impl Into<LineStyle>) -> Self {
+        let line_style = line_style.into();
+        self.horizontal_style = Some(line_style);
+        self.outer.top = self.outer.top.map(|_| line_style);
+        self.outer.bottom = self.outer.bottom.map(|_| line_style);
+        self
+    }
+
+    /// Sets the line style for the vertical borders, i. e. the left and right borders of the
+    /// cells, and returns the decorator.
+    ///
+    /// The style replaces the style of the inner vertical borders and of the outer left and right
+    /// borders, if they are drawn.
+    pub fn with_vertical_style(mut self, line_style: impl Into<LineStyle>) -> Self {
+        let line_style = line_style.into();
+        self.vertical_style = Some(line_style);
+        self.outer.left = self.outer.left.map(|_| line_style);
+        self.outer.right = self.outer.right.map(|_| line_style);
+        self
+    }
+
+    /// Sets the background colors for the rows with an even and an odd index and returns the
+    /// decorator.
+    ///
+    /// The first row has the index zero, so it uses the `even` color.  These colors have the
+    /// lowest precedence:  Cells with a background color, for example set with
+    /// [`TableCell::new`][] or [`TableLayout::push_row_with_background`][], keep their color, and
+    /// the colors set with [`TableLayout::set_alternating_row_colors_from`][] are used for the rows
+    /// that they apply to.
+    ///
+    /// [`TableCell::new`]: struct.TableCell.html#method.new
+    /// [`TableLayout::push_row_with_background`]: struct.TableLayout.html#method.push_row_with_background
+    /// [`TableLayout::set_alternating_row_colors_from`]: struct.TableLayout.html#method.set_alternating_row_colors_from
+    pub fn with_alternating_colors(
+        mut self,
+        even: Option<style::Color>,
+        odd: Option<style::Color>,
+    ) -> Self {
+        self.even_color = even;
+        self.odd_color = odd;
+        self
+    }
+
+    fn horizontal_inner_style(&self) -> LineStyle {
+        self.horizontal_style.unwrap_or(self.line_style)
+    }
+
+    fn vertical_inner_style(&self) -> LineStyle {
+        self.vertical_style.unwrap_or(self.line_style)
+    }
+
+    fn left_style(&self, column: usize) -> Option<LineStyle> {
+        if column == 0 {
+            self.outer.left
+        } else {
+            Some(self.vertical_inner_style()).filter(|_| self.inner)
+        }
+    }
+
+    fn right_style(&self, column: usize) -> Option<LineStyle> {
+        if column + 1 == self.num_columns {
+            self.outer.right
+        } else {
+            None
         }
     }
 
-    fn print_top(&self, row: usize, has_more: bool) -> bool {
+    fn top_style(&self, row: usize, has_more: bool) -> Option<LineStyle> {
         if has_more {
-            self.outer
+            self.outer.top
         } else if self.last_row.map(|last_row| row > last_row).unwrap_or(true) {
             if row == 0 {
-                self.outer
+                self.outer.top
             } else {
-                self.inner
+                Some(self.horizontal_inner_style()).filter(|_| self.inner)
             }
         } else {
             // self.cont
-            true
+            Some(self.horizontal_inner_style())
         }
     }
 
-    fn print_bottom(&self, row: usize, has_more: bool) -> bool {
+    fn bottom_style(&self, row: usize, has_more: bool) -> Option<LineStyle> {
         if has_more {
             // self.cont
-            true
+            Some(self.horizontal_inner_style())
         } else if row + 1 == self.num_rows {
-            self.outer
+            self.outer.bottom
         } else {
-            false
+            None
         }
     }
 }
 
+fn line_thickness(line_style: Option<LineStyle>) -> Mm {
+    line_style.map(|l| l.thickness()).unwrap_or_default()
+}
+
 impl CellDecorator for FrameCellDecorator {
     fn set_table_size(&mut self, num_columns: usize, num_rows: usize) {
         self.num_columns = num_columns;
@@ -1907,32 +3751,43 @@ impl CellDecorator for FrameCellDecorator {
         &self,
         column: usize,
         row: usize,
+        area: render::Area<'p>,
+    ) -> render::Area<'p> {
+        self.prepare_spanning_cell(column, 1, row, area, None)
+    }
+
+    fn prepare_spanning_cell<'p>(
+        &self,
+        column: usize,
+        span: usize,
+        row: usize,
         mut area: render::Area<'p>,
+        borders: Option<Borders>,
     ) -> render::Area<'p> {
-        let margin = self.line_style.thickness();
+        let right_style = borders
+            .map(|b| b.right)
+            .unwrap_or_else(|| self.right_style(column + span.max(1) - 1));
+        if right_style.is_none() {
+            // Fix to avoid a gap betwen the right border and the next cell
+            area.set_width(area.size().width + self.vertical_inner_style().thickness());
+        }
         let margins = Margins::trbl(
-            if self.print_top(row, false) {
-                margin
-            } else {
-                0.into()
-            },
-            if self.print_right(column) {
-                margin
-            } else {
-                // Fix to avoid a gap betwen the right border and the next cell
-                area.set_width(area.size().width + margin);
-                0.into()
-            },
-            if self.print_bottom(row, false) {
-                margin
-            } else {
-                0.into()
-            },
-            if self.print_left(column) {
-                margin
-            } else {
-                0.into()
-            },
+            line_thickness(
+                borders
+                    .map(|b| b.top)
+                    .unwrap_or_else(|| self.top_style(row, false)),
+            ),
+            line_thickness(right_style),
+            line_thickness(
+                borders
+                    .map(|b| b.bottom)
+                    .unwrap_or_else(|| self.bottom_style(row, false)),
+            ),
+            line_thickness(
+                borders
+                    .map(|b| b.left)
+                    .unwrap_or_else(|| self.left_style(column)),
+            ),
         );
         area.add_margins(margins);
         area
@@ -1946,18 +3801,42 @@ impl CellDecorator for FrameCellDecorator {
         area: render::Area<'_>,
         row_height: Mm,
         bg_color: Option<style::Color>,
+        borders: Option<Borders>,
+    ) -> Mm {
+        self.decorate_spanning_cell(
+            column, 1, row, has_more, area, row_height, bg_color, borders,
+        )
+    }
+
+    fn decorate_spanning_cell(
+        &mut self,
+        column: usize,
+        span: usize,
+        row: usize,
+        has_more: bool,
+        area: render::Area<'_>,
+        row_height: Mm,
+        bg_color: Option<style::Color>,
+        borders: Option<Borders>,
     ) -> Mm {
-        let print_top = self.print_top(row, has_more);
-        let print_bottom = self.print_bottom(row, has_more);
-        let print_left = self.print_left(column);
-        let print_right = self.print_right(column);
-
-        // println!("----------------------------------------------------------------------------------------------------------------------------------------");
-        // println!(
-        //     "Cell: {},{}: top={}, bottom={}, left={}, right={}",
-        //     column, row, print_top, print_bottom, print_left, print_right
-        // );
-        // println!("----------------------------------------------------------------------------------------------------------------------------------------");
+        let last_column = column + span.max(1) - 1;
+        let bg_color = bg_color.or(if row % 2 == 0 {
+            self.even_color
+        } else {
+            self.odd_color
+        });
+        let top_style = borders
+            .map(|b| b.top)
+            .unwrap_or_else(|| self.top_style(row, has_more));
+        let bottom_style = borders
+            .map(|b| b.bottom)
+            .unwrap_or_else(|| self.bottom_style(row, has_more));
+        let left_style = borders
+            .map(|b| b.left)
+            .unwrap_or_else(|| self.left_style(column));
+        let right_style = borders
+            .map(|b| b.right)
+            .unwrap_or_else(|| self.right_style(last_column));
 
         let size = area.size();
         let line_offset = self.line_style.thickness() / 2.0;
@@ -1965,17 +3844,9 @@ impl CellDecorator for FrameCellDecorator {
         let left = Mm::from(0);
         let right = size.width;
         let top = Mm::from(0);
-        let bottom = row_height
-            + if print_bottom {
-                self.line_style.thickness()
-            } else {
-                0.into()
-            }
-            + if print_top {
-                self.line_style.thickness()
-            } else {
-                0.into()
-            };
+        let bottom = row_height + line_thickness(bottom_style) + line_thickness(top_style);
+
+        let total_height = row_height + line_thickness(top_style) + line_thickness(bottom_style);
 
         if let Some(color) = bg_color {
             let bottom_left = Position::new(left + line_offset, bottom - line_offset);
@@ -1983,65 +3854,47 @@ impl CellDecorator for FrameCellDecorator {
             let top_right = Position::new(right - line_offset, top + line_offset);
             let bottom_right = Position::new(right - line_offset, bottom - line_offset);
 
-            // println!("decorateCell bottom_left: {:?}", bottom_left);
-            // println!("decorateCell top_left: {:?}", top_left);
-            // println!("decorateCell top_right: {:?}", top_right);
-            // println!("decorateCell bottom_right: {:?}", bottom_right);
             let filled_shape_points = vec![bottom_left, top_left, top_right, bottom_right];
-            // println!("----------------------------------------------------------------------------------------------------------------------------------------");
-            // println!(
-            //     "decorateCell, filled_shape_points: {:?}",
-            //     filled_shape_points
-            // );
-            // println!("----------------------------------------------------------------------------------------------------------------------------------------");
             area.draw_filled_shape(filled_shape_points, Some(color), self.line_style);
         }
 
-        let mut total_height = row_height;
-
-        let top_points = vec![
-            Position::new(left, top + line_offset),
-            Position::new(right, top + line_offset),
-        ];
-        if print_top {
-            // println!("decorateCell, top_points: {:?}", top_points);
-            area.draw_line(top_points, self.line_style);
-            total_height += self.line_style.thickness();
-        }
-        let right_points = vec![
-            Position::new(right - line_offset, top),
-            Position::new(right - line_offset, bottom),
-        ];
+        if let Some(line_style) = top_style {
+            let line_offset = line_style.thickness() / 2.0;
+            let top_points = vec![
+                Position::new(left, top + line_offset),
+                Position::new(right, top + line_offset),
+            ];
+            area.draw_line(top_points, line_style);
+        }
 
-        if print_right {
-            // println!("----------------------------------------------------------------------------------------------------------------------------------------");
-            // println!("decorateCell, right_points: {:?}", right_points);
-            // println!("----------------------------------------------------------------------------------------------------------------------------------------");
-            area.draw_line(right_points, self.line_style);
+        if let Some(line_style) = right_style {
+            let line_offset = line_style.thickness() / 2.0;
+            let right_points = vec![
+                Position::new(right - line_offset, top),
+                Position::new(right - line_offset, bottom),
+            ];
+            area.draw_line(right_points, line_style);
         }
 
-        let bottom_points = vec![
-            Position::new(left, bottom - line_offset),
-            Position::new(right, bottom - line_offset),
-        ];
-        if print_bottom {
-            // println!("----------------------------------------------------------------------------------------------------------------------------------------");
-            // println!("decorateCell, bottom_points: {:?}", bottom_points);
-            // println!("----------------------------------------------------------------------------------------------------------------------------------------");
-            area.draw_line(bottom_points, self.line_style);
-            total_height += self.line_style.thickness();
+        if let Some(line_style) = bottom_style {
+            let line_offset = line_style.thickness() / 2.0;
+            let bottom_points = vec![
+                Position::new(left, bottom - line_offset),
+                Position::new(right, bottom - line_offset),
+            ];
+            area.draw_line(bottom_points, line_style);
         }
 
-        let left_points = vec![
-            Position::new(left + line_offset, top),
-            Position::new(left + line_offset, bottom),
-        ];
-        // println!("decorateCell, left_points: {:?}", left_points);
-        if print_left {
-            area.draw_line(left_points, self.line_style);
+        if let Some(line_style) = left_style {
+            let line_offset = line_style.thickness() / 2.0;
+            let left_points = vec![
+                Position::new(left + line_offset, top),
+                Position::new(left + line_offset, bottom),
+            ];
+            area.draw_line(left_points, line_style);
         }
 
-        if column + 1 == self.num_columns {
+        if last_column + 1 == self.num_columns {
             self.last_row = Some(row);
         }
 
@@ -2060,7 +3913,7 @@ impl CellDecorator for FrameCellDecorator {
 /// With setters:
 /// ```
 /// use genpdf::elements;
-/// let mut table = elements::TableLayout::new(vec![1, 1]);
+/// let mut table = elements::TableLayout::new(elements::ColumnWidths::Weights(vec![1, 1]));
 /// let mut row = table.row();
 /// row.push_element(elements::Paragraph::new("Cell 1"));
 /// row.push_element(elements::Paragraph::new("Cell 2"));
@@ -2070,7 +3923,7 @@ impl CellDecorator for FrameCellDecorator {
 /// Chained:
 /// ```
 /// use genpdf::elements;
-/// let table = elements::TableLayout::new(vec![1, 1])
+/// let table = elements::TableLayout::new(elements::ColumnWidths::Weights(vec![1, 1]))
 ///     .row()
 ///     .element(elements::Paragraph::new("Cell 1"))
 ///     .element(elements::Paragraph::new("Cell 2"))
@@ -2085,16 +3938,34 @@ impl CellDecorator for FrameCellDecorator {
 pub struct TableLayoutRow<'a> {
     table_layout: &'a mut TableLayout,
     cells: Vec<TableCell>,
+    min_height: Option<Mm>,
 }
 
 /// A cell of a table layout.
+///
+/// By default, the borders of a cell are drawn by the [`CellDecorator`][] of the table.  You can
+/// override them for a single cell using [`with_borders`][], [`no_borders`][] or
+/// [`all_borders`][].  The overridden borders replace all four edges:  edges with a line style are
+/// drawn with this style even if the decorator would not draw them, and edges without a line
+/// style are not drawn.
+///
+/// [`CellDecorator`]: trait.CellDecorator.html
+/// [`with_borders`]: #method.with_borders
+/// [`no_borders`]: #method.no_borders
+/// [`all_borders`]: #method.all_borders
+///
+/// A cell can span multiple columns and rows, see [`with_colspan`][] and [`with_rowspan`][].
+///
+/// [`with_colspan`]: #method.with_colspan
+/// [`with_rowspan`]: #method.with_rowspan
 pub struct TableCell {
     element: Box<dyn Element>,
     background_color: Option<style::Color>,
-    draw_left_border: bool,
-    draw_right_border: bool,
-    draw_top_border: bool,
-    draw_bottom_border: bool,
+    borders: Option<Borders>,
+    colspan: usize,
+    rowspan: usize,
+    padding: Option<Margins>,
+    vertical_alignment: VerticalAlignment,
 }
 
 impl TableCell {
@@ -2103,34 +3974,255 @@ impl TableCell {
         TableCell {
             element,
             background_color,
-            draw_left_border: true,
-            draw_right_border: true,
-            draw_top_border: true,
-            draw_bottom_border: true,
+            borders: None,
+            colspan: 1,
+            rowspan: 1,
+            padding: None,
+            vertical_alignment: VerticalAlignment::Top,
+        }
+    }
+
+    /// Creates a new cell containing a paragraph with the given text.
+    pub fn text(text: impl Into<String>, background_color: Option<style::Color>) -> TableCell {
+        TableCell::new(Box::new(Paragraph::new(text.into())), background_color)
+    }
+
+    /// Creates a new cell containing a paragraph with the given text in bold.
+    pub fn bold_text(text: impl Into<String>, background_color: Option<style::Color>) -> TableCell {
+        TableCell::new(
+            Box::new(Paragraph::new(text.into()).styled(style::Effect::Bold)),
+            background_color,
+        )
+    }
+
+    /// Creates a new cell containing a paragraph with the given text that is centered
+    /// horizontally.
+    pub fn centered_text(
+        text: impl Into<String>,
+        background_color: Option<style::Color>,
+    ) -> TableCell {
+        TableCell::new(
+            Box::new(Paragraph::new(text.into()).aligned(Alignment::Center)),
+            background_color,
+        )
+    }
+
+    /// Creates a new cell with the given element that does not draw any borders.
+    pub fn no_borders(element: impl IntoBoxedElement) -> TableCell {
+        TableCell::new(element.into_boxed_element(), None).with_borders(Borders::none())
+    }
+
+    /// Creates a new cell with the given element that draws all borders with the given line
+    /// style.
+    pub fn all_borders(element: impl IntoBoxedElement, line_style: LineStyle) -> TableCell {
+        TableCell::new(element.into_boxed_element(), None).with_borders(Borders::all(line_style))
+    }
+
+    /// Sets the borders of this cell, overriding the borders of the cell decorator.
+    ///
+    /// Edges without a line style are not drawn, see [`Borders`][].
+    ///
+    /// [`Borders`]: ../struct.Borders.html
+    pub fn set_borders(&mut self, borders: Borders) {
+        self.borders = Some(borders);
+    }
+
+    /// Sets the borders of this cell, overriding the borders of the cell decorator, and returns
+    /// the cell.
+    pub fn with_borders(mut self, borders: Borders) -> Self {
+        self.set_borders(borders);
+        self
+    }
+
+    /// Returns the borders of this cell if they override the borders of the cell decorator.
+    pub fn borders(&self) -> Option<Borders> {
+        self.borders
+    }
+
+    /// Sets the padding between the borders of this cell and its element.
+    ///
+    /// This has the same effect as wrapping the element in a [`PaddedElement`][].
+    ///
+    /// [`PaddedElement`]: struct.PaddedElement.html
+    pub fn set_padding(&mut self, padding: impl Into<Margins>) {
+        self.padding = Some(padding.into());
+    }
+
+    /// Sets the padding between the borders of this cell and its element and returns the cell.
+    pub fn with_padding(mut self, padding: impl Into<Margins>) -> Self {
+        self.set_padding(padding);
+        self
+    }
+
+    /// Returns the padding of this cell, if set.
+    pub fn padding(&self) -> Option<Margins> {
+        self.padding
+    }
+
+    /// Sets the vertical alignment of the element of this cell within the row.
+    ///
+    /// The alignment is based on the probable height of the element, see
+    /// [`Element::get_probable_height`][].
+    ///
+    /// [`Element::get_probable_height`]: ../trait.Element.html#method.get_probable_height
+    pub fn set_vertical_alignment(&mut self, vertical_alignment: VerticalAlignment) {
+        self.vertical_alignment = vertical_alignment;
+    }
+
+    /// Sets the vertical alignment of the element of this cell within the row and returns the
+    /// cell.
+    pub fn with_vertical_alignment(mut self, vertical_alignment: VerticalAlignment) -> Self {
+        self.set_vertical_alignment(vertical_alignment);
+        self
+    }
+
+    /// Returns the vertical alignment of this cell.
+    pub fn vertical_alignment(&self) -> VerticalAlignment {
+        self.vertical_alignment
+    }
+
+    /// Returns the vertical offset of the element of this cell with the given probable height in
+    /// a row with the given height.
+    fn vertical_offset(&self, height: Mm, row_height: Mm) -> Mm {
+        // Continued elements may report a probable height of zero, so we keep them at the top.
+        if height <= Mm(0.0) || height >= row_height {
+            return Mm(0.0);
+        }
+        match self.vertical_alignment {
+            VerticalAlignment::Top => Mm(0.0),
+            VerticalAlignment::Middle => (row_height - height) / 2.0,
+            VerticalAlignment::Bottom => row_height - height,
+        }
+    }
+
+    /// Returns the area for the element of this cell within the given cell area.
+    fn element_area<'p>(&self, mut area: render::Area<'p>) -> render::Area<'p> {
+        if let Some(padding) = self.padding {
+            area.add_margins(Margins {
+                bottom: Mm(0.0),
+                ..padding
+            });
+        }
+        area
+    }
+
+    /// Returns the natural width of this cell, including the padding.
+    fn natural_width(&mut self, style: Style, context: &Context) -> Mm {
+        let width = self.element.get_natural_width(style, context);
+        match self.padding {
+            Some(padding) => width + padding.left + padding.right,
+            None => width,
+        }
+    }
+
+    /// Returns the vertical space taken by the padding of this cell.
+    fn vertical_padding(&self) -> Mm {
+        self.padding
+            .map(|padding| padding.top + padding.bottom)
+            .unwrap_or_default()
+    }
+
+    /// Returns the probable height of this cell, including the padding.
+    fn probable_height(&mut self, style: Style, context: &Context, area: render::Area<'_>) -> Mm {
+        let area = self.element_area(area);
+        self.element.get_probable_height(style, context, area) + self.vertical_padding()
+    }
+
+    /// Renders the element of this cell within the given cell area, including the padding.
+    fn render(
+        &mut self,
+        context: &Context,
+        area: render::Area<'_>,
+        style: Style,
+    ) -> Result<RenderResult, Error> {
+        let area = self.element_area(area);
+        let mut result = self.element.render(context, area, style)?;
+        if let Some(padding) = self.padding {
+            result.size.width += padding.left + padding.right;
+            result.size.height += padding.top + padding.bottom;
+        }
+        Ok(result)
+    }
+
+    /// Sets the number of columns spanned by this cell.
+    ///
+    /// The row containing this cell has fewer cells than the table has columns: the cells of a
+    /// row must span exactly the number of columns of the table.  Values smaller than one are
+    /// treated as one.
+    pub fn set_colspan(&mut self, colspan: usize) {
+        self.colspan = colspan.max(1);
+    }
+
+    /// Sets the number of columns spanned by this cell and returns the cell.
+    pub fn with_colspan(mut self, colspan: usize) -> Self {
+        self.set_colspan(colspan);
+        self
+    }
+
+    /// Returns the number of columns spanned by this cell.
+    pub fn colspan(&self) -> usize {
+        self.colspan
+    }
+
+    /// Sets the number of rows spanned by this cell.
+    ///
+    /// The following rows must not contain cells for the columns spanned by this cell.  The
+    /// spanned rows are always rendered on the same page.  Values smaller than one are treated as
+    /// one.
+    pub fn set_rowspan(&mut self, rowspan: usize) {
+        self.rowspan = rowspan.max(1);
+    }
+
+    /// Sets the number of rows spanned by this cell and returns the cell.
+    pub fn with_rowspan(mut self, rowspan: usize) -> Self {
+        self.set_rowspan(rowspan);
+        self
+    }
+
+    /// Returns the number of rows spanned by this cell.
+    pub fn rowspan(&self) -> usize {
+        self.rowspan
+    }
+
+    /// Shows or hides the given edge using the default line style.
+    ///
+    /// If the borders of this cell have not been overridden yet, all other edges are drawn with
+    /// the default line style.
+    fn set_border(&mut self, edge: fn(&mut Borders) -> &mut Option<LineStyle>, draw: bool) {
+        if self.borders.is_none() && draw {
+            return;
         }
+        let borders = self
+            .borders
+            .get_or_insert_with(|| Borders::all(LineStyle::new()));
+        *edge(borders) = if draw { Some(LineStyle::new()) } else { None };
     }
 
     /// set draw_left_border
+    #[deprecated(since = "0.3.0", note = "use `TableCell::with_borders` instead")]
     pub fn draw_left_border(mut self, draw_left_border: bool) -> Self {
-        self.draw_left_border = draw_left_border;
+        self.set_border(|b| &mut b.left, draw_left_border);
         self
     }
 
     /// set draw_right_border
+    #[deprecated(since = "0.3.0", note = "use `TableCell::with_borders` instead")]
     pub fn draw_right_border(mut self, draw_right_border: bool) -> Self {
-        self.draw_right_border = draw_right_border;
+        self.set_border(|b| &mut b.right, draw_right_border);
         self
     }
 
     /// set draw_top_border
+    #[deprecated(since = "0.3.0", note = "use `TableCell::with_borders` instead")]
     pub fn draw_top_border(mut self, draw_top_border: bool) -> Self {
-        self.draw_top_border = draw_top_border;
+        self.set_border(|b| &mut b.top, draw_top_border);
         self
     }
 
     /// set draw_bottom_border
+    #[deprecated(since = "0.3.0", note = "use `TableCell::with_borders` instead")]
     pub fn draw_bottom_border(mut self, draw_bottom_border: bool) -> Self {
-        self.draw_bottom_border = draw_bottom_border;
+        self.set_border(|b| &mut b.bottom, draw_bottom_border);
         self
     }
 }
@@ -2140,28 +4232,266 @@ impl<'a> TableLayoutRow<'a> {
         TableLayoutRow {
             table_layout,
             cells: Vec::new(),
+            min_height: None,
         }
     }
 
     /// Create a cell with  given element and color and add to cells
     pub fn cell<E: IntoBoxedElement>(mut self, element: E, color: Option<style::Color>) -> Self {
-        self.cells.push(TableCell {
-            element: element.into_boxed_element(),
-            background_color: color,
-            draw_left_border: true,
-            draw_right_border: true,
-            draw_top_border: true,
-            draw_bottom_border: true,
-        });
+        self.cells
+            .push(TableCell::new(element.into_boxed_element(), color));
+        self
+    }
+
+    /// Adds the given element to this row as a cell without a background color.
+    pub fn push_element<E: IntoBoxedElement>(&mut self, element: E) {
+        self.cells
+            .push(TableCell::new(element.into_boxed_element(), None));
+    }
+
+    /// Adds the given element to this row as a cell without a background color and returns the
+    /// row.
+    pub fn element<E: IntoBoxedElement>(mut self, element: E) -> Self {
+        self.push_element(element);
+        self
+    }
+
+    /// Adds the given element to this row as a cell that spans the given number of columns and
+    /// rows, see [`TableCell::with_colspan`][] and [`TableCell::with_rowspan`][].
+    ///
+    /// [`TableCell::with_colspan`]: struct.TableCell.html#method.with_colspan
+    /// [`TableCell::with_rowspan`]: struct.TableCell.html#method.with_rowspan
+    pub fn push_spanning_element<E: IntoBoxedElement>(
+        &mut self,
+        element: E,
+        colspan: usize,
+        rowspan: usize,
+    ) {
+        self.cells.push(
+            TableCell::new(element.into_boxed_element(), None)
+                .with_colspan(colspan)
+                .with_rowspan(rowspan),
+        );
+    }
+
+    /// Adds the given element to this row as a cell that spans the given number of columns and
+    /// rows and returns the row.
+    pub fn spanning_element<E: IntoBoxedElement>(
+        mut self,
+        element: E,
+        colspan: usize,
+        rowspan: usize,
+    ) -> Self {
+        self.push_spanning_element(element, colspan, rowspan);
+        self
+    }
+
+    /// Adds the given cell to this row.
+    pub fn push_cell(&mut self, cell: TableCell) {
+        self.cells.push(cell);
+    }
+
+    /// Sets the minimum height of this row.
+    ///
+    /// If the content of the row is less high, the row is enlarged to this height.
+    pub fn set_min_height(&mut self, height: impl Into<Mm>) {
+        self.min_height = Some(height.into());
+    }
+
+    /// Sets the minimum height of this row and returns the row.
+    pub fn with_min_height(mut self, height: impl Into<Mm>) -> Self {
+        self.set_min_height(height);
         self
     }
 
     /// Tries to append this row to the table.
     ///
-    /// This method fails if the number of elements in this row does not match the number of
-    /// columns in the table.
+    /// This method fails if the cells of this row do not span the number of columns in the
+    /// table.
     pub fn push(self) -> Result<(), Error> {
-        self.table_layout.push_row(self.cells, None)
+        self.table_layout.push_row_impl(self.cells, self.min_height)
+    }
+}
+
+/// A builder for a [`TableLayout`][].
+///
+/// The builder collects the rows of the table and the border settings and creates the table in
+/// [`build`][].  The number of cells is validated for every row that is added; the first invalid
+/// row is reported as an error by [`build`][].  If you need more control over the table, use the
+/// methods of [`TableLayout`][] directly.
+///
+/// # Example
+///
+/// ```
+/// use genpdf::elements;
+/// let data = vec![("a", "b"), ("c", "d")];
+/// let table = elements::TableBuilder::new(elements::ColumnWidths::Weights(vec![1, 1]))
+///     .header_row()
+///     .cell(elements::Paragraph::new("H1"), None)
+///     .cell(elements::Paragraph::new("H2"), None)
+///     .end_row()
+///     .data_rows(data.into_iter().map(|(a, b)| {
+///         vec![
+///             elements::TableCell::new(Box::new(elements::Paragraph::new(a)), None),
+///             elements::TableCell::new(Box::new(elements::Paragraph::new(b)), None),
+///         ]
+///     }))
+///     .inner_borders(true)
+///     .outer_borders(true)
+///     .build()
+///     .expect("Invalid table");
+/// ```
+///
+/// [`TableLayout`]: struct.TableLayout.html
+/// [`build`]: #method.build
+pub struct TableBuilder {
+    column_widths: ColumnWidths,
+    rows: Vec<Vec<TableCell>>,
+    inner_borders: bool,
+    outer_borders: bool,
+    has_row_spans: bool,
+    error: Option<Error>,
+}
+
+impl TableBuilder {
+    /// Creates a new table builder with the given column widths.
+    pub fn new(column_widths: ColumnWidths) -> TableBuilder {
+        TableBuilder {
+            column_widths,
+            rows: Vec::new(),
+            inner_borders: false,
+            outer_borders: false,
+            has_row_spans: false,
+            error: None,
+        }
+    }
+
+    /// Starts the header row of the table.
+    ///
+    /// The header row is the first row of the table.  If rows have already been added, the
+    /// header row is inserted before them.
+    pub fn header_row(self) -> TableBuilderRow {
+        TableBuilderRow::new(self, true)
+    }
+
+    /// Starts a new row of the table.
+    pub fn row(self) -> TableBuilderRow {
+        TableBuilderRow::new(self, false)
+    }
+
+    /// Adds the given rows to the table and returns the builder.
+    pub fn data_rows<I>(mut self, rows: I) -> Self
+    where
+        I: IntoIterator<Item = Vec<TableCell>>,
+    {
+        for cells in rows {
+            self.push_cells(cells, false);
+        }
+        self
+    }
+
+    /// Sets whether the inner borders of the table are drawn and returns the builder.
+    pub fn inner_borders(mut self, inner_borders: bool) -> Self {
+        self.inner_borders = inner_borders;
+        self
+    }
+
+    /// Sets whether the outer borders of the table are drawn and returns the builder.
+    pub fn outer_borders(mut self, outer_borders: bool) -> Self {
+        self.outer_borders = outer_borders;
+        self
+    }
+
+    /// Creates the table layout.
+    ///
+    /// This method fails if any of the rows added to this builder does not have the same number
+    /// of cells as the table has columns or if the column widths are invalid, see
+    /// [`ColumnWidths::validate`][].
+    ///
+    /// [`ColumnWidths::validate`]: enum.ColumnWidths.html#method.validate
+    pub fn build(self) -> Result<TableLayout, Error> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        self.column_widths.validate(1)?;
+        let mut table = TableLayout::new_with_borders(
+            self.column_widths,
+            self.inner_borders,
+            self.outer_borders,
+        );
+        for cells in self.rows {
+            table.push_row(cells, None)?;
+        }
+        Ok(table)
+    }
+
+    fn push_cells(&mut self, cells: Vec<TableCell>, is_header: bool) {
+        if self.error.is_some() {
+            return;
+        }
+        // Rows following a cell that spans multiple rows have fewer cells, so they are only fully
+        // validated when the table is built.
+        let columns: usize = cells.iter().map(|cell| cell.colspan).sum();
+        let num_columns = self.column_widths.len();
+        if columns > num_columns || (columns < num_columns && !self.has_row_spans) {
+            self.error = Some(Error::new(
+                format!(
+                    "Expected {} elements in table row {}, received {}",
+                    num_columns,
+                    if is_header { 0 } else { self.rows.len() },
+                    columns
+                ),
+                ErrorKind::InvalidData,
+            ));
+            return;
+        }
+        self.has_row_spans |= cells.iter().any(|cell| cell.rowspan > 1);
+        if is_header {
+            self.rows.insert(0, cells);
+        } else {
+            self.rows.push(cells);
+        }
+    }
+}
+
+/// A row of a [`TableBuilder`][].
+///
+/// Call [`end_row`][] to add the row to the builder.
+///
+/// [`TableBuilder`]: struct.TableBuilder.html
+/// [`end_row`]: #method.end_row
+pub struct TableBuilderRow {
+    builder: TableBuilder,
+    cells: Vec<TableCell>,
+    is_header: bool,
+}
+
+impl TableBuilderRow {
+    fn new(builder: TableBuilder, is_header: bool) -> TableBuilderRow {
+        TableBuilderRow {
+            builder,
+            cells: Vec::new(),
+            is_header,
+        }
+    }
+
+    /// Adds a cell with the given element and background color to this row and returns the row.
+    pub fn cell<E: IntoBoxedElement>(mut self, element: E, color: Option<style::Color>) -> Self {
+        self.cells
+            .push(TableCell::new(element.into_boxed_element(), color));
+        self
+    }
+
+    /// Adds the given cell to this row and returns the row.
+    pub fn table_cell(mut self, cell: TableCell) -> Self {
+        self.cells.push(cell);
+        self
+    }
+
+    /// Finishes this row and returns the table builder.
+    pub fn end_row(mut self) -> TableBuilder {
+        self.builder.push_cells(self.cells, self.is_header);
+        self.builder
     }
 }
 
@@ -2180,8 +4510,8 @@ impl<'a> TableLayoutRow<'a> {
 /// With setters:
 /// ```
 /// use genpdf::elements;
-/// let mut table = elements::TableLayout::new(vec![1, 1]);
-/// table.set_cell_decorator(elements::FrameCellDecorator::new(true, true, false));
+/// let mut table = elements::TableLayout::new(elements::ColumnWidths::Weights(vec![1, 1]));
+/// table.set_cell_decorator(elements::FrameCellDecorator::new(true, true));
 /// let mut row = table.row();
 /// row.push_element(elements::Paragraph::new("Cell 1"));
 /// row.push_element(elements::Paragraph::new("Cell 2"));
@@ -2191,7 +4521,7 @@ impl<'a> TableLayoutRow<'a> {
 /// Chained:
 /// ```
 /// use genpdf::elements;
-/// let table = elements::TableLayout::new(vec![1, 1])
+/// let table = elements::TableLayout::new(elements::ColumnWidths::Weights(vec![1, 1]))
 ///     .row()
 ///     .element(elements::Paragraph::new("Cell 1"))
 ///     .element(elements::Paragraph::new("Cell 2"))
@@ -2203,11 +4533,40 @@ impl<'a> TableLayoutRow<'a> {
 /// [`FrameCellDecorator`]: struct.FrameCellDecorator.html
 ///
 #[derive(Clone)]
+#[non_exhaustive]
 pub enum ColumnWidths {
     /// The columns have the given weights.
     Weights(Vec<usize>),
     /// The columns have the given pixel widths.
     PixelWidths(Vec<f64>),
+    /// The columns have either a fixed width or a weight.
+    ///
+    /// The fixed columns are allocated first.  The remaining width is distributed among the
+    /// weighted columns according to their weights.
+    Mixed(Vec<ColumnSpec>),
+    /// The columns have the given weights, but their widths are limited by the given minimum and
+    /// maximum widths.
+    ///
+    /// The minimum widths are satisfied first and the maximum widths are never exceeded.  The
+    /// remaining width is distributed among the other columns according to their weights.  If
+    /// the constraints cannot be satisfied for the available width, rendering the table fails.
+    Constrained {
+        /// The weights of the columns.
+        weights: Vec<usize>,
+        /// The minimum widths of the columns.
+        min_widths: Vec<Option<Mm>>,
+        /// The maximum widths of the columns.
+        max_widths: Vec<Option<Mm>>,
+    },
+    /// The given number of columns is as wide as the widest cell of each column.
+    ///
+    /// The natural widths of the cells are measured before the table is rendered, see
+    /// [`Element::get_natural_width`][].  If the total width exceeds the available width, the
+    /// columns are shrunk proportionally.  Otherwise, the table is narrower than the available
+    /// width.  Cells spanning multiple columns are not considered.
+    ///
+    /// [`Element::get_natural_width`]: ../trait.Element.html#method.get_natural_width
+    AutoFit(usize),
 }
 
 impl ColumnWidths {
@@ -2216,6 +4575,9 @@ impl ColumnWidths {
         match self {
             ColumnWidths::Weights(weights) => weights.len(),
             ColumnWidths::PixelWidths(widths) => widths.len(),
+            ColumnWidths::Mixed(specs) => specs.len(),
+            ColumnWidths::Constrained { weights, .. } => weights.len(),
+            ColumnWidths::AutoFit(columns) => *columns,
         }
     }
 
@@ -2224,6 +4586,9 @@ impl ColumnWidths {
         match self {
             ColumnWidths::Weights(weights) => weights.is_empty(),
             ColumnWidths::PixelWidths(widths) => widths.is_empty(),
+            ColumnWidths::Mixed(specs) => specs.is_empty(),
+            ColumnWidths::Constrained { weights, .. } => weights.is_empty(),
+            ColumnWidths::AutoFit(columns) => *columns == 0,
         }
     }
 
@@ -2238,14 +4603,388 @@ impl ColumnWidths {
                 widths
             }
             ColumnWidths::PixelWidths(widths) => widths.clone(),
+            ColumnWidths::Mixed(specs) => specs
+                .iter()
+                .map(|spec| match spec {
+                    ColumnSpec::Fixed(width) => width.0,
+                    ColumnSpec::Weight(weight) => *weight as f64,
+                })
+                .collect(),
+            ColumnWidths::Constrained { weights, .. } => {
+                weights.iter().map(|weight| *weight as f64).collect()
+            }
+            ColumnWidths::AutoFit(columns) => vec![1.0; *columns],
+        }
+    }
+
+    /// Checks whether these column widths are valid.
+    ///
+    /// An error is returned if there are less than `min_columns` columns or if a weight or width
+    /// is not positive.  For [`ColumnWidths::Constrained`][], the number of minimum and maximum
+    /// widths must match the number of weights, and the minimum widths must not exceed the
+    /// maximum widths.
+    ///
+    /// [`ColumnWidths::Constrained`]: #variant.Constrained
+    pub fn validate(&self, min_columns: usize) -> Result<(), Error> {
+        if self.len() < min_columns {
+            return Err(Error::new(
+                format!(
+                    "Expected at least {} table columns, received {}",
+                    min_columns,
+                    self.len()
+                ),
+                ErrorKind::InvalidData,
+            ));
+        }
+        if let ColumnWidths::Constrained {
+            weights,
+            min_widths,
+            max_widths,
+        } = self
+        {
+            if min_widths.len() != weights.len() || max_widths.len() != weights.len() {
+                return Err(Error::new(
+                    format!(
+                        "Expected {} minimum and maximum column widths, received {} and {}",
+                        weights.len(),
+                        min_widths.len(),
+                        max_widths.len()
+                    ),
+                    ErrorKind::InvalidData,
+                ));
+            }
+            let conflicting_columns: Vec<String> = min_widths
+                .iter()
+                .zip(max_widths)
+                .enumerate()
+                .filter(|(_, (min, max))| matches!((min, max), (Some(min), Some(max)) if min > max))
+                .map(|(i, _)| i.to_string())
+                .collect();
+            if !conflicting_columns.is_empty() {
+                return Err(Error::new(
+                    format!(
+                        "Minimum column widths exceed the maximum widths, invalid columns: {}",
+                        conflicting_columns.join(", ")
+                    ),
+                    ErrorKind::InvalidData,
+                ));
+            }
+        }
+        let invalid_columns: Vec<String> = self
+            .to_vec()
+            .into_iter()
+            .enumerate()
+            .filter(|(_, width)| !(width.is_finite() && *width > 0.0))
+            .map(|(i, _)| i.to_string())
+            .collect();
+        if invalid_columns.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::new(
+                format!(
+                    "Table column widths must be positive, invalid columns: {}",
+                    invalid_columns.join(", ")
+                ),
+                ErrorKind::InvalidData,
+            ))
+        }
+    }
+
+    /// Checks whether the pixel widths and fixed widths of these column widths fit into the
+    /// given width.
+    ///
+    /// Weighted columns always share the available width, so they are not considered.  For
+    /// [`ColumnWidths::Constrained`][], the minimum and maximum widths must be satisfiable, see
+    /// [`try_resolve`][].
+    ///
+    /// [`ColumnWidths::Constrained`]: #variant.Constrained
+    /// [`try_resolve`]: #method.try_resolve
+    pub fn validate_against_width(&self, available_width: Mm) -> Result<(), Error> {
+        let total_width: Mm = match self {
+            ColumnWidths::Weights(_) | ColumnWidths::AutoFit(_) => Mm(0.0),
+            ColumnWidths::Constrained { .. } => {
+                return self.try_resolve(available_width).map(|_| ());
+            }
+            ColumnWidths::PixelWidths(widths) => widths.iter().map(|w| Mm::from(*w)).sum(),
+            ColumnWidths::Mixed(specs) => specs
+                .iter()
+                .filter_map(|spec| match spec {
+                    ColumnSpec::Fixed(width) => Some(*width),
+                    ColumnSpec::Weight(_) => None,
+                })
+                .sum(),
+        };
+        if total_width > available_width {
+            Err(Error::new(
+                format!(
+                    "The total column width exceeds the available width of {} by {}",
+                    available_width,
+                    total_width - available_width
+                ),
+                ErrorKind::InvalidData,
+            ))
+        } else {
+            Ok(())
         }
     }
+
+    /// Returns the widths of the columns if the given width is available.
+    ///
+    /// Pixel widths and fixed widths are returned as given.  Weighted columns share the width that
+    /// is not used by fixed columns.  As the content of the columns is not known, auto-fit columns
+    /// share the available width equally.  If the constraints of [`ColumnWidths::Constrained`][] cannot
+    /// be satisfied, the width is distributed according to the weights only, see
+    /// [`try_resolve`][].
+    ///
+    /// [`ColumnWidths::Constrained`]: #variant.Constrained
+    /// [`try_resolve`]: #method.try_resolve
+    pub fn resolve(&self, available_width: Mm) -> Vec<Mm> {
+        match self {
+            ColumnWidths::Constrained { weights, .. } => {
+                self.try_resolve(available_width).unwrap_or_else(|_| {
+                    ColumnWidths::Weights(weights.clone()).resolve(available_width)
+                })
+            }
+            ColumnWidths::AutoFit(columns) => {
+                ColumnWidths::Weights(vec![1; *columns]).resolve(available_width)
+            }
+            ColumnWidths::Weights(weights) => {
+                let total_weight: usize = weights.iter().sum();
+                if total_weight == 0 {
+                    return vec![Mm(0.0); weights.len()];
+                }
+                let factor = available_width / total_weight as f64;
+                weights
+                    .iter()
+                    .map(|weight| factor * *weight as f64)
+                    .collect()
+            }
+            ColumnWidths::PixelWidths(widths) => widths.iter().map(|w| Mm::from(*w)).collect(),
+            ColumnWidths::Mixed(specs) => {
+                let fixed_width: Mm = specs
+                    .iter()
+                    .filter_map(|spec| match spec {
+                        ColumnSpec::Fixed(width) => Some(*width),
+                        ColumnSpec::Weight(_) => None,
+                    })
+                    .sum();
+                let total_weight: usize = specs
+                    .iter()
+                    .filter_map(|spec| match spec {
+                        ColumnSpec::Fixed(_) => None,
+                        ColumnSpec::Weight(weight) => Some(*weight),
+                    })
+                    .sum();
+                let remaining = (available_width - fixed_width).max(Mm(0.0));
+                specs
+                    .iter()
+                    .map(|spec| match spec {
+                        ColumnSpec::Fixed(width) => *width,
+                        ColumnSpec::Weight(_) if total_weight == 0 => Mm(0.0),
+                        ColumnSpec::Weight(weight) => {
+                            remaining * (*weight as f64 / total_weight as f64)
+                        }
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Returns the widths of the columns if the given width is available, or an error if the
+    /// minimum and maximum widths of [`ColumnWidths::Constrained`][] cannot be satisfied.
+    ///
+    /// For the other kinds of column widths, this is the same as [`resolve`][].
+    ///
+    /// [`ColumnWidths::Constrained`]: #variant.Constrained
+    /// [`resolve`]: #method.resolve
+    pub fn try_resolve(&self, available_width: Mm) -> Result<Vec<Mm>, Error> {
+        let (weights, min_widths, max_widths) = match self {
+            ColumnWidths::Constrained {
+                weights,
+                min_widths,
+                max_widths,
+            } => (weights, min_widths, max_widths),
+            _ => return Ok(self.resolve(available_width)),
+        };
+        self.validate(0)?;
+        let min_width = |i: usize| min_widths[i].unwrap_or_default();
+
+        let total_min_width: Mm = (0..weights.len()).map(min_width).sum();
+        if total_min_width > available_width {
+            return Err(Error::new(
+                format!(
+                    "The minimum column widths exceed the available width of {} by {}",
+                    available_width,
+                    total_min_width - available_width
+                ),
+                ErrorKind::InvalidData,
+            ));
+        }
+
+        // Distribute the remaining width among the columns that are not limited by their
+        // minimum or maximum width until no column violates its constraints.
+        let mut widths: Vec<Option<Mm>> = vec![None; weights.len()];
+        loop {
+            let free: Vec<usize> = (0..weights.len())
+                .filter(|&i| widths[i].is_none())
+                .collect();
+            if free.is_empty() {
+                break;
+            }
+            let used: Mm = widths.iter().flatten().copied().sum();
+            let remaining = (available_width - used).max(Mm(0.0));
+            let total_weight: usize = free.iter().map(|&i| weights[i]).sum();
+            let width = |i: usize| {
+                if total_weight == 0 {
+                    Mm(0.0)
+                } else {
+                    remaining * (weights[i] as f64 / total_weight as f64)
+                }
+            };
+
+            let mut changed = false;
+            for &i in &free {
+                if width(i) < min_width(i) {
+                    widths[i] = Some(min_width(i));
+                    changed = true;
+                }
+            }
+            if !changed {
+                for &i in &free {
+                    if let Some(max_width) = max_widths[i].filter(|&max| width(i) > max) {
+                        widths[i] = Some(max_width);
+                        changed = true;
+                    }
+                }
+            }
+            if !changed {
+                for &i in &free {
+                    widths[i] = Some(width(i));
+                }
+            }
+        }
+
+        let widths: Vec<Mm> = widths.into_iter().flatten().collect();
+        let total_width: Mm = widths.iter().copied().sum();
+        if (available_width - total_width).0.abs() > 0.001 {
+            return Err(Error::new(
+                format!(
+                    "The maximum column widths do not fill the available width of {}, {} remaining",
+                    available_width,
+                    available_width - total_width
+                ),
+                ErrorKind::InvalidData,
+            ));
+        }
+        Ok(widths)
+    }
+}
+
+/// The width of a column in [`ColumnWidths::Mixed`][].
+///
+/// [`ColumnWidths::Mixed`]: enum.ColumnWidths.html#variant.Mixed
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ColumnSpec {
+    /// The column has the given fixed width.
+    Fixed(Mm),
+    /// The column has the given weight and shares the remaining width with the other weighted
+    /// columns.
+    Weight(usize),
 }
 
+/// The width of a single table column, see [`TableLayout::set_column_width`][].
+///
+/// [`TableLayout::set_column_width`]: struct.TableLayout.html#method.set_column_width
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ColumnWidthSpec {
+    /// The column has the given weight, see [`ColumnWidths::Weights`][].
+    ///
+    /// [`ColumnWidths::Weights`]: enum.ColumnWidths.html#variant.Weights
+    Weight(usize),
+    /// The column has the given pixel width, see [`ColumnWidths::PixelWidths`][].
+    ///
+    /// [`ColumnWidths::PixelWidths`]: enum.ColumnWidths.html#variant.PixelWidths
+    PixelWidth(f64),
+}
+
+/// The behavior of a [`TableLayout`][] that does not fit in the remaining space of a page, see
+/// [`TableLayout::set_overflow`][].
+///
+/// [`TableLayout`]: struct.TableLayout.html
+/// [`TableLayout::set_overflow`]: struct.TableLayout.html#method.set_overflow
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TableOverflow {
+    /// The rows that do not fit are rendered on the next page.
+    ContinueOnNextPage,
+    /// If the table does not fit in the remaining space, a page break is inserted before the
+    /// table.  If it does not fit on a new page either, the remaining rows are rendered on the
+    /// next page.
+    KeepOnOnePage,
+    /// Like `KeepOnOnePage`, but an error is returned if the table does not fit on a new page.
+    Error,
+}
+
+impl Default for TableOverflow {
+    fn default() -> TableOverflow {
+        TableOverflow::ContinueOnNextPage
+    }
+}
+
+/// The minimum width of a weighted table column, see [`TableLayout::validate_column_widths`][].
+///
+/// [`TableLayout::validate_column_widths`]: struct.TableLayout.html#method.validate_column_widths
+pub const MIN_COLUMN_WIDTH: Mm = Mm(5.0);
+
 /// Table Row
 pub struct TableRow {
     cells: Vec<TableCell>,
-    row_height: Option<i32>,
+    row_height: Option<Mm>,
+    full_width: bool,
+}
+
+impl TableRow {
+    /// Sets the background color of all cells of this row that do not have a background color.
+    pub fn set_background(&mut self, color: Color) {
+        for cell in &mut self.cells {
+            cell.background_color.get_or_insert(color);
+        }
+    }
+
+    /// Returns the columns and the number of spanned columns of the cells of this row.
+    ///
+    /// `occupied` contains the number of remaining rows for every column that is covered by a
+    /// cell of a previous row.  These columns are skipped and `occupied` is updated for the next
+    /// row.
+    fn spans(&self, occupied: &mut [usize]) -> Vec<(usize, usize)> {
+        let num_columns = occupied.len();
+        let spans = if self.full_width {
+            vec![(0, num_columns)]
+        } else {
+            let mut spans = Vec::with_capacity(self.cells.len());
+            let mut column = 0;
+            for cell in &self.cells {
+                while column < num_columns && occupied[column] > 0 {
+                    column += 1;
+                }
+                let span = cell.colspan.min(num_columns.saturating_sub(column));
+                if span == 0 {
+                    break;
+                }
+                spans.push((column, span));
+                column += span;
+            }
+            spans
+        };
+        for rows in occupied.iter_mut() {
+            *rows = rows.saturating_sub(1);
+        }
+        for (&(column, span), cell) in spans.iter().zip(&self.cells) {
+            for rows in &mut occupied[column..column + span] {
+                *rows = cell.rowspan - 1;
+            }
+        }
+        spans
+    }
 }
 
 /// Table Layout
@@ -2258,7 +4997,19 @@ pub struct TableLayout {
     draw_inner_borders: bool,
     draw_outer_borders: bool,
     has_header_row_callback: bool,
+    max_header_height: Option<Mm>,
+    header_skip_on_first_page: bool,
     margins: Option<Margins>,
+    alternating_row_colors: Option<(Color, Color, usize)>,
+    overflow: TableOverflow,
+    overflow_checked: bool,
+    page_break_forced: bool,
+    widths_validated: bool,
+    has_row_spans: bool,
+    header_rows: usize,
+    footer_rows: usize,
+    auto_fit_widths: Option<ColumnWidths>,
+    default_row_height: Option<Mm>,
 }
 
 type TableHeaderRowCallback = Box<dyn Fn(usize) -> Result<Box<dyn Element>, Error>>;
@@ -2278,10 +5029,29 @@ impl TableLayout {
 
     /// Creates a new table layout with the given column weights.
     ///
+    /// The column weights are validated when a row is added with [`push_row`][] and before the
+    /// table is rendered, see [`ColumnWidths::validate`][].  Use [`try_new`][] to validate them
+    /// immediately.
+    ///
+    /// [`push_row`]: #method.push_row
+    /// [`try_new`]: #method.try_new
+    /// [`ColumnWidths::validate`]: enum.ColumnWidths.html#method.validate
     pub fn new(column_weights: ColumnWidths) -> Self {
         TableLayout::new_with_borders(column_weights, false, false)
     }
 
+    /// Creates a new table layout with the given column weights after validating them.
+    ///
+    /// In contrast to [`new`][], this method returns an error if the table would not have any
+    /// columns or if a column weight or width is not positive, see [`ColumnWidths::validate`][].
+    ///
+    /// [`new`]: #method.new
+    /// [`ColumnWidths::validate`]: enum.ColumnWidths.html#method.validate
+    pub fn try_new(column_weights: ColumnWidths) -> Result<Self, Error> {
+        column_weights.validate(1)?;
+        Ok(TableLayout::new(column_weights))
+    }
+
     /// Creates a new table layout with the given column weights.
     ///
     /// The column weights are used to determine the relative width of the columns.  The number of
@@ -2300,12 +5070,100 @@ impl TableLayout {
             draw_inner_borders,
             draw_outer_borders,
             has_header_row_callback: false,
+            max_header_height: None,
+            header_skip_on_first_page: false,
             margins: None,
+            alternating_row_colors: None,
+            overflow: TableOverflow::default(),
+            overflow_checked: false,
+            page_break_forced: false,
+            widths_validated: false,
+            has_row_spans: false,
+            header_rows: 0,
+            footer_rows: 0,
+            auto_fit_widths: None,
+            default_row_height: None,
         };
         set_cell_decorator(&mut tl, draw_inner_borders, draw_outer_borders);
         tl
     }
 
+    /// Sets the behavior of this table if it does not fit in the remaining space of a page.
+    ///
+    /// If this method is not called, [`TableOverflow::ContinueOnNextPage`][] is used.  The
+    /// check uses the probable height of the table, so it is only an estimate.
+    ///
+    /// [`TableOverflow::ContinueOnNextPage`]: enum.TableOverflow.html#variant.ContinueOnNextPage
+    pub fn set_overflow(&mut self, overflow: TableOverflow) {
+        self.overflow = overflow;
+    }
+
+    /// Replaces the column widths of this table.
+    ///
+    /// The number of columns cannot be changed, so the given column widths must have the same
+    /// length as the current column widths.  Otherwise, an error is returned.
+    pub fn set_column_widths(&mut self, column_widths: ColumnWidths) -> Result<(), Error> {
+        if column_widths.len() != self.column_weights.len() {
+            return Err(Error::new(
+                format!(
+                    "Expected {} column widths, received {}",
+                    self.column_weights.len(),
+                    column_widths.len()
+                ),
+                ErrorKind::InvalidData,
+            ));
+        }
+        self.column_weights = column_widths;
+        Ok(())
+    }
+
+    /// Replaces the width of the column with the given index.
+    ///
+    /// The kind of the width must match the current column widths, i. e. a
+    /// [`ColumnWidthSpec::Weight`][] can only be used for [`ColumnWidths::Weights`][] and a
+    /// [`ColumnWidthSpec::PixelWidth`][] only for [`ColumnWidths::PixelWidths`][].  For
+    /// [`ColumnWidths::Mixed`][], both kinds can be used.  Otherwise, or if the index is out of
+    /// bounds, an error is returned.
+    ///
+    /// [`ColumnWidthSpec::Weight`]: enum.ColumnWidthSpec.html#variant.Weight
+    /// [`ColumnWidthSpec::PixelWidth`]: enum.ColumnWidthSpec.html#variant.PixelWidth
+    /// [`ColumnWidths::Weights`]: enum.ColumnWidths.html#variant.Weights
+    /// [`ColumnWidths::PixelWidths`]: enum.ColumnWidths.html#variant.PixelWidths
+    /// [`ColumnWidths::Mixed`]: enum.ColumnWidths.html#variant.Mixed
+    pub fn set_column_width(&mut self, index: usize, width: ColumnWidthSpec) -> Result<(), Error> {
+        let len = self.column_weights.len();
+        if index >= len {
+            return Err(Error::new(
+                format!("Column index {} out of bounds for {} columns", index, len),
+                ErrorKind::InvalidData,
+            ));
+        }
+        match (&mut self.column_weights, width) {
+            (ColumnWidths::Weights(weights), ColumnWidthSpec::Weight(weight)) => {
+                weights[index] = weight;
+            }
+            (ColumnWidths::PixelWidths(widths), ColumnWidthSpec::PixelWidth(pixel_width)) => {
+                widths[index] = pixel_width;
+            }
+            (ColumnWidths::Mixed(specs), ColumnWidthSpec::Weight(weight)) => {
+                specs[index] = ColumnSpec::Weight(weight);
+            }
+            (ColumnWidths::Mixed(specs), ColumnWidthSpec::PixelWidth(pixel_width)) => {
+                specs[index] = ColumnSpec::Fixed(Mm::from(pixel_width));
+            }
+            (ColumnWidths::Constrained { weights, .. }, ColumnWidthSpec::Weight(weight)) => {
+                weights[index] = weight;
+            }
+            _ => {
+                return Err(Error::new(
+                    "Column width kind does not match the column widths of the table",
+                    ErrorKind::InvalidData,
+                ));
+            }
+        }
+        Ok(())
+    }
+
     /// set margins
     /// margins is the distance between the text and the border
     pub fn set_margins(&mut self, margins: Margins) {
@@ -2338,41 +5196,510 @@ impl TableLayout {
             Some(Box::new(move |page| cb(page).map(|e| Box::new(e) as _)));
     }
 
+    /// Sets the maximum height of the header row rendered by the header row callback.
+    ///
+    /// If the probable height of the header element exceeds this height, the header is skipped
+    /// for this page instead of being rendered.
+    ///
+    /// If the header is taller than the body of an empty page, it can never be rendered.  In this
+    /// case, it is skipped on that page and a warning is logged, regardless of this setting.
+    pub fn set_max_header_height(&mut self, max_header_height: impl Into<Mm>) {
+        self.max_header_height = Some(max_header_height.into());
+    }
+
+    /// Sets whether the header row rendered by the header row callback should be skipped on the
+    /// page where the table starts.
+    ///
+    /// If this is set to `true`, the header is only rendered on the continuation pages of this
+    /// table.  Per default, the header is rendered on every page.
+    pub fn set_header_skip_on_first_page(&mut self, skip: bool) {
+        self.header_skip_on_first_page = skip;
+    }
+
+    /// Sets the minimum height of all rows of this table.
+    ///
+    /// Rows with less content are enlarged to this height.  The height of a single row can be set
+    /// with [`TableLayoutRow::with_min_height`][]; the larger of both heights is used.
+    ///
+    /// [`TableLayoutRow::with_min_height`]: struct.TableLayoutRow.html#method.with_min_height
+    pub fn set_default_row_height(&mut self, height: impl Into<Mm>) {
+        self.default_row_height = Some(height.into());
+    }
+
+    /// Sets the number of header rows of this table.
+    ///
+    /// The first `count` rows of the table are header rows.  If the table is continued on the next
+    /// page, the header rows are rendered again at the top of the continuation page.  In contrast
+    /// to [`register_header_row_callback_fn`][], the header rows are regular table rows, so they
+    /// use the same column widths and cell decorator as the other rows.
+    ///
+    /// [`register_header_row_callback_fn`]: #method.register_header_row_callback_fn
+    pub fn set_header_rows(&mut self, count: usize) {
+        self.header_rows = count;
+    }
+
+    /// Sets the number of footer rows of this table.
+    ///
+    /// The last `count` rows of the table are footer rows, for example for totals.  Space for the
+    /// footer rows is reserved on every page.  If the table is continued on the next page, the
+    /// footer rows are rendered below the last row on the current page.  After the last row of
+    /// the table, they are rendered a final time.
+    pub fn set_footer_rows(&mut self, count: usize) {
+        self.footer_rows = count;
+    }
+
+    /// Sets alternating background colors for the rows of this table, starting with the row with
+    /// the index `start_row`.
+    ///
+    /// The row with the index `start_row` uses the `even` color, the next row the `odd` color and
+    /// so on.  The rows before `start_row`, for example header rows, are not striped.  Cells with
+    /// an explicit background color keep their color.  The colors are drawn by the cell
+    /// decorator, so this has no effect if the table has no cell decorator.  They take precedence
+    /// over the colors set with [`FrameCellDecorator::with_alternating_colors`][].
+    ///
+    /// [`FrameCellDecorator::with_alternating_colors`]: struct.FrameCellDecorator.html#method.with_alternating_colors
+    pub fn set_alternating_row_colors_from(&mut self, even: Color, odd: Color, start_row: usize) {
+        self.alternating_row_colors = Some((even, odd, start_row));
+    }
+
+    /// Returns the background color for the row with the given index according to the
+    /// alternating row colors, if set.
+    fn alternating_row_color(&self, row: usize) -> Option<Color> {
+        match self.alternating_row_colors {
+            Some((even, odd, start_row)) if row >= start_row => {
+                if (row - start_row) % 2 == 0 {
+                    Some(even)
+                } else {
+                    Some(odd)
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Checks whether the column widths of this table can be used for the given width.
+    ///
+    /// Pixel widths and fixed widths must not exceed the available width, and weighted columns
+    /// must be at least [`MIN_COLUMN_WIDTH`][] wide.  Otherwise, an error is returned that lists
+    /// the problematic columns.  The column widths must also be valid according to
+    /// [`ColumnWidths::validate`][].  This check is performed before the table is rendered.
+    ///
+    /// [`MIN_COLUMN_WIDTH`]: constant.MIN_COLUMN_WIDTH.html
+    /// [`ColumnWidths::validate`]: enum.ColumnWidths.html#method.validate
+    pub fn validate_column_widths(&self, available_width: Mm) -> Result<(), Error> {
+        self.column_weights.validate(1)?;
+        let widths = self
+            .effective_column_widths()
+            .try_resolve(available_width)?;
+        let mut problems = Vec::new();
+        let total_width: Mm = widths.iter().copied().sum();
+        if total_width > available_width {
+            problems.push(format!(
+                "the total column width exceeds the available width of {} by {}",
+                available_width,
+                total_width - available_width
+            ));
+        }
+        let weighted_columns: Vec<usize> = match self.effective_column_widths() {
+            ColumnWidths::Weights(weights) => (0..weights.len()).collect(),
+            ColumnWidths::AutoFit(columns) => (0..*columns).collect(),
+            ColumnWidths::PixelWidths(_) => Vec::new(),
+            ColumnWidths::Mixed(specs) => specs
+                .iter()
+                .enumerate()
+                .filter(|(_, spec)| matches!(spec, ColumnSpec::Weight(_)))
+                .map(|(i, _)| i)
+                .collect(),
+            // The minimum widths are chosen explicitly and have already been checked.
+            ColumnWidths::Constrained { min_widths, .. } => min_widths
+                .iter()
+                .enumerate()
+                .filter(|(_, min_width)| min_width.is_none())
+                .map(|(i, _)| i)
+                .collect(),
+        };
+        for i in weighted_columns {
+            if widths[i] < MIN_COLUMN_WIDTH {
+                problems.push(format!(
+                    "column {} is {} narrower than the minimum width of {}",
+                    i,
+                    MIN_COLUMN_WIDTH - widths[i],
+                    MIN_COLUMN_WIDTH
+                ));
+            }
+        }
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::new(
+                format!("Invalid table column widths: {}", problems.join(", ")),
+                ErrorKind::InvalidData,
+            ))
+        }
+    }
+
+    /// Returns whether the column widths of this table can be used for the given width, see
+    /// [`validate_column_widths`][].
+    ///
+    /// [`validate_column_widths`]: #method.validate_column_widths
+    pub fn is_valid_for_width(&self, available_width: impl Into<Mm>) -> bool {
+        self.validate_column_widths(available_width.into()).is_ok()
+    }
+
+    /// Returns the index of the row that is rendered next.
+    pub fn current_row(&self) -> usize {
+        self.render_idx
+    }
+
     /// Sets the cell decorator for this table.
     pub fn set_cell_decorator(&mut self, decorator: impl CellDecorator + 'static) {
         self.cell_decorator = Some(Box::from(decorator));
     }
 
-    /// Adds a row to this table using the [`TableLayoutRow`][] helper struct.
-    ///
-    /// [`TableLayoutRow`]: struct.TableLayoutRow.html
-    pub fn row(&mut self) -> TableLayoutRow<'_> {
-        TableLayoutRow::new(self)
+    /// Adds a row to this table using the [`TableLayoutRow`][] helper struct.
+    ///
+    /// [`TableLayoutRow`]: struct.TableLayoutRow.html
+    pub fn row(&mut self) -> TableLayoutRow<'_> {
+        TableLayoutRow::new(self)
+    }
+
+    /// Adds a row to this table.
+    ///
+    /// The number of elements in the given vector must match the number of columns.  Otherwise, an
+    /// error is returned.  A cell that spans multiple columns counts as multiple elements, and the
+    /// columns covered by cells spanning multiple rows are skipped in the following rows, see
+    /// [`TableCell::with_colspan`][] and [`TableCell::with_rowspan`][].
+    ///
+    /// [`TableCell::with_colspan`]: struct.TableCell.html#method.with_colspan
+    /// [`TableCell::with_rowspan`]: struct.TableCell.html#method.with_rowspan
+    pub fn push_row(
+        &mut self,
+        cells: Vec<TableCell>,
+        row_height: Option<i32>,
+    ) -> Result<(), Error> {
+        self.push_row_impl(cells, row_height.map(Mm::from))
+    }
+
+    fn push_row_impl(
+        &mut self,
+        cells: Vec<TableCell>,
+        row_height: Option<Mm>,
+    ) -> Result<(), Error> {
+        self.validate_row(self.rows.len(), &cells)?;
+        self.has_row_spans |= cells.iter().any(|cell| cell.rowspan > 1);
+        let r = TableRow {
+            cells,
+            row_height,
+            full_width: false,
+        };
+        self.rows.push(r);
+        Ok(())
+    }
+
+    /// Checks whether the given cells span all columns of the table if they are inserted at the
+    /// given row index.
+    fn validate_row(&self, index: usize, cells: &[TableCell]) -> Result<(), Error> {
+        self.column_weights.validate(1)?;
+        let num_columns = self.column_weights.len();
+        let mut occupied = vec![0; num_columns];
+        if self.has_row_spans {
+            for row in &self.rows[..index] {
+                row.spans(&mut occupied);
+            }
+        }
+        check_row_columns(&occupied, cells)
+    }
+
+    /// Checks whether the cells of all rows of this table span all columns of the table.
+    ///
+    /// The rows are validated when they are added with [`push_row`][] or [`insert_row`][].  If
+    /// the table has cells that span multiple rows, removing or swapping rows with
+    /// [`remove_row`][] or [`swap_rows`][] can change the columns that are available for the
+    /// following rows.  These methods do not validate the rows again, so this method should be
+    /// called afterwards.
+    ///
+    /// [`push_row`]: #method.push_row
+    /// [`insert_row`]: #method.insert_row
+    /// [`remove_row`]: #method.remove_row
+    /// [`swap_rows`]: #method.swap_rows
+    pub fn validate_rows(&self) -> Result<(), Error> {
+        let mut occupied = vec![0; self.column_weights.len()];
+        for row in &self.rows {
+            if !row.full_width {
+                check_row_columns(&occupied, &row.cells)?;
+            }
+            row.spans(&mut occupied);
+        }
+        Ok(())
+    }
+
+    /// Returns the columns and the number of spanned columns of the cells of all rows.
+    fn cell_spans(&self) -> Vec<Vec<(usize, usize)>> {
+        let mut occupied = vec![0; self.column_weights.len()];
+        self.rows
+            .iter()
+            .map(|row| row.spans(&mut occupied))
+            .collect()
+    }
+
+    /// Adds a row to this table and uses the given background color for all cells that do not
+    /// have a background color.
+    ///
+    /// If `height` is set, the row is at least as high as the given height.  See [`push_row`][]
+    /// for more information.
+    ///
+    /// [`push_row`]: #method.push_row
+    pub fn push_row_with_background(
+        &mut self,
+        cells: Vec<TableCell>,
+        background_color: Color,
+        height: Option<Mm>,
+    ) -> Result<(), Error> {
+        self.push_row_impl(cells, height)?;
+        if let Some(row) = self.rows.last_mut() {
+            row.set_background(background_color);
+        }
+        Ok(())
+    }
+
+    /// Adds a row to this table that consists of a single cell spanning all columns.
+    ///
+    /// This can be used for section headers within a table.  If `height` is set, the row is at
+    /// least as high as the given height.
+    pub fn push_full_width_header<E: IntoBoxedElement>(&mut self, element: E, height: Option<Mm>) {
+        self.rows.push(TableRow {
+            cells: vec![TableCell::new(element.into_boxed_element(), None)],
+            row_height: height,
+            full_width: true,
+        });
+    }
+
+    /// Inserts a row at the given index, shifting all rows after it.
+    ///
+    /// The number of elements in the given vector must match the number of columns and the index
+    /// must not be greater than the number of rows.  If the table has cells that span multiple
+    /// rows, the following rows must still span all columns after the insertion, see
+    /// [`validate_rows`][].  Otherwise, an error is returned and the table is not changed.
+    ///
+    /// [`validate_rows`]: #method.validate_rows
+    pub fn insert_row(
+        &mut self,
+        index: usize,
+        cells: Vec<TableCell>,
+        row_height: Option<i32>,
+    ) -> Result<(), Error> {
+        if index > self.rows.len() {
+            return Err(Error::new(
+                format!(
+                    "Row index {} out of bounds for {} rows",
+                    index,
+                    self.rows.len()
+                ),
+                ErrorKind::InvalidData,
+            ));
+        }
+        self.validate_row(index, &cells)?;
+        let has_row_spans = self.has_row_spans;
+        self.has_row_spans |= cells.iter().any(|cell| cell.rowspan > 1);
+        self.rows.insert(
+            index,
+            TableRow {
+                cells,
+                row_height: row_height.map(Mm::from),
+                full_width: false,
+            },
+        );
+        if self.has_row_spans {
+            if let Err(err) = self.validate_rows() {
+                self.rows.remove(index);
+                self.has_row_spans = has_row_spans;
+                return Err(err);
+            }
+        }
+        if index < self.render_idx {
+            self.render_idx += 1;
+        }
+        Ok(())
+    }
+
+    /// Returns a mutable reference to the row with the given index, or `None` if the index is out
+    /// of bounds.
+    pub fn get_row_mut(&mut self, index: usize) -> Option<&mut TableRow> {
+        self.rows.get_mut(index)
+    }
+
+    /// Removes the row with the given index and returns it, or returns `None` if the index is out
+    /// of bounds.
+    ///
+    /// This method does not check whether the following rows still span all columns of the
+    /// table, see [`validate_rows`][].
+    ///
+    /// [`validate_rows`]: #method.validate_rows
+    pub fn remove_row(&mut self, index: usize) -> Option<TableRow> {
+        if index >= self.rows.len() {
+            return None;
+        }
+        if index < self.render_idx {
+            self.render_idx -= 1;
+        }
+        Some(self.rows.remove(index))
+    }
+
+    /// Swaps the rows with the given indices.
+    ///
+    /// This method does not check whether the rows still span all columns of the table, see
+    /// [`validate_rows`][].
+    ///
+    /// [`validate_rows`]: #method.validate_rows
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` or `b` are out of bounds.
+    pub fn swap_rows(&mut self, a: usize, b: usize) {
+        self.rows.swap(a, b);
+    }
+
+    /// Returns the column widths used for rendering, i. e. the measured column widths for
+    /// [`ColumnWidths::AutoFit`][] or the column widths of this table.
+    ///
+    /// [`ColumnWidths::AutoFit`]: enum.ColumnWidths.html#variant.AutoFit
+    fn effective_column_widths(&self) -> &ColumnWidths {
+        self.auto_fit_widths
+            .as_ref()
+            .unwrap_or(&self.column_weights)
+    }
+
+    /// Measures the natural widths of the cells to determine the column widths for
+    /// [`ColumnWidths::AutoFit`][].
+    ///
+    /// [`ColumnWidths::AutoFit`]: enum.ColumnWidths.html#variant.AutoFit
+    fn measure_column_widths(
+        &mut self,
+        context: &Context,
+        area: render::Area<'_>,
+        style: Style,
+    ) -> ColumnWidths {
+        let spans = self.cell_spans();
+        let mut widths = vec![MIN_COLUMN_WIDTH; self.column_weights.len()];
+        for (row_idx, (row, row_spans)) in self.rows.iter_mut().zip(&spans).enumerate() {
+            for (cell, &(column, span)) in row.cells.iter_mut().zip(row_spans) {
+                if span != 1 {
+                    continue;
+                }
+                let mut width = cell.natural_width(style, context);
+                // Add the space that is used by the cell decorator, e. g. for the borders
+                if let Some(decorator) = &self.cell_decorator {
+                    let cell_area = decorator.prepare_cell(column, row_idx, area.clone());
+                    width += (area.size().width - cell_area.size().width).max(Mm(0.0));
+                }
+                widths[column] = widths[column].max(width);
+            }
+        }
+        let total_width: Mm = widths.iter().copied().sum();
+        let available_width = area.size().width;
+        if total_width > available_width {
+            let factor = available_width.0 / total_width.0;
+            for width in &mut widths {
+                *width *= factor;
+            }
+        }
+        ColumnWidths::PixelWidths(widths.into_iter().map(|width| width.0).collect())
+    }
+
+    /// Returns the minimum height of the row with the given index, i. e. the height of the row or
+    /// the default row height.
+    fn min_row_height(&self, index: usize) -> Option<Mm> {
+        let row_height = self.rows[index].row_height;
+        match (row_height, self.default_row_height) {
+            (Some(row_height), Some(default)) => Some(row_height.max(default)),
+            (row_height, default) => row_height.or(default),
+        }
+    }
+
+    /// Resets the render state of the cells of the rows in the given range.
+    fn reset_rows(&mut self, rows: ops::Range<usize>) {
+        for row in &mut self.rows[rows] {
+            for cell in &mut row.cells {
+                cell.element.reset_render();
+            }
+        }
+    }
+
+    /// Resets and renders the rows in the given range, for example header rows that are repeated
+    /// on a continuation page, without changing the index of the next row.
+    fn render_rows_again(
+        &mut self,
+        rows: ops::Range<usize>,
+        context: &Context,
+        mut area: render::Area<'_>,
+        style: Style,
+        spans: &[Vec<(usize, usize)>],
+    ) -> Result<RenderResult, Error> {
+        let mut result = RenderResult::default();
+        let render_idx = self.render_idx;
+        self.reset_rows(rows.clone());
+        self.render_idx = rows.start;
+        while self.render_idx < rows.end {
+            let row_result = self.render_row(context, area.clone(), style, spans)?;
+            result.size.height += row_result.size.height;
+            area.add_offset(Position::new(0, row_result.size.height));
+            if row_result.has_more {
+                result.has_more = true;
+                break;
+            }
+            self.render_idx += 1;
+        }
+        self.render_idx = render_idx;
+        Ok(result)
+    }
+
+    /// Returns the probable height of the row with the given index.
+    fn row_probable_height(
+        &mut self,
+        index: usize,
+        context: &Context,
+        area: render::Area<'_>,
+        style: Style,
+        spans: &[Vec<(usize, usize)>],
+    ) -> Mm {
+        let areas = area.split_horizontally(self.effective_column_widths());
+        let mut height = self.min_row_height(index).unwrap_or_default();
+        let row = &mut self.rows[index];
+        for (cell, &(column, span)) in row.cells.iter_mut().zip(&spans[index]) {
+            let mut cell_area = areas[column].clone();
+            cell_area.set_width(
+                areas[column..column + span]
+                    .iter()
+                    .map(|a| a.size().width)
+                    .sum(),
+            );
+            let (cell_area, extra) = prepare_cell_area(
+                &self.cell_decorator,
+                column,
+                span,
+                index,
+                cell_area,
+                cell.borders,
+            );
+            height = height.max(cell.probable_height(style, context, cell_area) + extra);
+        }
+        height
     }
 
-    /// Adds a row to this table.
+    /// Returns the index after the last row of the group of rows that starts with the given row.
     ///
-    /// The number of elements in the given vector must match the number of columns.  Otherwise, an
-    /// error is returned.
-    pub fn push_row(
-        &mut self,
-        cells: Vec<TableCell>,
-        row_height: Option<i32>,
-    ) -> Result<(), Error> {
-        if cells.len() == self.column_weights.len() {
-            let r = TableRow { cells, row_height };
-            self.rows.push(r);
-            Ok(())
-        } else {
-            Err(Error::new(
-                format!(
-                    "Expected {} elements in table row, received {}",
-                    self.column_weights.len(),
-                    cells.len()
-                ),
-                ErrorKind::InvalidData,
-            ))
+    /// A group consists of all rows that are spanned by the cells of its first row and of the rows
+    /// that are spanned by the cells of the group.
+    fn row_group_end(&self, start: usize) -> usize {
+        let mut end = start + 1;
+        let mut i = start;
+        while i < end && i < self.rows.len() {
+            for cell in &self.rows[i].cells {
+                end = end.max(i + cell.rowspan);
+            }
+            i += 1;
         }
+        end.min(self.rows.len())
     }
 
     fn render_row(
@@ -2380,75 +5707,278 @@ impl TableLayout {
         context: &Context,
         area: render::Area<'_>,
         style: Style,
+        spans: &[Vec<(usize, usize)>],
     ) -> Result<RenderResult, Error> {
+        let end = self.row_group_end(self.render_idx);
+        if end > self.render_idx + 1 {
+            return self.render_row_group(context, area, style, spans, end);
+        }
+
         let mut result = RenderResult::default();
-        let areas = area.split_horizontally(&self.column_weights);
-        let cell_areas = if let Some(decorator) = &self.cell_decorator {
-            areas
-                .iter()
-                .enumerate()
-                .map(|(i, area)| decorator.prepare_cell(i, self.render_idx, area.clone()))
-                .collect()
-        } else {
-            areas.clone()
-        };
+        let areas = area.split_horizontally(self.effective_column_widths());
+        let spans = &spans[self.render_idx];
+        let areas: Vec<_> = spans
+            .iter()
+            .map(|&(column, span)| {
+                let mut cell_area = areas[column].clone();
+                cell_area.set_width(
+                    areas[column..column + span]
+                        .iter()
+                        .map(|a| a.size().width)
+                        .sum(),
+                );
+                cell_area
+            })
+            .collect();
+        let (cell_areas, extras): (Vec<_>, Vec<_>) = areas
+            .iter()
+            .zip(spans)
+            .zip(&self.rows[self.render_idx].cells)
+            .map(|((area, &(column, span)), cell)| {
+                prepare_cell_area(
+                    &self.cell_decorator,
+                    column,
+                    span,
+                    self.render_idx,
+                    area.clone(),
+                    cell.borders,
+                )
+            })
+            .unzip();
 
         // get row probable height
         let mut row_probable_height = Mm::from(0);
-        for (area, cell) in cell_areas
+        let mut cell_heights = Vec::with_capacity(cell_areas.len());
+        for ((area, cell), extra) in cell_areas
             .clone()
             .iter()
             .zip(self.rows[self.render_idx].cells.iter_mut())
+            .zip(&extras)
         {
-            let el_probable_height = cell
-                .element
-                .get_probable_height(style, context, area.clone());
+            let el_probable_height = cell.probable_height(style, context, area.clone()) + *extra;
             row_probable_height = row_probable_height.max(el_probable_height);
+            cell_heights.push(el_probable_height);
         }
-        if let Some(rh) = self.rows[self.render_idx].row_height {
-            if rh > row_probable_height.0 as i32 {
-                row_probable_height = rh.into();
-            }
+        if let Some(min_height) = self.min_row_height(self.render_idx) {
+            row_probable_height = row_probable_height.max(min_height);
         }
         if row_probable_height > area.size().height {
             result.has_more = true;
             return Ok(result);
         }
 
+        let row_color = self.alternating_row_color(self.render_idx);
         if let Some(decorator) = &mut self.cell_decorator {
             for (i, area) in cell_areas.clone().into_iter().enumerate() {
-                let cell_bg_color = self.rows[self.render_idx].cells[i].background_color;
-                let height = decorator.decorate_cell(
-                    i,
+                let cell = &self.rows[self.render_idx].cells[i];
+                let (column, span) = spans[i];
+                let height = decorator.decorate_spanning_cell(
+                    column,
+                    span,
                     self.render_idx,
                     true,
                     area,
                     row_probable_height,
-                    cell_bg_color,
+                    cell.background_color.or(row_color),
+                    cell.borders,
                 );
                 result.size.height = result.size.height.max(height);
             }
         }
 
         let mut row_height = Mm::from(0);
-        for (area, cell) in cell_areas
+        for (((area, cell), height), extra) in cell_areas
             .iter()
             .zip(self.rows[self.render_idx].cells.iter_mut())
+            .zip(cell_heights)
+            .zip(extras)
         {
-            let element_result = cell.element.render(context, area.clone(), style)?;
+            let offset = cell.vertical_offset(height, row_probable_height);
+            let mut area = area.clone();
+            area.add_offset(Position::new(0, offset));
+            let element_result = cell.render(context, area, style)?;
             result.has_more |= element_result.has_more;
-            row_height = row_height.max(element_result.size.height);
+            row_height = row_height.max(offset + element_result.size.height + extra);
         }
         result.size.height = row_height;
-        if let Some(rh) = self.rows[self.render_idx].row_height {
-            if rh > row_height.0 as i32 {
-                result.size.height = rh.into();
+        if let Some(min_height) = self.min_row_height(self.render_idx) {
+            result.size.height = result.size.height.max(min_height);
+        }
+        Ok(result)
+    }
+
+    /// Renders the rows from `self.render_idx` to `end` that are connected by cells spanning
+    /// multiple rows.
+    ///
+    /// The rows of the group are always rendered on the same page.  The height of each row is
+    /// determined by the cells that span only this row.  If a cell spanning multiple rows needs
+    /// more space, the last row spanned by the cell is enlarged.  On success, `self.render_idx`
+    /// is set to the last row of the group.
+    fn render_row_group(
+        &mut self,
+        context: &Context,
+        area: render::Area<'_>,
+        style: Style,
+        spans: &[Vec<(usize, usize)>],
+        end: usize,
+    ) -> Result<RenderResult, Error> {
+        let mut result = RenderResult::default();
+        let start = self.render_idx;
+        let column_areas = area.split_horizontally(self.effective_column_widths());
+
+        // The cell areas and the probable heights of the cells, by row
+        let mut cells = Vec::new();
+        let mut row_heights = Vec::new();
+        for (row_idx, row_spans) in spans.iter().enumerate().take(end).skip(start) {
+            let mut row_cells = Vec::new();
+            let mut row_height = Mm::from(0);
+            for (cell, &(column, span)) in self.rows[row_idx].cells.iter_mut().zip(row_spans) {
+                let mut cell_area = column_areas[column].clone();
+                cell_area.set_width(
+                    column_areas[column..column + span]
+                        .iter()
+                        .map(|a| a.size().width)
+                        .sum(),
+                );
+                let (cell_area, extra) = prepare_cell_area(
+                    &self.cell_decorator,
+                    column,
+                    span,
+                    row_idx,
+                    cell_area,
+                    cell.borders,
+                );
+                let height = cell.probable_height(style, context, cell_area.clone()) + extra;
+                let last_row = (row_idx + cell.rowspan).min(end) - 1;
+                if last_row == row_idx {
+                    row_height = row_height.max(height);
+                }
+                row_cells.push((column, span, last_row, cell_area, height));
+            }
+            if let Some(min_height) = self.min_row_height(row_idx) {
+                row_height = row_height.max(min_height);
             }
+            cells.push(row_cells);
+            row_heights.push(row_height);
+        }
+
+        // Enlarge the last spanned row if a cell spanning multiple rows needs more space
+        for (i, row_cells) in cells.iter().enumerate() {
+            for &(_, _, last_row, _, height) in row_cells {
+                let last = last_row - start;
+                let available: Mm = row_heights[i..=last].iter().copied().sum();
+                if height > available {
+                    row_heights[last] += height - available;
+                }
+            }
+        }
+
+        let total_height: Mm = row_heights.iter().copied().sum();
+        if total_height > area.size().height {
+            result.has_more = true;
+            return Ok(result);
+        }
+
+        let offsets: Vec<Mm> = row_heights
+            .iter()
+            .scan(Mm::from(0), |offset, &height| {
+                let current = *offset;
+                *offset += height;
+                Some(current)
+            })
+            .collect();
+
+        // Decorate all cells before rendering the elements so that the background colors do not
+        // cover the content
+        let row_colors: Vec<_> = (start..end)
+            .map(|row_idx| self.alternating_row_color(row_idx))
+            .collect();
+        if let Some(decorator) = &mut self.cell_decorator {
+            for (i, row_cells) in cells.iter().enumerate() {
+                let row_idx = start + i;
+                for (j, (column, span, last_row, cell_area, _)) in row_cells.iter().enumerate() {
+                    let cell = &self.rows[row_idx].cells[j];
+                    let height: Mm = row_heights[i..=*last_row - start].iter().copied().sum();
+                    let mut cell_area = cell_area.clone();
+                    cell_area.add_offset(Position::new(0, offsets[i]));
+                    decorator.decorate_spanning_cell(
+                        *column,
+                        *span,
+                        row_idx,
+                        true,
+                        cell_area,
+                        height,
+                        cell.background_color.or(row_colors[i]),
+                        cell.borders,
+                    );
+                }
+            }
+        }
+
+        for (i, row_cells) in cells.into_iter().enumerate() {
+            let row_idx = start + i;
+            for (cell, (_, _, last_row, mut cell_area, height)) in
+                self.rows[row_idx].cells.iter_mut().zip(row_cells)
+            {
+                let available: Mm = row_heights[i..=last_row - start].iter().copied().sum();
+                let offset = offsets[i] + cell.vertical_offset(height, available);
+                cell_area.add_offset(Position::new(0, offset));
+                let element_result = cell.render(context, cell_area, style)?;
+                result.has_more |= element_result.has_more;
+                result.size.height = result.size.height.max(offset + element_result.size.height);
+            }
+        }
+        result.size.height = result.size.height.max(total_height);
+        if !result.has_more {
+            self.render_idx = end - 1;
         }
         Ok(result)
     }
 }
 
+/// Prepares the area of a table cell using the given cell decorator.
+///
+/// Returns the prepared area and the additional height that is required if the overridden borders
+/// of the cell are thicker than the borders of the decorator.
+fn prepare_cell_area<'p>(
+    decorator: &Option<Box<dyn CellDecorator>>,
+    column: usize,
+    span: usize,
+    row: usize,
+    area: render::Area<'p>,
+    borders: Option<Borders>,
+) -> (render::Area<'p>, Mm) {
+    let decorator = match decorator {
+        Some(decorator) => decorator,
+        None => return (area, Mm(0.0)),
+    };
+    let cell_area = decorator.prepare_spanning_cell(column, span, row, area.clone(), borders);
+    if borders.is_none() {
+        return (cell_area, Mm(0.0));
+    }
+    let default_area = decorator.prepare_spanning_cell(column, span, row, area, None);
+    let extra = default_area.size().height - cell_area.size().height;
+    (cell_area, extra.max(Mm(0.0)))
+}
+
+/// Checks whether the given cells span all columns that are not occupied by cells of previous
+/// rows.
+fn check_row_columns(occupied: &[usize], cells: &[TableCell]) -> Result<(), Error> {
+    let available = occupied.iter().filter(|&&rows| rows == 0).count();
+    let columns: usize = cells.iter().map(|cell| cell.colspan).sum();
+    if columns == available {
+        Ok(())
+    } else {
+        Err(Error::new(
+            format!(
+                "Expected {} elements in table row, received {}",
+                available, columns
+            ),
+            ErrorKind::InvalidData,
+        ))
+    }
+}
+
 fn set_cell_decorator(tl: &mut TableLayout, draw_inner_borders: bool, draw_outer_borders: bool) {
     tl.set_cell_decorator(FrameCellDecorator::new(
         draw_inner_borders,
@@ -2458,6 +5988,18 @@ fn set_cell_decorator(tl: &mut TableLayout, draw_inner_borders: bool, draw_outer
 }
 
 impl Element for TableLayout {
+    fn reset_render(&mut self) {
+        self.render_idx = 0;
+        self.overflow_checked = false;
+        self.page_break_forced = false;
+        self.widths_validated = false;
+        for row in &mut self.rows {
+            for cell in &mut row.cells {
+                cell.element.reset_render();
+            }
+        }
+    }
+
     fn render(
         &mut self,
         context: &Context,
@@ -2468,17 +6010,49 @@ impl Element for TableLayout {
         if self.column_weights.is_empty() {
             return Ok(result);
         }
+        if self.overflow != TableOverflow::ContinueOnNextPage && !self.overflow_checked {
+            let height = self.get_probable_height(style, context, area.clone());
+            if height > area.size().height {
+                // Only force a page break if something has been rendered on this page already, as
+                // the table would not fit on a new page either.
+                if !self.page_break_forced && area.size().height < context.page_body_height() {
+                    self.page_break_forced = true;
+                    result.has_more = true;
+                    return Ok(result);
+                }
+                if self.overflow == TableOverflow::Error {
+                    return Err(Error::new(
+                        format!(
+                            "Table with a height of {} does not fit on a page with {}",
+                            height,
+                            area.size().height
+                        ),
+                        ErrorKind::PageSizeExceeded,
+                    ));
+                }
+            }
+            self.overflow_checked = true;
+        }
         if let Some(margins) = self.margins {
             result.size.height += margins.top + margins.bottom;
             area.add_margins(margins);
         }
+        if !self.widths_validated {
+            if let ColumnWidths::AutoFit(_) = self.column_weights {
+                self.auto_fit_widths =
+                    Some(self.measure_column_widths(context, area.clone(), style));
+            }
+            self.validate_column_widths(area.size().width)?;
+            self.widths_validated = true;
+        }
         if let Some(decorator) = &mut self.cell_decorator {
             decorator.set_table_size(self.column_weights.len(), self.rows.len());
         }
         result.size.width = area.size().width;
 
         // render table header row using callback function
-        if let Some(cb) = &self.header_row_callback_fn {
+        let skip_header = self.header_skip_on_first_page && self.render_idx == 0;
+        if let (Some(cb), false) = (&self.header_row_callback_fn, skip_header) {
             let rr = match cb(context.page_number) {
                 Ok(v) => Ok(v),
                 Err(e) => Err(e),
@@ -2486,17 +6060,28 @@ impl Element for TableLayout {
             match rr {
                 Ok(mut element) => {
                     let prob_height = element.get_probable_height(style, context, area.clone());
-                    if prob_height > area.size().height {
-                        log(
-                            "TableHeaderRowSpace",
-                            "Cannot render header row, not enough space",
+                    // A header that is taller than an empty page would never fit, so we skip it
+                    // instead of requesting a new page over and over again.
+                    let page_height = context.page_body_height();
+                    let exceeds_page = page_height > Mm(0.0) && prob_height > page_height;
+                    let exceeds_max = self.max_header_height.map_or(false, |h| prob_height > h);
+                    if exceeds_page || exceeds_max {
+                        context.trace(
+                            LogLevel::Warn,
+                            "Skipping table header row, it exceeds the maximum height",
+                        );
+                    } else if prob_height > area.size().height {
+                        context.trace(
+                            LogLevel::Warn,
+                            "Cannot render table header row, not enough space",
                         );
                         result.has_more = true;
                         return Ok(result);
+                    } else {
+                        let header_result = element.render(context, area.clone(), style)?;
+                        result.size.height += header_result.size.height;
+                        area.add_offset(Position::new(0, header_result.size.height));
                     }
-                    let header_result = element.render(context, area.clone(), style)?;
-                    result.size.height += header_result.size.height;
-                    area.add_offset(Position::new(0, header_result.size.height));
                 }
                 Err(e) => {
                     return Err(e);
@@ -2504,15 +6089,84 @@ impl Element for TableLayout {
             };
         };
 
-        while self.render_idx < self.rows.len() {
-            let row_result = self.render_row(context, area.clone(), style)?;
+        let spans = self.cell_spans();
+
+        // repeat the header rows on continuation pages
+        let header_rows = self.header_rows.min(self.rows.len());
+        if header_rows > 0 && self.render_idx >= header_rows {
+            // If the header rows and the next row do not fit on an empty page, repeating the
+            // header rows would request a new page over and over again, so we skip them.
+            self.reset_rows(0..header_rows);
+            let header_height: Mm = (0..header_rows)
+                .map(|row| self.row_probable_height(row, context, area.clone(), style, &spans))
+                .sum();
+            let next_row_height = if self.render_idx < self.rows.len() {
+                self.row_probable_height(self.render_idx, context, area.clone(), style, &spans)
+            } else {
+                Mm(0.0)
+            };
+            let page_height = context.page_body_height();
+            let exceeds_page =
+                page_height > Mm(0.0) && header_height + next_row_height > page_height;
+            if exceeds_page {
+                context.trace(
+                    LogLevel::Warn,
+                    "Skipping table header rows, they do not fit on a page with the next row",
+                );
+            } else {
+                let header_result =
+                    self.render_rows_again(0..header_rows, context, area.clone(), style, &spans)?;
+                result.size.height += header_result.size.height;
+                area.add_offset(Position::new(0, header_result.size.height));
+                if header_result.has_more {
+                    context.trace(
+                        LogLevel::Warn,
+                        "Cannot render table header rows, not enough space",
+                    );
+                    result.has_more = true;
+                    return Ok(result);
+                }
+            }
+        }
+
+        // reserve space for the footer rows at the bottom of the page
+        let footer_rows = self
+            .footer_rows
+            .min(self.rows.len().saturating_sub(header_rows));
+        let body_end = self.rows.len() - footer_rows;
+        self.reset_rows(body_end..self.rows.len());
+        let footer_height = (body_end..self.rows.len())
+            .map(|row| self.row_probable_height(row, context, area.clone(), style, &spans))
+            .sum();
+        let mut body_area = area.clone();
+        body_area.set_height(area.size().height - footer_height);
+
+        let first_row = self.render_idx;
+        while self.render_idx < body_end {
+            let row_result = self.render_row(context, body_area.clone(), style, &spans)?;
             result.size.height += row_result.size.height;
             area.add_offset(Position::new(0, row_result.size.height));
+            body_area.add_offset(Position::new(0, row_result.size.height));
             if row_result.has_more {
                 break;
             }
             self.render_idx += 1;
         }
+
+        // render the footer rows after the rows on this page, and a last time after the last row
+        if footer_rows > 0 && (self.render_idx > first_row || self.render_idx == body_end) {
+            let footer_result =
+                self.render_rows_again(body_end..self.rows.len(), context, area, style, &spans)?;
+            result.size.height += footer_result.size.height;
+            if footer_result.has_more {
+                context.trace(
+                    LogLevel::Warn,
+                    "Cannot render table footer rows, not enough space",
+                );
+            } else if self.render_idx == body_end {
+                self.render_idx = self.rows.len();
+            }
+        }
         result.has_more = self.render_idx < self.rows.len();
         Ok(result)
     }
@@ -2525,19 +6179,18 @@ impl Element for TableLayout {
     ) -> Mm {
         let mut height = Mm::from(0);
         // calculate table height using rows
-        for row in self.rows.iter_mut() {
-            let mut row_height = Mm::from(0);
-            for cell in row.cells.iter_mut() {
-                let cell_height = cell
-                    .element
-                    .get_probable_height(style, context, area.clone());
+        for i in 0..self.rows.len() {
+            let mut row_height = self.min_row_height(i).unwrap_or_default();
+            for cell in self.rows[i].cells.iter_mut() {
+                let cell_height = cell.probable_height(style, context, area.clone());
                 row_height = row_height.max(cell_height);
             }
             height += row_height;
         }
 
         // TODO: calculate table height row height
-        if let Some(cb) = &self.header_row_callback_fn {
+        let skip_header = self.header_skip_on_first_page && self.render_idx == 0;
+        if let (Some(cb), false) = (&self.header_row_callback_fn, skip_header) {
             let rr = match cb(context.page_number) {
                 Ok(v) => Ok(v),
                 Err(e) => Err(e),
@@ -2545,7 +6198,9 @@ impl Element for TableLayout {
             match rr {
                 Ok(mut element) => {
                     let header_height = element.get_probable_height(style, context, area.clone());
-                    height += header_height;
+                    if self.max_header_height.map_or(true, |h| header_height <= h) {
+                        height += header_height;
+                    }
                 }
                 Err(_) => {
                     return Mm::from(0);
@@ -2561,3 +6216,843 @@ impl Element for TableLayout {
         height
     }
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::{LinearLayout, Paragraph};
+    use crate::{fonts, render, style, Context, Element as _, Mm, Size};
+
+    const FONT_FILES: &[&str] = &[
+        "/usr/share/fonts/liberation/LiberationSans-Regular.ttf",
+        "/usr/share/fonts/truetype/liberation/LiberationSans-Regular.ttf",
+        "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf",
+    ];
+
+    const TEXT: &str = "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod \
+        tempor incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud \
+        exercitation ullamco laboris nisi ut aliquip ex ea commodo consequat.";
+
+    pub(crate) fn get_font_family() -> fonts::FontFamily<fonts::FontData> {
+        let path = FONT_FILES
+            .iter()
+            .find(|path| std::path::Path::new(path).exists())
+            .expect("Could not find font file");
+        let font = fonts::FontData::load(path, None).expect("Failed to load font");
+        fonts::FontFamily {
+            regular: font.clone(),
+            bold: font.clone(),
+            italic: font.clone(),
+            bold_italic: font,
+        }
+    }
+
+    fn get_context() -> Context {
+        Context::new(fonts::FontCache::new(get_font_family()))
+    }
+
+    /// Returns the line height of the default style with the test font.
+    fn get_line_height() -> Mm {
+        style::Style::new().line_height(&get_context().font_cache)
+    }
+
+    /// Calls the given function with a context, the area of a page with the given size and the
+    /// default style, and returns the uncompressed PDF document.
+    fn test_area<F>(page_size: impl Into<Size>, f: F) -> String
+    where
+        F: FnOnce(&Context, render::Area<'_>, style::Style),
+    {
+        let mut context = get_context();
+        let renderer = render::Renderer::new(page_size, "test").unwrap();
+        context.font_cache.load_pdf_fonts(&renderer).unwrap();
+        f(
+            &context,
+            renderer.first_page().first_layer().area(),
+            style::Style::new(),
+        );
+        let mut data = Vec::new();
+        renderer
+            .with_compression(render::CompressionLevel::None)
+            .write(&mut data)
+            .unwrap();
+        String::from_utf8_lossy(&data).into_owned()
+    }
+
+    #[test]
+    fn test_paragraph_line_spacing() {
+        test_area(Size::new(100, 200), |context, area, style| {
+            let mut single = Paragraph::new(TEXT);
+            let mut double = Paragraph::new(TEXT);
+            double.set_line_spacing(2.0);
+            assert!(single.line_count(context, style, area.clone()) > 1);
+
+            let single_height = single.get_probable_height(style, context, area.clone());
+            let double_height = double.get_probable_height(style, context, area.clone());
+            assert!((double_height.0 - 2.0 * single_height.0).abs() < 0.001);
+
+            let single_result = single.render(context, area.clone(), style).unwrap();
+            let double_result = double.render(context, area, style).unwrap();
+            assert!(!double_result.has_more);
+            assert!(
+                (double_result.size.height.0 - 2.0 * single_result.size.height.0).abs() < 0.001
+            );
+        });
+    }
+
+    #[test]
+    fn test_paragraph_line_height() {
+        let context = get_context();
+        let style = style::Style::new();
+
+        let mut paragraph = Paragraph::new(TEXT);
+        let single_height = paragraph.get_line_height(style, &context);
+        assert_eq!(single_height, style.line_height(&context.font_cache));
+
+        paragraph.set_line_spacing(2.0);
+        paragraph.set_font_size(20);
+        let expected = style
+            .with_font_size(20)
+            .with_line_spacing(2.0)
+            .line_height(&context.font_cache);
+        assert_eq!(paragraph.get_line_height(style, &context), expected);
+        assert!(paragraph.get_line_height(style, &context) > single_height);
+    }
+
+    #[test]
+    fn test_paragraph_first_line_indent() {
+        test_area(Size::new(100, 200), |context, area, style| {
+            let line_height = style.line_height(&context.font_cache);
+
+            let mut paragraph = Paragraph::new("aaa bbb");
+            let result = paragraph.render(context, area.clone(), style).unwrap();
+            assert_eq!(result.size.height, line_height);
+
+            let mut paragraph = Paragraph::new("aaa bbb").with_first_line_indent(90);
+            assert_eq!(
+                paragraph.get_probable_height(style, context, area.clone()),
+                line_height * 2.0
+            );
+            let result = paragraph.render(context, area, style).unwrap();
+            assert_eq!(result.size.height, line_height * 2.0);
+        });
+    }
+
+    #[test]
+    fn test_paragraph_space_collapsing() {
+        test_area(Size::new(100, 200), |context, area, style| {
+            let line_height = style.line_height(&context.font_cache);
+            let first = Paragraph::new("first").with_space_after(5);
+            let second = Paragraph::new("second").with_space_before(3);
+
+            let mut layout = LinearLayout::vertical().element(first).element(second);
+            let result = layout.render(context, area.clone(), style).unwrap();
+            assert!(!result.has_more);
+            let expected = line_height + line_height + Mm(5.0);
+            assert!((result.size.height.0 - expected.0).abs() < 0.001);
+
+            // Empty paragraphs do not collapse the space after the previous element.
+            let first = Paragraph::new("first").with_space_after(5);
+            let empty = Paragraph::default().with_space_before(5);
+            let second = Paragraph::new("second");
+            let mut layout = LinearLayout::vertical()
+                .element(first)
+                .element(empty)
+                .element(second);
+            let result = layout.render(context, area, style).unwrap();
+            assert!(!result.has_more);
+            assert!((result.size.height.0 - expected.0).abs() < 0.001);
+        });
+    }
+
+    #[test]
+    fn test_paragraph_space_before_continued() {
+        let page_size = Size::new(100, get_line_height() * 2.0 + Mm(10.0));
+        test_area(page_size, |context, area, style| {
+            let mut paragraph = Paragraph::new(TEXT).with_space_before(10);
+            assert!(paragraph.get_probable_height(style, context, area.clone()) > Mm(10.0));
+            let result = paragraph.render(context, area.clone(), style).unwrap();
+            assert!(result.has_more);
+            assert!(paragraph.get_probable_height(style, context, area) < Mm(10.0));
+        });
+    }
+
+    #[test]
+    fn test_replace_placeholders() {
+        let mut context = get_context();
+        context.page_number = 3;
+        assert_eq!(
+            super::replace_placeholders("Page #{page} of #{total_pages}", &context),
+            "Page 3 of "
+        );
+        context.total_pages = Some(12);
+        assert_eq!(
+            super::replace_placeholders("Page #{page} of #{total_pages}", &context),
+            "Page 3 of 12"
+        );
+    }
+
+    /// Wraps the given words using the given width and returns the text of the lines.
+    fn wrap_lines(context: &Context, words: &[style::StyledString], width: Mm) -> Vec<String> {
+        crate::wrap::Wrapper::new(words.iter().map(Into::into), context, width)
+            .map(|(line, _)| line.iter().map(|s| s.s.as_ref()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_hyphenator() {
+        use crate::Hyphenator;
+
+        // Allows a break after every second byte.
+        struct Pairs;
+
+        impl Hyphenator for Pairs {
+            fn hyphenate(&self, word: &str) -> Vec<usize> {
+                (2..word.len()).step_by(2).collect()
+            }
+        }
+
+        let mut context = get_context();
+        let style = style::Style::new();
+        let words = vec![style::StyledString::new("abcdefgh", style)];
+        let width = style.str_width(&context.font_cache, "abcd-");
+        // Without a hyphenator, the word does not fit into the line.
+        assert!(wrap_lines(&context, &words, width).is_empty());
+
+        // The longest first part that fits into the line is used.
+        context.hyphenator = Some(Box::new(Pairs));
+        assert_eq!(wrap_lines(&context, &words, width), vec!["abcd-", "efgh"]);
+    }
+
+    #[test]
+    fn test_non_breaking_words() {
+        use crate::wrap::Words;
+
+        let context = get_context();
+        let style = style::Style::new();
+        let width = style.str_width(&context.font_cache, "aaa bbb ");
+        let text = |group: style::StyledString| {
+            Words::new(vec![style::StyledString::new("aaa ", style), group]).collect::<Vec<_>>()
+        };
+
+        let words = text(style::StyledString::new("bbb ccc", style));
+        assert_eq!(wrap_lines(&context, &words, width), vec!["aaa bbb ", "ccc"]);
+
+        // The group is moved to the next line as a whole.
+        let words = text(style::StyledString::new("bbb ccc", style).non_breaking());
+        assert_eq!(words.len(), 2);
+        assert_eq!(wrap_lines(&context, &words, width), vec!["aaa ", "bbb ccc"]);
+
+        // Groups that are longer than a line are never split.
+        let words = text(style::StyledString::new("bbb ccc ddd", style).non_breaking());
+        assert!(wrap_lines(&context, &words, width).is_empty());
+    }
+
+    #[test]
+    fn test_script_position() {
+        let context = get_context();
+        let style = style::Style::new();
+        let superscript = style.and(style::Effect::Superscript);
+        let subscript = style.and(style::Effect::Subscript);
+
+        let width = style.str_width(&context.font_cache, "2");
+        let script_width = superscript.str_width(&context.font_cache, "2");
+        assert!((script_width.0 - 0.65 * width.0).abs() < 0.001);
+        assert_eq!(
+            superscript.line_height(&context.font_cache),
+            style.line_height(&context.font_cache)
+        );
+        assert!(superscript.script_rise(&context.font_cache) > Mm(0.0));
+        assert!(subscript.script_rise(&context.font_cache) < Mm(0.0));
+        assert_eq!(style.script_rise(&context.font_cache), Mm(0.0));
+    }
+
+    #[test]
+    fn test_letter_spacing() {
+        let context = get_context();
+        let style = style::Style::new();
+        let tracked = style.with_letter_spacing(1);
+        let compressed = style.with_letter_spacing(-0.5);
+
+        let width = style.str_width(&context.font_cache, "abcd");
+        assert_eq!(
+            tracked.str_width(&context.font_cache, "abcd"),
+            width + Mm(3.0)
+        );
+        assert_eq!(
+            compressed.str_width(&context.font_cache, "abcd"),
+            width - Mm(1.5)
+        );
+        assert_eq!(
+            tracked.str_width(&context.font_cache, "a"),
+            style.str_width(&context.font_cache, "a")
+        );
+    }
+
+    #[test]
+    fn test_font_scale() {
+        let mut context = get_context();
+        let style = style::Style::new();
+        let width = style.str_width(&context.font_cache, "abcd");
+        let line_height = style.line_height(&context.font_cache);
+        let metrics = style.metrics(&context.font_cache);
+
+        context.font_cache.set_font_scale(2.0);
+        assert_eq!(context.font_scale(), 2.0);
+        assert_eq!(style.str_width(&context.font_cache, "abcd"), width * 2.0);
+        assert_eq!(style.line_height(&context.font_cache), line_height * 2.0);
+        let scaled = style.metrics(&context.font_cache);
+        assert_eq!(scaled.line_height, metrics.line_height * 2.0);
+        assert_eq!(scaled.glyph_height, metrics.glyph_height * 2.0);
+        assert_eq!(scaled.ascent, metrics.ascent * 2.0);
+    }
+
+    #[test]
+    fn test_style_diff() {
+        let style = style::Style::new();
+        let other = style
+            .with_letter_spacing(0.5)
+            .with_script_position(style::ScriptPosition::Superscript);
+
+        let diff = style.diff(&other);
+        assert_eq!(diff.letter_spacing, Some(Mm(0.5)));
+        assert_eq!(
+            diff.script_position,
+            Some(style::ScriptPosition::Superscript)
+        );
+        assert_eq!(style.apply_diff(&diff), other);
+        assert!(other.diff(&style.apply_diff(&diff)).is_empty());
+    }
+
+    #[test]
+    fn test_framed_element_fill() {
+        use super::{Break, FramedElement};
+
+        let content = test_area(Size::new(100, 200), |context, area, style| {
+            let mut framed =
+                FramedElement::with_fill(Break::from_mm(50), style::Color::Rgb(255, 0, 0), 1)
+                    .with_inner_padding(4);
+            let result = framed.render(context, area, style).unwrap();
+            assert!(!result.has_more);
+            assert_eq!(result.size, Size::new(100, 60));
+        });
+
+        // The fill covers the element, the padding and the frame:  60 mm from the top of the
+        // 200 mm page.
+        assert!(content.contains("1.00 0.00 0.00 rg\n"));
+        assert!(
+            content.contains("0.00 566.93 m\n283.46 566.93 l\n283.46 396.85 l\n0.00 396.85 l\nb\n")
+        );
+    }
+
+    #[test]
+    fn test_definition_list() {
+        test_area(Size::new(100, 200), |context, area, style| {
+            let term_height = style.bold().line_height(&context.font_cache);
+            let description_height = style.line_height(&context.font_cache);
+
+            let mut list = super::DefinitionList::new()
+                .with_description_indent(20)
+                .entry("PDF", Paragraph::new("Portable Document Format"))
+                .entry("TTF", Paragraph::new("TrueType Font"));
+            assert_eq!(list.entry_count(), 2);
+            let result = list.render(context, area, style).unwrap();
+            assert!(!result.has_more);
+            assert!(
+                (result.size.height.0 - 2.0 * (term_height.0 + description_height.0)).abs() < 0.001
+            );
+        });
+    }
+
+    #[test]
+    fn test_named_list_counter() {
+        use super::OrderedList;
+
+        test_area(Size::new(100, 200), |context, area, style| {
+            let mut list = OrderedList::with_named_counter("steps");
+            list.push(Paragraph::new("first"));
+            list.push(Paragraph::new("second"));
+            assert_eq!(list.item_count(), 2);
+            assert_eq!(list.next_number(), 3);
+            list.set_next_number(5);
+            list.push(Paragraph::new("fifth"));
+
+            // Measuring the list does not change the counter.
+            context.set_list_counter("steps", 3);
+            assert!(list.get_probable_height(style, context, area.clone()) > Mm(0.0));
+            assert_eq!(context.list_counter("steps"), 3);
+
+            // The numbers are offset by the counter:  3, 4 and 7.
+            let result = list.render(context, area, style).unwrap();
+            assert!(!result.has_more);
+            assert_eq!(context.list_counter("steps"), 8);
+            assert_eq!(list.item_count(), 3);
+        });
+    }
+
+    #[test]
+    fn test_number_formats() {
+        use super::{format_ordinal, format_roman_lower, format_roman_upper};
+
+        let ordinals: Vec<_> = [1, 2, 3, 4, 11, 12, 13, 21, 22, 101, 111]
+            .iter()
+            .map(|&n| format_ordinal(n))
+            .collect();
+        assert_eq!(
+            ordinals,
+            [
+                "1st", "2nd", "3rd", "4th", "11th", "12th", "13th", "21st", "22nd", "101st",
+                "111th"
+            ]
+        );
+        assert_eq!(format_roman_upper(0), "0");
+        assert_eq!(format_roman_upper(4), "IV");
+        assert_eq!(format_roman_upper(1994), "MCMXCIV");
+        assert_eq!(format_roman_lower(2024), "mmxxiv");
+    }
+
+    #[test]
+    fn test_table_column_validation() {
+        use super::{ColumnWidths, TableCell, TableLayout};
+
+        test_area(Size::new(100, 200), |context, area, style| {
+            let widths = ColumnWidths::Weights(vec![0, 0]);
+            assert_eq!(widths.resolve(Mm(100.0)), vec![Mm(0.0), Mm(0.0)]);
+            let areas = area.split_horizontally(&widths);
+            assert!(areas.iter().all(|a| a.size().width == Mm(0.0)));
+
+            assert!(TableLayout::try_new(widths.clone()).is_err());
+            let mut table = TableLayout::new(widths);
+            let cells = vec![TableCell::text("a", None), TableCell::text("b", None)];
+            assert!(table.push_row(cells, None).is_err());
+            assert!(table.render(context, area.clone(), style).is_err());
+
+            let mut table = TableLayout::new(ColumnWidths::PixelWidths(vec![10.0, -1.0]));
+            assert!(table.validate_column_widths(Mm(100.0)).is_err());
+            assert!(table.render(context, area, style).is_err());
+        });
+    }
+
+    #[test]
+    fn test_table_builder() {
+        use super::{ColumnWidths, TableBuilder, TableCell};
+        use crate::error::ErrorKind;
+
+        let builder = || TableBuilder::new(ColumnWidths::Weights(vec![1, 1]));
+        let data = vec![("a", "b"), ("c", "d")];
+        let table = builder()
+            .data_rows(
+                data.into_iter()
+                    .map(|(a, b)| vec![TableCell::text(a, None), TableCell::text(b, None)]),
+            )
+            .header_row()
+            .cell(Paragraph::new("H1"), None)
+            .cell(Paragraph::new("H2"), None)
+            .end_row()
+            .inner_borders(true)
+            .build()
+            .unwrap();
+        assert_eq!(table.rows.len(), 3);
+        assert_eq!(table.borders(), (true, false));
+        let first = table.rows[0].cells[0].element.as_plain_text_extractor();
+        assert_eq!(first.unwrap().extract(), "H1");
+
+        // Rows with too few or too many cells are rejected, even if later rows are valid.
+        let err = builder()
+            .row()
+            .cell(Paragraph::new("a"), None)
+            .end_row()
+            .row()
+            .cell(Paragraph::new("b"), None)
+            .cell(Paragraph::new("c"), None)
+            .end_row()
+            .build()
+            .err()
+            .unwrap();
+        assert!(matches!(err.kind(), ErrorKind::InvalidData));
+        let err = builder()
+            .data_rows(vec![vec![
+                TableCell::text("a", None),
+                TableCell::text("b", None),
+                TableCell::text("c", None),
+            ]])
+            .build()
+            .err()
+            .unwrap();
+        assert!(matches!(err.kind(), ErrorKind::InvalidData));
+
+        // Rows below a cell that spans multiple rows have fewer cells.
+        let table = builder()
+            .row()
+            .table_cell(TableCell::text("a", None).with_rowspan(2))
+            .cell(Paragraph::new("b"), None)
+            .end_row()
+            .row()
+            .cell(Paragraph::new("c"), None)
+            .end_row()
+            .build()
+            .unwrap();
+        assert_eq!(table.rows.len(), 2);
+        assert!(TableBuilder::new(ColumnWidths::Weights(vec![]))
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_table_spans() {
+        use super::{ColumnWidths, TableCell, TableLayout};
+
+        test_area(Size::new(100, 200), |context, area, style| {
+            let line_height = style.line_height(&context.font_cache);
+
+            let mut table = TableLayout::new(ColumnWidths::Weights(vec![1, 1, 1]));
+            let cell = |text: &str| TableCell::text(text, None);
+            table
+                .push_row(vec![cell("a").with_colspan(2), cell("b")], None)
+                .unwrap();
+            assert!(table
+                .push_row(vec![cell("a").with_colspan(2)], None)
+                .is_err());
+            table
+                .push_row(vec![cell("c").with_rowspan(3), cell(TEXT), cell("d")], None)
+                .unwrap();
+            assert!(table
+                .push_row(vec![cell("e"), cell("f"), cell("g")], None)
+                .is_err());
+            table.push_row(vec![cell("e"), cell("f")], None).unwrap();
+            table
+                .push_row(vec![cell("g").with_colspan(2)], None)
+                .unwrap();
+            table
+                .push_row(vec![cell("h"), cell("i"), cell("j")], None)
+                .unwrap();
+            assert!(table.validate_rows().is_ok());
+            // The following rows would not span all columns after the insertion.
+            assert!(table
+                .insert_row(2, vec![cell("x"), cell("y")], None)
+                .is_err());
+            assert!(table.validate_rows().is_ok());
+
+            let result = table.render(context, area, style).unwrap();
+            assert!(!result.has_more);
+            assert!(result.size.height > line_height * 5.0);
+
+            assert!(table.remove_row(2).is_some());
+            assert!(table.validate_rows().is_err());
+        });
+    }
+
+    #[test]
+    fn test_table_header_rows() {
+        use super::{ColumnWidths, TableCell, TableLayout};
+
+        let line_height = get_line_height();
+        test_area(Size::new(100, line_height * 3.5), |context, area, style| {
+            let mut table = TableLayout::new(ColumnWidths::Weights(vec![1, 1]));
+            table.set_header_rows(1);
+            for i in 0..8 {
+                let cells = vec![
+                    TableCell::text(i.to_string(), None),
+                    TableCell::text("x", None),
+                ];
+                table.push_row(cells, None).unwrap();
+            }
+
+            let result = table.render(context, area.clone(), style).unwrap();
+            assert!(result.has_more);
+            assert_eq!(table.current_row(), 3);
+            let result = table.render(context, area, style).unwrap();
+            assert!(result.has_more);
+            assert_eq!(result.size.height, line_height * 3.0);
+            assert_eq!(table.current_row(), 5);
+        });
+    }
+
+    #[test]
+    fn test_table_header_rows_reset() {
+        use super::{Break, ColumnWidths, TableCell, TableLayout};
+
+        let line_height = get_line_height();
+        test_area(Size::new(100, line_height * 4.5), |context, area, style| {
+            // The break in the header row must have the same height on every page.
+            let mut table = TableLayout::new(ColumnWidths::Weights(vec![1, 1]));
+            table.set_header_rows(1);
+            let cells = vec![
+                TableCell::new(Box::new(Break::from_mm(line_height * 2.0)), None),
+                TableCell::text("Header", None),
+            ];
+            table.push_row(cells, None).unwrap();
+            for i in 0..8 {
+                let cells = vec![
+                    TableCell::text(i.to_string(), None),
+                    TableCell::text("x", None),
+                ];
+                table.push_row(cells, None).unwrap();
+            }
+
+            let result = table.render(context, area.clone(), style).unwrap();
+            assert!(result.has_more);
+            assert_eq!(table.current_row(), 3);
+            let result = table.render(context, area, style).unwrap();
+            assert!(result.has_more);
+            assert_eq!(table.current_row(), 5);
+        });
+    }
+
+    #[test]
+    fn test_table_header_rows_exceed_page() {
+        use super::{ColumnWidths, TableCell, TableLayout};
+        use crate::{utils::NoopTracer, Document};
+
+        // The header rows and a body row do not fit on a 60 mm page, so the header rows are only
+        // rendered on the first page.
+        let mut table = TableLayout::new(ColumnWidths::Weights(vec![1]));
+        table.set_header_rows(1);
+        table
+            .push_row(vec![TableCell::text("Header", None)], Some(30))
+            .unwrap();
+        for i in 0..3 {
+            table
+                .push_row(vec![TableCell::text(i.to_string(), None)], Some(40))
+                .unwrap();
+        }
+        let mut doc = Document::new(get_font_family());
+        doc.set_paper_size(Size::new(100, 60));
+        doc.set_tracer(NoopTracer);
+        doc.push(table);
+        let stats = doc.render_with_stats(std::io::sink()).unwrap();
+        assert_eq!(stats.page_count, 4);
+    }
+
+    #[test]
+    fn test_table_footer_rows() {
+        use super::{ColumnWidths, TableCell, TableLayout};
+
+        let line_height = get_line_height();
+        test_area(Size::new(100, line_height * 3.5), |context, area, style| {
+            let mut table = TableLayout::new(ColumnWidths::Weights(vec![1, 1]));
+            table.set_footer_rows(1);
+            for i in 0..8 {
+                let cells = vec![
+                    TableCell::text(i.to_string(), None),
+                    TableCell::text("x", None),
+                ];
+                table.push_row(cells, None).unwrap();
+            }
+            let cells = vec![TableCell::text("Total", None), TableCell::text("8", None)];
+            table.push_row(cells, None).unwrap();
+
+            for page in 1..=4 {
+                let result = table.render(context, area.clone(), style).unwrap();
+                assert_eq!(result.size.height, line_height * 3.0);
+                assert_eq!(result.has_more, page < 4);
+                assert_eq!(table.current_row(), if page < 4 { page * 2 } else { 9 });
+            }
+        });
+    }
+
+    #[test]
+    fn test_table_cell_padding() {
+        use super::{ColumnWidths, TableCell, TableLayout};
+
+        test_area(Size::new(100, 200), |context, area, style| {
+            let line_height = style.line_height(&context.font_cache);
+
+            let mut table = TableLayout::new(ColumnWidths::Weights(vec![1, 1]));
+            let cells = vec![
+                TableCell::text("a", None).with_padding((2, 1)),
+                TableCell::text("b", None),
+            ];
+            table.push_row(cells, None).unwrap();
+            assert_eq!(
+                table.get_probable_height(style, context, area.clone()),
+                line_height + Mm(4.0)
+            );
+            let result = table.render(context, area, style).unwrap();
+            assert_eq!(result.size.height, line_height + Mm(4.0));
+        });
+    }
+
+    #[test]
+    fn test_table_cell_borders() {
+        use super::{CellDecorator, ColumnWidths, TableCell, TableLayout};
+        use crate::style::{Color, LineStyle};
+        use crate::Borders;
+        use std::{cell, rc};
+
+        // Records the borders that are passed to decorate_cell.
+        struct Decorator(rc::Rc<cell::RefCell<Vec<Option<Borders>>>>);
+
+        impl CellDecorator for Decorator {
+            fn decorate_cell(
+                &mut self,
+                _column: usize,
+                _row: usize,
+                _has_more: bool,
+                _area: render::Area<'_>,
+                row_height: Mm,
+                _bg_color: Option<Color>,
+                borders: Option<Borders>,
+            ) -> Mm {
+                self.0.borrow_mut().push(borders);
+                row_height
+            }
+        }
+
+        test_area(Size::new(100, 200), |context, area, style| {
+            let line_height = style.line_height(&context.font_cache);
+
+            // Decorators that only implement decorate_cell receive the overridden borders.
+            let borders = rc::Rc::new(cell::RefCell::new(Vec::new()));
+            let mut table = TableLayout::new(ColumnWidths::Weights(vec![1, 1]));
+            table.set_cell_decorator(Decorator(borders.clone()));
+            let cells = vec![
+                TableCell::no_borders(Paragraph::new("a")),
+                TableCell::text("b", None),
+            ];
+            table.push_row(cells, None).unwrap();
+            table.render(context, area.clone(), style).unwrap();
+            assert_eq!(*borders.borrow(), vec![Some(Borders::none()), None]);
+
+            // Overridden edges are drawn even if the decorator does not draw them, and their
+            // thickness is added to the row height.
+            let mut table = TableLayout::new(ColumnWidths::Weights(vec![1, 1]));
+            let line_style = LineStyle::new().with_thickness(2);
+            let cells = vec![
+                TableCell::all_borders(Paragraph::new("a"), line_style),
+                TableCell::text("b", None),
+            ];
+            table.push_row(cells, None).unwrap();
+            let result = table.render(context, area, style).unwrap();
+            assert_eq!(result.size.height, line_height + Mm(4.0));
+        });
+    }
+
+    #[test]
+    fn test_table_cell_vertical_alignment() {
+        use super::{ColumnWidths, TableCell, TableLayout};
+        use crate::VerticalAlignment;
+
+        test_area(Size::new(100, 200), |context, area, style| {
+            let cell = TableCell::text("a", None);
+            assert_eq!(cell.vertical_offset(Mm(5.0), Mm(15.0)), Mm(0.0));
+            let cell = cell.with_vertical_alignment(VerticalAlignment::Middle);
+            assert_eq!(cell.vertical_offset(Mm(5.0), Mm(15.0)), Mm(5.0));
+            let cell = cell.with_vertical_alignment(VerticalAlignment::Bottom);
+            assert_eq!(cell.vertical_offset(Mm(5.0), Mm(15.0)), Mm(10.0));
+            assert_eq!(cell.vertical_offset(Mm(0.0), Mm(15.0)), Mm(0.0));
+
+            let mut table = TableLayout::new(ColumnWidths::Weights(vec![1, 1]));
+            let cells = vec![TableCell::text(TEXT, None), TableCell::text("a", None)];
+            table.push_row(cells, None).unwrap();
+            let top_result = table.render(context, area.clone(), style).unwrap();
+
+            let mut table = TableLayout::new(ColumnWidths::Weights(vec![1, 1]));
+            let cells = vec![TableCell::text(TEXT, None), cell];
+            table.push_row(cells, None).unwrap();
+            let result = table.render(context, area, style).unwrap();
+            assert!(!result.has_more);
+            assert_eq!(result.size.height, top_result.size.height);
+        });
+    }
+
+    #[test]
+    fn test_table_min_row_height() {
+        use super::{ColumnWidths, TableCell, TableLayout};
+
+        test_area(Size::new(100, 200), |context, area, style| {
+            let mut table = TableLayout::new(ColumnWidths::Weights(vec![1, 1]));
+            table.set_default_row_height(Mm(10.0));
+            table
+                .row()
+                .element(Paragraph::new("a"))
+                .element(Paragraph::new("b"))
+                .push()
+                .unwrap();
+            table
+                .row()
+                .element(Paragraph::new("c"))
+                .element(Paragraph::new("d"))
+                .with_min_height(Mm(15.0))
+                .push()
+                .unwrap();
+            assert_eq!(
+                table.get_probable_height(style, context, area.clone()),
+                Mm(25.0)
+            );
+
+            // Fractional minimum heights are not rounded.
+            table
+                .row()
+                .element(Paragraph::new("e"))
+                .element(Paragraph::new("f"))
+                .with_min_height(Mm(12.5))
+                .push()
+                .unwrap();
+            let cells = vec![TableCell::text("g", None), TableCell::text("h", None)];
+            table
+                .push_row_with_background(cells, style::Color::Rgb(200, 200, 200), Some(Mm(10.5)))
+                .unwrap();
+            table.push_full_width_header(Paragraph::new("i"), Some(Mm(11.5)));
+            assert_eq!(
+                table.get_probable_height(style, context, area.clone()),
+                Mm(59.5)
+            );
+            let result = table.render(context, area, style).unwrap();
+            assert_eq!(result.size.height, Mm(59.5));
+        });
+    }
+
+    #[test]
+    fn test_constrained_column_widths() {
+        use super::ColumnWidths;
+
+        let widths = ColumnWidths::Constrained {
+            weights: vec![1, 1, 1],
+            min_widths: vec![Some(Mm(50.0)), None, None],
+            max_widths: vec![None, None, Some(Mm(10.0))],
+        };
+        assert_eq!(
+            widths.try_resolve(Mm(100.0)).unwrap(),
+            vec![Mm(50.0), Mm(40.0), Mm(10.0)]
+        );
+        assert!(widths.try_resolve(Mm(40.0)).is_err());
+
+        let widths = ColumnWidths::Constrained {
+            weights: vec![1, 1],
+            min_widths: vec![None, None],
+            max_widths: vec![Some(Mm(20.0)), Some(Mm(20.0))],
+        };
+        assert!(widths.try_resolve(Mm(100.0)).is_err());
+        assert_eq!(widths.resolve(Mm(100.0)), vec![Mm(50.0), Mm(50.0)]);
+    }
+
+    #[test]
+    fn test_auto_fit_column_widths() {
+        use super::{ColumnWidths, TableCell, TableLayout, MIN_COLUMN_WIDTH};
+
+        test_area(Size::new(100, 200), |context, area, style| {
+            let text_width = style.str_width(&context.font_cache, "aaa bbb ccc");
+            let natural_width = Paragraph::new("aaa bbb ccc").get_natural_width(style, context);
+            assert!((natural_width.0 - text_width.0).abs() < 0.001);
+
+            let mut table = TableLayout::new(ColumnWidths::AutoFit(2));
+            let cells = vec![
+                TableCell::text("a", None),
+                TableCell::text("aaa bbb ccc", None),
+            ];
+            table.push_row(cells, None).unwrap();
+            let result = table.render(context, area.clone(), style).unwrap();
+            assert!(!result.has_more);
+            let widths = table.effective_column_widths().resolve(area.size().width);
+            assert_eq!(widths[0], MIN_COLUMN_WIDTH);
+            assert!((widths[1].0 - text_width.0).abs() < 0.001);
+
+            let mut table = TableLayout::new(ColumnWidths::AutoFit(2));
+            let cells = vec![TableCell::text(TEXT, None), TableCell::text(TEXT, None)];
+            table.push_row(cells, None).unwrap();
+            table.render(context, area.clone(), style).unwrap();
+            let widths = table.effective_column_widths().resolve(area.size().width);
+            assert!((widths[0].0 - 50.0).abs() < 0.001);
+            assert!((widths[1].0 - 50.0).abs() < 0.001);
+        });
+    }
+}
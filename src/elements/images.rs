@@ -8,7 +8,7 @@ use std::path;
 
 use image::GenericImageView;
 
-use crate::error::{Context as _, Error};
+use crate::error::{Context as _, Error, ErrorKind};
 use crate::{render, style, Margins};
 use crate::{Alignment, Context, Element, Mm, Position, RenderResult, Rotation, Scale, Size};
 
@@ -44,7 +44,11 @@ use crate::{Alignment, Context, Element, Mm, Position, RenderResult, Rotation, S
 /// [`printpdf` issue #98]: https://github.com/fschutt/printpdf/issues/98
 #[derive(Clone)]
 pub struct Image {
-    data: image::DynamicImage,
+    /// The decoded image, or `None` if the image is loaded lazily and has not been read yet.
+    image_data: Option<image::DynamicImage>,
+
+    /// The path to read the image from if it is loaded lazily, see [`Image::from_path_lazy`][].
+    path: Option<path::PathBuf>,
 
     /// Used for positioning if no absolute position is given.
     alignment: Alignment,
@@ -63,26 +67,33 @@ pub struct Image {
     /// DPI override if you know better. Defaults to `printpdf`’s default of 300 dpi.
     dpi: Option<f64>,
     margins: Option<Margins>,
+
+    /// The height of the part of the image that has already been rendered.
+    ///
+    /// Images that are taller than the available area are split across multiple pages.
+    image_offset_y: Mm,
 }
 
 impl Image {
     /// Creates a new image
     pub fn new(data: image::DynamicImage) -> Self {
         Self {
-            data,
+            image_data: Some(data),
+            path: None,
             alignment: Alignment::Left,
             position: None,
             scale: Scale::new(1.0, 1.0),
             rotation: Rotation::from_degrees(0.0),
             dpi: None,
             margins: None,
+            image_offset_y: Mm::default(),
         }
     }
     /// set pixel width, pixel height
     pub fn set_pixel_size(&mut self, width: u32, height: u32) {
-        self.data = self
-            .data
-            .resize(width, height, image::imageops::FilterType::Nearest);
+        if let Some(data) = &mut self.image_data {
+            *data = data.resize(width, height, image::imageops::FilterType::Nearest);
+        }
     }
 
     /// Creates a new image from an already loaded image.
@@ -95,13 +106,15 @@ impl Image {
         //     ))
         // } else {
         Ok(Image {
-            data,
+            image_data: Some(data),
+            path: None,
             alignment: Alignment::default(),
             position: None,
             scale: Scale::default(),
             rotation: Rotation::default(),
             dpi: None,
             margins: None,
+            image_offset_y: Mm::default(),
         })
         // }
     }
@@ -138,6 +151,46 @@ impl Image {
         Self::from_image_reader(reader)
     }
 
+    /// Creates a new image that is read from the given path when it is rendered for the first
+    /// time.
+    ///
+    /// In contrast to [`from_path`][], the file does not have to exist when the image is created.
+    /// If the file cannot be read or decoded, rendering the image fails.  Until the image has been
+    /// loaded, its size is unknown and treated as zero.
+    ///
+    /// [`from_path`]: #method.from_path
+    pub fn from_path_lazy(path: impl Into<path::PathBuf>) -> Self {
+        Image {
+            image_data: None,
+            path: Some(path.into()),
+            alignment: Alignment::default(),
+            position: None,
+            scale: Scale::default(),
+            rotation: Rotation::default(),
+            dpi: None,
+            margins: None,
+            image_offset_y: Mm::default(),
+        }
+    }
+
+    /// Reads the image from its path if it is loaded lazily and has not been read yet.
+    fn load(&mut self) -> Result<(), Error> {
+        if self.image_data.is_none() {
+            if let Some(path) = &self.path {
+                self.image_data = Self::from_path(path)?.image_data;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the size of the image in pixels, or zero if it has not been loaded yet.
+    fn dimensions(&self) -> (u32, u32) {
+        self.image_data
+            .as_ref()
+            .map(GenericImageView::dimensions)
+            .unwrap_or_default()
+    }
+
     /// from bytes
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
         Self::from_reader(std::io::Cursor::new(bytes))
@@ -201,7 +254,7 @@ impl Image {
         let mmpi: f64 = 25.4; // millimeters per inch
                               // Assume 300 DPI to be consistent with printpdf.
         let dpi: f64 = self.dpi.unwrap_or(300.0);
-        let (px_width, px_height) = self.data.dimensions();
+        let (px_width, px_height) = self.dimensions();
         let (scale_width, scale_height): (f64, f64) = (self.scale.x, self.scale.y);
         Size::new(
             mmpi * ((scale_width * px_width as f64) / dpi),
@@ -232,11 +285,90 @@ impl Image {
         self
     }
 
+    /// Returns the unscaled size of this image in millimeters if it is rendered with the given
+    /// DPI, or `None` if the DPI is not a positive number or if the image has not been loaded
+    /// yet.
+    ///
+    /// This can be used to calculate the [`Scale`][] required for a desired size, for example
+    /// `Scale::new(desired.width / natural.width, desired.height / natural.height)`.
+    ///
+    /// [`Scale`]: ../struct.Scale.html
+    pub fn natural_size_mm(&self, dpi: f64) -> Option<Size> {
+        if !dpi.is_finite() || dpi <= 0.0 || self.image_data.is_none() {
+            return None;
+        }
+        let mmpi: f64 = 25.4; // millimeters per inch
+        let (px_width, px_height) = self.dimensions();
+        Some(Size::new(
+            (f64::from(px_width) / dpi) * mmpi,
+            (f64::from(px_height) / dpi) * mmpi,
+        ))
+    }
+
+    /// Returns the aspect ratio (width divided by height) of this image in pixels.
+    ///
+    /// If the image has a height of zero, the aspect ratio is not finite.
+    pub fn aspect_ratio(&self) -> f64 {
+        let (px_width, px_height) = self.dimensions();
+        f64::from(px_width) / f64::from(px_height)
+    }
+
+    /// Renders the next horizontal slice of this image that fits into the given area.
+    ///
+    /// This is used for images that are taller than the available area.  Rotated images and
+    /// images with an absolute position are never split.
+    fn render_slice(
+        &mut self,
+        context: &Context,
+        area: &render::Area<'_>,
+        true_size: Size,
+    ) -> Result<RenderResult, Error> {
+        let mut result = RenderResult::default();
+        let (px_width, px_height) = self.dimensions();
+        let px_per_mm = f64::from(px_height) / true_size.height.0;
+
+        let remaining = true_size.height - self.image_offset_y;
+        let height = if remaining > area.size().height {
+            area.size().height
+        } else {
+            remaining
+        };
+        let px_offset = ((self.image_offset_y.0 * px_per_mm).round() as u32).min(px_height);
+        let px_slice = ((height.0 * px_per_mm).floor() as u32).min(px_height - px_offset);
+        if px_slice == 0 {
+            // Not even a single pixel row fits into the area.  If something has been rendered on
+            // this page already, we try again on the next page.
+            if area.size().height >= context.page_body_height() {
+                return Err(Error::new(
+                    "Could not fit a single row of the image on a page",
+                    ErrorKind::PageSizeExceeded,
+                ));
+            }
+            result.has_more = true;
+            return Ok(result);
+        }
+
+        let slice = match &self.image_data {
+            Some(data) => data.crop_imm(0, px_offset, px_width, px_slice),
+            None => return Ok(result),
+        };
+        let slice_height = Mm::from(f64::from(px_slice) / px_per_mm);
+        // Images are positioned by their lower left corner.
+        let position =
+            self.get_offset(true_size.width, area.size().width) + Position::new(0, slice_height);
+        area.add_image(&slice, position, self.scale, self.rotation, self.dpi);
+
+        self.image_offset_y += slice_height;
+        result.size = Size::new(true_size.width, slice_height);
+        result.has_more = px_offset + px_slice < px_height;
+        Ok(result)
+    }
+
     /// Load image data from given file path
     pub fn with_file_path<P: AsRef<path::Path>>(mut self, path: P) {
         match Self::from_path(path) {
             Ok(image) => {
-                self.data = image.data;
+                self.image_data = image.image_data;
             }
             Err(e) => {
                 eprintln!("Error loading image: {}", e);
@@ -246,19 +378,42 @@ impl Image {
 }
 
 impl Element for Image {
+    fn reset_render(&mut self) {
+        self.image_offset_y = Mm::default();
+    }
+
     fn render(
         &mut self,
-        _context: &Context,
+        context: &Context,
         mut area: render::Area<'_>,
         _style: style::Style,
     ) -> Result<RenderResult, Error> {
         let mut result = RenderResult::default();
+        self.load()?;
 
-        if let Some(margins) = self.margins {
+        // The top margin is only added before the first slice of a split image.
+        let margins = self.margins.map(|mut margins| {
+            if self.image_offset_y > Mm::default() {
+                margins.top = Mm::default();
+            }
+            margins
+        });
+        if let Some(margins) = margins {
             area.add_margins(margins);
         }
 
         let true_size = self.get_size();
+        let can_split = self.position.is_none() && self.rotation.degrees().is_none();
+        if can_split
+            && (self.image_offset_y > Mm::default() || true_size.height > area.size().height)
+        {
+            result = self.render_slice(context, &area, true_size)?;
+            if let Some(margins) = margins {
+                result.size.height += margins.top;
+            }
+            return Ok(result);
+        }
+
         let (bb_origin, bb_size) = bounding_box_offset_and_size(&self.rotation, &true_size);
 
         let mut position: Position = if let Some(position) = self.position {
@@ -281,11 +436,11 @@ impl Element for Image {
         }
 
         // Insert/render the image with the overridden/calculated position.
-        area.add_image(&self.data, position, self.scale, self.rotation, self.dpi);
+        if let Some(data) = &self.image_data {
+            area.add_image(data, position, self.scale, self.rotation, self.dpi);
+        }
 
-        // Always false as we can't safely do this unless we want to try to do "sub-images".
-        // This is technically possible with the `image` package, but it is potentially more
-        // work than necessary. I'd rather support an "Auto-Scale" method to fit to area.
+        // Images that do not fit into the area are split using render_slice, so we are done.
         result.has_more = false;
 
         Ok(result)
@@ -297,7 +452,24 @@ impl Element for Image {
         _context: &Context,
         _area: render::Area<'_>,
     ) -> Mm {
-        self.get_size().height
+        // Errors are reported when the image is rendered.
+        let _ = self.load();
+        self.get_size().height - self.image_offset_y
+    }
+
+    fn get_min_width(&mut self, _style: style::Style, _context: &Context) -> Mm {
+        let _ = self.load();
+        let (_, bb_size) = bounding_box_offset_and_size(&self.rotation, &self.get_size());
+        match self.margins {
+            Some(margins) => bb_size.width + margins.left + margins.right,
+            None => bb_size.width,
+        }
+    }
+
+    fn preferred_size(&self) -> Option<Size> {
+        self.image_data.as_ref()?;
+        let (_, bb_size) = bounding_box_offset_and_size(&self.rotation, &self.get_size());
+        Some(bb_size)
     }
 }
 
@@ -347,7 +519,9 @@ fn bounding_box_offset_and_size(rotation: &Rotation, size: &Size) -> (Position,
 
 #[cfg(test)]
 mod tests {
-    use super::bounding_box_offset_and_size;
+    use super::{bounding_box_offset_and_size, Image};
+    use crate::elements::tests::get_font_family;
+    use crate::{fonts, render, style, Context, Element as _, Margins, Mm};
     use crate::{Position, Rotation, Size};
     use float_cmp::approx_eq;
 
@@ -580,4 +754,72 @@ mod tests {
         test_position(size, 90.0, Position::new(100, 200));
         test_position(size, 180.0, Position::new(200, 0));
     }
+
+    fn tall_image() -> Image {
+        // 2953 pixels at 300 dpi are about 250 mm.
+        Image::from_dynamic_image(image::DynamicImage::new_rgb8(10, 2953)).unwrap()
+    }
+
+    #[test]
+    fn test_split_image() {
+        let context = Context::new(fonts::FontCache::new(get_font_family()));
+        let renderer = render::Renderer::new(Size::new(100, 100), "test").unwrap();
+        let area = renderer.first_page().first_layer().area();
+        let style = style::Style::new();
+
+        let mut image = tall_image();
+        image.set_margins(Margins::trbl(10, 0, 0, 0));
+        let total_height = image.get_probable_height(style, &context, area.clone());
+
+        let result = image.render(&context, area.clone(), style).unwrap();
+        assert!(result.has_more);
+        assert!((result.size.height.0 - 100.0).abs() < 0.1);
+
+        let result = image.render(&context, area.clone(), style).unwrap();
+        assert!(result.has_more);
+        assert!((result.size.height.0 - 100.0).abs() < 0.1);
+
+        let result = image.render(&context, area, style).unwrap();
+        assert!(!result.has_more);
+        assert!((result.size.height.0 - (total_height.0 - 190.0)).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_split_image_reset() {
+        let context = Context::new(fonts::FontCache::new(get_font_family()));
+        let renderer = render::Renderer::new(Size::new(100, 100), "test").unwrap();
+        let area = renderer.first_page().first_layer().area();
+        let style = style::Style::new();
+
+        let mut image = tall_image();
+        let total_height = image.get_probable_height(style, &context, area.clone());
+        let first = image.render(&context, area.clone(), style).unwrap();
+        assert!(image.get_probable_height(style, &context, area.clone()) < total_height);
+
+        image.reset_render();
+        assert_eq!(
+            image.get_probable_height(style, &context, area.clone()),
+            total_height
+        );
+        assert_eq!(image.render(&context, area, style).unwrap(), first);
+    }
+
+    #[test]
+    fn test_split_image_without_space() {
+        let mut context = Context::new(fonts::FontCache::new(get_font_family()));
+        let renderer = render::Renderer::new(Size::new(100, 100), "test").unwrap();
+        let mut area = renderer.first_page().first_layer().area();
+        area.add_margins(Margins::trbl(99.99, 0, 0, 0));
+        let style = style::Style::new();
+
+        // If something has been rendered on this page, the image is moved to the next page.
+        context.page_body_height = Mm(100.0);
+        let result = tall_image().render(&context, area.clone(), style).unwrap();
+        assert!(result.has_more);
+        assert_eq!(result.size, Size::new(0, 0));
+
+        // If the page is empty, the image can never be rendered.
+        context.page_body_height = area.size().height;
+        assert!(tall_image().render(&context, area, style).is_err());
+    }
 }
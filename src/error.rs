@@ -85,6 +85,7 @@ impl error::Error for Error {
             ErrorKind::PdfError(err) => Some(err),
             ErrorKind::PdfIndexError(err) => Some(err),
             ErrorKind::RusttypeError(err) => Some(err),
+            ErrorKind::LopdfError(err) => Some(err),
             #[cfg(feature = "images")]
             ErrorKind::ImageError(err) => Some(err),
         }
@@ -113,6 +114,8 @@ pub enum ErrorKind {
     PdfIndexError(printpdf::IndexError),
     /// An error caused by `rusttype`.
     RusttypeError(rusttype::Error),
+    /// An error caused by `lopdf` when post-processing the generated PDF document.
+    LopdfError(lopdf::Error),
     /// An error caused by `image`.
     ///
     /// *Only available if the `images` feature is enabled.*
@@ -137,6 +140,12 @@ impl From<printpdf::Error> for ErrorKind {
     }
 }
 
+impl From<lopdf::Error> for ErrorKind {
+    fn from(error: lopdf::Error) -> ErrorKind {
+        ErrorKind::LopdfError(error)
+    }
+}
+
 impl From<printpdf::IndexError> for ErrorKind {
     fn from(error: printpdf::IndexError) -> ErrorKind {
         ErrorKind::PdfIndexError(error)
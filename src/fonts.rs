@@ -67,11 +67,12 @@
 use std::fmt;
 use std::fs;
 use std::path;
+use std::sync;
 
 use crate::error::{Context as _, Error, ErrorKind};
 use crate::render;
 use crate::style::Style;
-use crate::utils::log_msg;
+use crate::utils::{trace, LogLevel};
 use crate::Mm;
 
 /// Stores font data that can be referenced by a [`Font`][] or [`FontFamily`][].
@@ -90,6 +91,8 @@ pub struct FontCache {
     // a font, but the default font is always loaded in new, so this options is always some
     // (outside of new).
     default_font_family: Option<FontFamily<Font>>,
+    families: Vec<FontFamilyInfo>,
+    font_scale: f64,
 }
 
 impl FontCache {
@@ -99,6 +102,8 @@ impl FontCache {
             fonts: Vec::new(),
             pdf_fonts: Vec::new(),
             default_font_family: None,
+            families: Vec::new(),
+            font_scale: 1.0,
         };
         font_cache.default_font_family = Some(font_cache.add_font_family(default_font_family));
         font_cache
@@ -110,19 +115,48 @@ impl FontCache {
             RawFontData::Builtin(_) => true,
             RawFontData::Embedded(_) => false,
         };
-        let font = Font::new(self.fonts.len(), is_builtin, &font_data.rt_font);
+        let font = Font::new(self.fonts.len(), is_builtin, &font_data);
         self.fonts.push(font_data);
         font
     }
 
     /// Adds the given font family to the cache and returns a reference to it.
+    ///
+    /// If a variant of the family uses the same font data as the regular variant, the regular
+    /// font is reused for it instead of adding the font again.
     pub fn add_font_family(&mut self, family: FontFamily<FontData>) -> FontFamily<Font> {
-        FontFamily {
-            regular: self.add_font(family.regular),
-            bold: self.add_font(family.bold),
-            italic: self.add_font(family.italic),
-            bold_italic: self.add_font(family.bold_italic),
-        }
+        let name = family.regular.family_name.clone();
+        let has_bold = family.has_bold();
+        let has_italic = family.has_italic();
+        let has_bold_italic = family.has_bold_italic();
+        let regular = self.add_font(family.regular);
+        let mut add_variant = |data: FontData, is_real: bool| {
+            if is_real {
+                self.add_font(data)
+            } else {
+                regular
+            }
+        };
+        let font_family = FontFamily {
+            regular,
+            bold: add_variant(family.bold, has_bold),
+            italic: add_variant(family.italic, has_italic),
+            bold_italic: add_variant(family.bold_italic, has_bold_italic),
+        };
+        self.families.push(FontFamilyInfo {
+            name,
+            family: font_family,
+            has_bold,
+            has_italic,
+            has_bold_italic,
+        });
+        font_family
+    }
+
+    /// Returns information about all font families that have been added to this cache, including
+    /// the default font family.
+    pub fn list_families(&self) -> Vec<FontFamilyInfo> {
+        self.families.clone()
     }
 
     /// Embeds all loaded fonts into the document generated by the given renderer and caches a
@@ -139,12 +173,38 @@ impl FontCache {
         Ok(())
     }
 
+    /// Returns the number of fonts in this cache that are embedded into the PDF document, i. e.
+    /// that are not built-in fonts.
+    pub fn embedded_font_count(&self) -> usize {
+        self.fonts
+            .iter()
+            .filter(|font| matches!(font.raw_data, RawFontData::Embedded(_)))
+            .count()
+    }
+
     /// Returns the default font family for this font cache.
     pub fn default_font_family(&self) -> FontFamily<Font> {
         self.default_font_family
             .expect("Invariant violated: no default font family for FontCache")
     }
 
+    /// Sets the factor that all font sizes are multiplied with when calculating text metrics and
+    /// rendering text with a [`Style`][].
+    ///
+    /// The default value is 1.
+    ///
+    /// [`Style`]: ../style/struct.Style.html
+    pub fn set_font_scale(&mut self, font_scale: f64) {
+        self.font_scale = font_scale;
+    }
+
+    /// Returns the factor that all font sizes are multiplied with, see [`set_font_scale`][].
+    ///
+    /// [`set_font_scale`]: #method.set_font_scale
+    pub fn font_scale(&self) -> f64 {
+        self.font_scale
+    }
+
     /// Returns a reference to the emebdded PDF font for the given font, if available.
     ///
     /// This method may only be called with [`Font`][] instances that have been created by this
@@ -174,6 +234,17 @@ impl FontCache {
 pub struct FontData {
     rt_font: rusttype::Font<'static>,
     raw_data: RawFontData,
+    metrics_override: Option<MetricsOverride>,
+    family_name: Option<String>,
+}
+
+/// Vertical font metrics that replace the metrics read from the font file, as factors of the
+/// font size.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct MetricsOverride {
+    ascent: f64,
+    descent: f64,
+    line_gap: f64,
 }
 
 impl FontData {
@@ -189,7 +260,7 @@ impl FontData {
         let raw_data = if let Some(builtin) = builtin {
             RawFontData::Builtin(builtin)
         } else {
-            RawFontData::Embedded(data.clone())
+            RawFontData::Embedded(sync::Arc::new(data.clone()))
         };
         let rt_font = rusttype::Font::from_bytes(data).context("Failed to read rusttype font")?;
         if rt_font.units_per_em() == 0 {
@@ -198,7 +269,12 @@ impl FontData {
                 ErrorKind::InvalidFont,
             ))
         } else {
-            Ok(FontData { rt_font, raw_data })
+            Ok(FontData {
+                rt_font,
+                raw_data,
+                metrics_override: None,
+                family_name: None,
+            })
         }
     }
 
@@ -218,34 +294,91 @@ impl FontData {
             .with_context(|| format!("Failed to open font file {}", path.as_ref().display()))?;
         FontData::new(data, builtin)
     }
+
+    /// Sets the name of the font family this font belongs to and returns the font data.
+    ///
+    /// The name is set automatically if the font is loaded with [`from_files`][].
+    ///
+    /// [`from_files`]: fn.from_files.html
+    pub fn with_family_name(mut self, name: impl Into<String>) -> Self {
+        self.family_name = Some(name.into());
+        self
+    }
+
+    /// Returns the name of the font family this font belongs to, if known.
+    pub fn family_name(&self) -> Option<&str> {
+        self.family_name.as_deref()
+    }
+
+    /// Returns `true` if this font uses the same font data and metrics as the given font.
+    ///
+    /// Embedded fonts only use the same font data if one of them is a clone of the other one, so
+    /// this check does not compare the font files.  This can be used to check whether a variant of
+    /// a font family is not a real variant, but only a copy of the regular font.
+    pub fn is_same_font(&self, other: &FontData) -> bool {
+        let is_same_data = match (&self.raw_data, &other.raw_data) {
+            (RawFontData::Builtin(a), RawFontData::Builtin(b)) => a == b,
+            (RawFontData::Embedded(a), RawFontData::Embedded(b)) => sync::Arc::ptr_eq(a, b),
+            _ => false,
+        };
+        is_same_data && self.metrics_override == other.metrics_override
+    }
+
+    /// Overrides the vertical metrics of this font and returns the font data.
+    ///
+    /// Some fonts have incorrect ascent or descent values, leading to clipped text or wrong line
+    /// heights.  If the metrics are overridden, the given values are used instead of the values
+    /// from the font file.  All values are factors of the font size:  For example, an ascent
+    /// factor of 0.8 means that the ascent of a 10 pt font is 8 pt.  The descent is the distance
+    /// below the baseline and should be positive.
+    pub fn with_metrics_override(
+        mut self,
+        ascent_factor: f64,
+        descent_factor: f64,
+        line_gap_factor: f64,
+    ) -> Self {
+        self.metrics_override = Some(MetricsOverride {
+            ascent: ascent_factor,
+            descent: descent_factor,
+            line_gap: line_gap_factor,
+        });
+        self
+    }
 }
 
 #[derive(Clone, Debug)]
 enum RawFontData {
     Builtin(printpdf::BuiltinFont),
-    Embedded(Vec<u8>),
+    Embedded(sync::Arc<Vec<u8>>),
 }
 
-#[derive(Clone, Copy, Debug)]
-enum FontStyle {
+/// A variant of a [`FontFamily`][].
+///
+/// [`FontFamily`]: struct.FontFamily.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FontVariant {
+    /// The regular variant.
     Regular,
+    /// The bold variant.
     Bold,
+    /// The italic variant.
     Italic,
+    /// The bold italic variant.
     BoldItalic,
 }
 
-impl FontStyle {
+impl FontVariant {
     fn name(&self) -> &'static str {
         match self {
-            FontStyle::Regular => "Regular",
-            FontStyle::Bold => "Bold",
-            FontStyle::Italic => "Italic",
-            FontStyle::BoldItalic => "BoldItalic",
+            FontVariant::Regular => "Regular",
+            FontVariant::Bold => "Bold",
+            FontVariant::Italic => "Italic",
+            FontVariant::BoldItalic => "BoldItalic",
         }
     }
 }
 
-impl fmt::Display for FontStyle {
+impl fmt::Display for FontVariant {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_str(self.name())
     }
@@ -268,25 +401,25 @@ pub enum Builtin {
 }
 
 impl Builtin {
-    fn style(&self, style: FontStyle) -> printpdf::BuiltinFont {
+    fn style(&self, style: FontVariant) -> printpdf::BuiltinFont {
         match self {
             Builtin::Times => match style {
-                FontStyle::Regular => printpdf::BuiltinFont::TimesRoman,
-                FontStyle::Bold => printpdf::BuiltinFont::TimesBold,
-                FontStyle::Italic => printpdf::BuiltinFont::TimesItalic,
-                FontStyle::BoldItalic => printpdf::BuiltinFont::TimesBoldItalic,
+                FontVariant::Regular => printpdf::BuiltinFont::TimesRoman,
+                FontVariant::Bold => printpdf::BuiltinFont::TimesBold,
+                FontVariant::Italic => printpdf::BuiltinFont::TimesItalic,
+                FontVariant::BoldItalic => printpdf::BuiltinFont::TimesBoldItalic,
             },
             Builtin::Helvetica => match style {
-                FontStyle::Regular => printpdf::BuiltinFont::Helvetica,
-                FontStyle::Bold => printpdf::BuiltinFont::HelveticaBold,
-                FontStyle::Italic => printpdf::BuiltinFont::HelveticaOblique,
-                FontStyle::BoldItalic => printpdf::BuiltinFont::HelveticaBoldOblique,
+                FontVariant::Regular => printpdf::BuiltinFont::Helvetica,
+                FontVariant::Bold => printpdf::BuiltinFont::HelveticaBold,
+                FontVariant::Italic => printpdf::BuiltinFont::HelveticaOblique,
+                FontVariant::BoldItalic => printpdf::BuiltinFont::HelveticaBoldOblique,
             },
             Builtin::Courier => match style {
-                FontStyle::Regular => printpdf::BuiltinFont::Courier,
-                FontStyle::Bold => printpdf::BuiltinFont::CourierBold,
-                FontStyle::Italic => printpdf::BuiltinFont::CourierOblique,
-                FontStyle::BoldItalic => printpdf::BuiltinFont::CourierBoldOblique,
+                FontVariant::Regular => printpdf::BuiltinFont::Courier,
+                FontVariant::Bold => printpdf::BuiltinFont::CourierBold,
+                FontVariant::Italic => printpdf::BuiltinFont::CourierOblique,
+                FontVariant::BoldItalic => printpdf::BuiltinFont::CourierBoldOblique,
             },
         }
     }
@@ -322,6 +455,108 @@ impl<T: Clone + Copy + fmt::Debug + PartialEq> FontFamily<T> {
     }
 }
 
+impl FontFamily<Font> {
+    /// Returns `true` if this family has a bold font that is different from the regular font.
+    pub fn has_bold(&self) -> bool {
+        self.bold != self.regular
+    }
+
+    /// Returns `true` if this family has an italic font that is different from the regular font.
+    pub fn has_italic(&self) -> bool {
+        self.italic != self.regular
+    }
+
+    /// Returns `true` if this family has a bold italic font that is different from the regular
+    /// font.
+    pub fn has_bold_italic(&self) -> bool {
+        self.bold_italic != self.regular
+    }
+}
+
+impl FontFamily<FontData> {
+    /// Returns `true` if the given variant of this family is not a real variant, but a copy of the
+    /// regular font, see [`FontData::is_same_font`][].
+    ///
+    /// Text with such a variant is rendered with the regular font.  The regular variant is never
+    /// synthesized.
+    ///
+    /// [`FontData::is_same_font`]: struct.FontData.html#method.is_same_font
+    pub fn is_synthesized_from_regular(&self, variant: FontVariant) -> bool {
+        match variant {
+            FontVariant::Regular => false,
+            FontVariant::Bold => self.bold.is_same_font(&self.regular),
+            FontVariant::Italic => self.italic.is_same_font(&self.regular),
+            FontVariant::BoldItalic => self.bold_italic.is_same_font(&self.regular),
+        }
+    }
+
+    /// Returns `true` if this family has a bold font that is different from the regular font.
+    pub fn has_bold(&self) -> bool {
+        !self.is_synthesized_from_regular(FontVariant::Bold)
+    }
+
+    /// Returns `true` if this family has an italic font that is different from the regular font.
+    pub fn has_italic(&self) -> bool {
+        !self.is_synthesized_from_regular(FontVariant::Italic)
+    }
+
+    /// Returns `true` if this family has a bold italic font that is different from the regular
+    /// font.
+    pub fn has_bold_italic(&self) -> bool {
+        !self.is_synthesized_from_regular(FontVariant::BoldItalic)
+    }
+
+    /// Returns the name of this font family, or an empty string if the name is not known.
+    ///
+    /// See [`FontData::with_family_name`][].
+    ///
+    /// [`FontData::with_family_name`]: struct.FontData.html#method.with_family_name
+    pub fn family_name(&self) -> &str {
+        self.regular.family_name().unwrap_or_default()
+    }
+
+    /// Overrides the vertical metrics of all fonts in this family and returns the family.
+    ///
+    /// See [`FontData::with_metrics_override`][] for more information.
+    ///
+    /// [`FontData::with_metrics_override`]: struct.FontData.html#method.with_metrics_override
+    pub fn with_metrics_override(
+        self,
+        ascent_factor: f64,
+        descent_factor: f64,
+        line_gap_factor: f64,
+    ) -> Self {
+        let apply = |data: FontData| {
+            data.with_metrics_override(ascent_factor, descent_factor, line_gap_factor)
+        };
+        FontFamily {
+            regular: apply(self.regular),
+            bold: apply(self.bold),
+            italic: apply(self.italic),
+            bold_italic: apply(self.bold_italic),
+        }
+    }
+}
+
+/// Information about a font family in a [`FontCache`][], see [`FontCache::list_families`][].
+///
+/// [`FontCache`]: struct.FontCache.html
+/// [`FontCache::list_families`]: struct.FontCache.html#method.list_families
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct FontFamilyInfo {
+    /// The name of the font family, if known.
+    pub name: Option<String>,
+    /// The cached font family.
+    pub family: FontFamily<Font>,
+    /// Whether the family has a bold font that is different from the regular font.
+    pub has_bold: bool,
+    /// Whether the family has an italic font that is different from the regular font.
+    pub has_italic: bool,
+    /// Whether the family has a bold italic font that is different from the regular font.
+    pub has_bold_italic: bool,
+}
+
 /// A reference to a font cached by a [`FontCache`][].
 ///
 /// See the [module documentation](index.html) for details on the internals.
@@ -338,25 +573,38 @@ pub struct Font {
 }
 
 impl Font {
-    fn new(idx: usize, is_builtin: bool, rt_font: &rusttype::Font<'static>) -> Font {
+    fn new(idx: usize, is_builtin: bool, font_data: &FontData) -> Font {
+        let rt_font = &font_data.rt_font;
         let units_per_em = rt_font.units_per_em();
         assert!(units_per_em != 0);
 
         let units_per_em = f32::from(units_per_em);
         let v_metrics = rt_font.v_metrics_unscaled();
         let glyph_height = (v_metrics.ascent - v_metrics.descent) / units_per_em;
+        // The scale is used for the horizontal metrics, so it must not be affected by the
+        // metrics override.
         let scale = rusttype::Scale::uniform(glyph_height);
 
-        let ascent = v_metrics.ascent / units_per_em;
-        let line_height = glyph_height + v_metrics.line_gap / units_per_em;
+        let (ascent, glyph_height, line_height) = match font_data.metrics_override {
+            Some(metrics) => (
+                metrics.ascent,
+                metrics.ascent + metrics.descent,
+                metrics.ascent + metrics.descent + metrics.line_gap,
+            ),
+            None => (
+                f64::from(v_metrics.ascent / units_per_em),
+                f64::from(glyph_height),
+                f64::from(glyph_height + v_metrics.line_gap / units_per_em),
+            ),
+        };
 
         Font {
             idx,
             is_builtin,
             scale,
-            line_height: printpdf::Pt(f64::from(line_height)).into(),
-            glyph_height: printpdf::Pt(f64::from(glyph_height)).into(),
-            ascent: printpdf::Pt(f64::from(ascent)).into(),
+            line_height: printpdf::Pt(line_height).into(),
+            glyph_height: printpdf::Pt(glyph_height).into(),
+            ascent: printpdf::Pt(ascent).into(),
         }
     }
 
@@ -488,30 +736,36 @@ impl Font {
 fn from_file(
     dir: impl AsRef<path::Path>,
     name: &str,
-    style: FontStyle,
+    style: FontVariant,
     builtin: Option<Builtin>,
 ) -> Result<FontData, Error> {
     let builtin = builtin.map(|b| b.style(style));
     let path = format!("{}-{}.ttf", name, style);
-    log_msg(&format!(
-        "Accessing font {:?} from directory {:?}",
-        path,
-        dir.as_ref()
-    ));
+    trace(
+        LogLevel::Info,
+        &format!(
+            "Accessing font {:?} from directory {:?}",
+            path,
+            dir.as_ref()
+        ),
+    );
     FontData::load(&dir.as_ref().join(path), builtin)
 }
 
 fn from_file_name(
     dir: impl AsRef<path::Path>,
     name: &str,
-    style: FontStyle,
+    style: FontVariant,
     builtin: Option<Builtin>,
 ) -> Result<FontData, Error> {
-    log_msg(&format!(
-        "Accessing font: {:?} from directory {:?} ",
-        name,
-        dir.as_ref()
-    ));
+    trace(
+        LogLevel::Info,
+        &format!(
+            "Accessing font: {:?} from directory {:?} ",
+            name,
+            dir.as_ref()
+        ),
+    );
     let builtin = builtin.map(|b| b.style(style));
     FontData::load(&dir.as_ref().join(format!("{}.ttf", name)), builtin)
 }
@@ -534,10 +788,10 @@ pub fn from_files(
 ) -> Result<FontFamily<FontData>, Error> {
     let dir = dir.as_ref();
     Ok(FontFamily {
-        regular: from_file(dir, name, FontStyle::Regular, builtin)?,
-        bold: from_file(dir, name, FontStyle::Bold, builtin)?,
-        italic: from_file(dir, name, FontStyle::Italic, builtin)?,
-        bold_italic: from_file(dir, name, FontStyle::BoldItalic, builtin)?,
+        regular: from_file(dir, name, FontVariant::Regular, builtin)?.with_family_name(name),
+        bold: from_file(dir, name, FontVariant::Bold, builtin)?.with_family_name(name),
+        italic: from_file(dir, name, FontVariant::Italic, builtin)?.with_family_name(name),
+        bold_italic: from_file(dir, name, FontVariant::BoldItalic, builtin)?.with_family_name(name),
     })
 }
 
@@ -565,10 +819,10 @@ pub fn from_file_names(
     }
     let dir = dir.as_ref();
     Ok(FontFamily {
-        regular: from_file_name(dir, names.remove(0), FontStyle::Regular, builtin)?,
-        bold: from_file_name(dir, names.remove(0), FontStyle::Bold, builtin)?,
-        italic: from_file_name(dir, names.remove(0), FontStyle::Italic, builtin)?,
-        bold_italic: from_file_name(dir, names.remove(0), FontStyle::BoldItalic, builtin)?,
+        regular: from_file_name(dir, names.remove(0), FontVariant::Regular, builtin)?,
+        bold: from_file_name(dir, names.remove(0), FontVariant::Bold, builtin)?,
+        italic: from_file_name(dir, names.remove(0), FontVariant::Italic, builtin)?,
+        bold_italic: from_file_name(dir, names.remove(0), FontVariant::BoldItalic, builtin)?,
     })
 }
 
@@ -163,9 +163,17 @@ pub mod style;
 /// utils mod
 pub mod utils;
 
+use std::cell;
+use std::collections;
+use std::fmt;
 use std::fs;
 use std::io;
 use std::path;
+use std::rc;
+use std::time;
+
+pub use utils::{LogLevel, Tracer};
+pub use wrap::Hyphenator;
 
 use derive_more::{
     Add, AddAssign, Div, DivAssign, From, Into, Mul, MulAssign, Sub, SubAssign, Sum,
@@ -209,12 +217,71 @@ use style::Style;
 pub struct Mm(f64);
 
 impl Mm {
+    /// A length of zero millimeters.
+    pub const ZERO: Mm = Mm(0.0);
+
+    /// The largest representable length.
+    pub const MAX: Mm = Mm(f64::MAX);
+
+    /// Creates a new length from the given value in points (1/72 inch).
+    pub fn from_pt(pt: f64) -> Mm {
+        printpdf::Pt(pt).into()
+    }
+
+    /// Creates a new length from the given value in inches.
+    pub fn from_inches(inches: f64) -> Mm {
+        Mm(inches * 25.4)
+    }
+
+    /// Creates a new length from the given value in centimeters.
+    pub fn from_cm(cm: f64) -> Mm {
+        Mm(cm * 10.0)
+    }
+
+    /// Returns this length in points (1/72 inch).
+    pub fn to_pt(&self) -> f64 {
+        printpdf::Pt::from(*self).0
+    }
+
+    /// Returns this length in inches.
+    pub fn to_inches(&self) -> f64 {
+        self.0 / 25.4
+    }
+
+    /// Returns true if the difference between this value and the given value is not larger than
+    /// `epsilon`.
+    pub fn approx_eq(&self, other: Mm, epsilon: Mm) -> bool {
+        (self.0 - other.0).abs() <= epsilon.0
+    }
+
+    /// Restricts this value to the interval `[min, max]`.
+    pub fn clamp(&self, min: Mm, max: Mm) -> Mm {
+        if *self < min {
+            min
+        } else if *self > max {
+            max
+        } else {
+            *self
+        }
+    }
+
+    /// Returns the minimum of this value and the given value.
+    pub fn min(self, other: Mm) -> Mm {
+        Mm(self.0.min(other.0))
+    }
+
     /// Returns the maximum of this value and the given value.
     pub fn max(self, other: Mm) -> Mm {
         Mm(self.0.max(other.0))
     }
 }
 
+impl fmt::Display for Mm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.2} mm", self.0)
+    }
+}
+
 impl From<i8> for Mm {
     fn from(mm: i8) -> Mm {
         Mm(mm.into())
@@ -304,6 +371,53 @@ impl Default for Alignment {
     }
 }
 
+/// The vertical alignment of a [`TableCell`][] within its row.
+///
+/// The default alignment is top-aligned.
+///
+/// [`TableCell`]: elements/struct.TableCell.html
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum VerticalAlignment {
+    /// Top-aligned.
+    Top,
+    /// Centered.
+    Middle,
+    /// Bottom-aligned.
+    Bottom,
+}
+
+impl Default for VerticalAlignment {
+    fn default() -> VerticalAlignment {
+        VerticalAlignment::Top
+    }
+}
+
+/// The direction of the text in a [`Paragraph`][].
+///
+/// The default direction is left-to-right.
+///
+/// Right-to-left support is a simplification that is sufficient for simple texts:  The strings
+/// of each line are printed in reverse order with their characters reversed, and the
+/// [`Alignment`][] is mirrored so that left-flushed text starts at the right border.  There is no
+/// support for the Unicode bidirectional algorithm, mixed left-to-right and right-to-left text or
+/// contextual glyph shaping.
+///
+/// [`Paragraph`]: elements/struct.Paragraph.html
+/// [`Alignment`]: enum.Alignment.html
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum TextDirection {
+    /// Left-to-right text.
+    Ltr,
+    /// Right-to-left text.
+    Rtl,
+}
+
+impl Default for TextDirection {
+    fn default() -> TextDirection {
+        TextDirection::Ltr
+    }
+}
+
 /// A position on a PDF layer, measured in millimeters.
 ///
 /// All positions used by `genpdf` are measured from the top left corner of the reference area.
@@ -510,6 +624,18 @@ impl Margins {
         let all = all.into();
         Margins::trbl(all, all, all, all)
     }
+
+    /// Creates a new `Margins` instance with the left and right margins set to the given value
+    /// and the top and bottom margins set to zero.
+    pub fn horizontal(horizontal: impl Into<Mm>) -> Margins {
+        Margins::vh(0, horizontal)
+    }
+
+    /// Creates a new `Margins` instance with the top and bottom margins set to the given value
+    /// and the left and right margins set to zero.
+    pub fn vertical(vertical: impl Into<Mm>) -> Margins {
+        Margins::vh(vertical, 0)
+    }
 }
 
 impl<T: Into<Mm>, R: Into<Mm>, B: Into<Mm>, L: Into<Mm>> From<(T, R, B, L)> for Margins {
@@ -530,6 +656,90 @@ impl<T: Into<Mm>> From<T> for Margins {
     }
 }
 
+/// The borders of an area or element.
+///
+/// Every edge has an optional [`LineStyle`][].  If it is `None`, the border is not drawn.  The
+/// default value does not draw any borders.
+///
+/// Borders are used by the [`CustomPageDecorator`][], the [`FramedElement`][] and the
+/// [`FrameCellDecorator`][].
+///
+/// [`LineStyle`]: style/struct.LineStyle.html
+/// [`CustomPageDecorator`]: struct.CustomPageDecorator.html
+/// [`FramedElement`]: elements/struct.FramedElement.html
+/// [`FrameCellDecorator`]: elements/struct.FrameCellDecorator.html
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Borders {
+    /// The top border.
+    pub top: Option<LineStyle>,
+    /// The right border.
+    pub right: Option<LineStyle>,
+    /// The bottom border.
+    pub bottom: Option<LineStyle>,
+    /// The left border.
+    pub left: Option<LineStyle>,
+}
+
+impl Borders {
+    /// Creates a new `Borders` instance without any borders.
+    pub fn none() -> Borders {
+        Borders::default()
+    }
+
+    /// Creates a new `Borders` instance with all four borders set to the given line style.
+    pub fn all(line_style: impl Into<LineStyle>) -> Borders {
+        let line_style = line_style.into();
+        Borders {
+            top: Some(line_style),
+            right: Some(line_style),
+            bottom: Some(line_style),
+            left: Some(line_style),
+        }
+    }
+
+    /// Creates a new `Borders` instance for the outer frame of a table with the given line style.
+    ///
+    /// This is meant to be used together with [`FrameCellDecorator::with_borders`][], where it
+    /// sets the borders around the table.
+    ///
+    /// [`FrameCellDecorator::with_borders`]: elements/struct.FrameCellDecorator.html#method.with_borders
+    pub fn outer(line_style: impl Into<LineStyle>) -> Borders {
+        Borders::all(line_style)
+    }
+
+    /// Creates a new `Borders` instance with all four line styles set to the given value.
+    #[deprecated(since = "0.3.0", note = "use `Borders::all` instead")]
+    pub fn all_styled(all: impl Into<LineStyle>) -> Borders {
+        Borders::all(all)
+    }
+
+    /// Returns the line style of the borders if all four borders are drawn with the same style.
+    pub(crate) fn uniform_line_style(&self) -> Option<LineStyle> {
+        let top = self.top?;
+        if [self.right, self.bottom, self.left]
+            .iter()
+            .all(|&line| line == Some(top))
+        {
+            Some(top)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the thickness of the top, right, bottom and left border as margins.
+    ///
+    /// Missing borders have a thickness of zero.
+    pub fn thickness(&self) -> Margins {
+        let thickness = |line: Option<LineStyle>| line.map(|l| l.thickness()).unwrap_or_default();
+        Margins::trbl(
+            thickness(self.top),
+            thickness(self.right),
+            thickness(self.bottom),
+            thickness(self.left),
+        )
+    }
+}
+
 /// A PDF document.
 ///
 /// This struct is the entry point for the high-level `genpdf` API.  It stores a set of elements
@@ -546,8 +756,10 @@ impl<T: Into<Mm>> From<T> for Margins {
 /// elements to the page before it is filled with the actual document content.  See the
 /// [`SimplePageDecorator`][] for a basic implementation.
 ///
-/// If the `hyphenation` feature is enabled, users can activate hyphenation with the
-/// [`set_hyphenator`][] method.
+/// Users can activate hyphenation with the [`set_hyphenator`][] method.  If the `hyphenation`
+/// feature is enabled, the hyphenators provided by the [`hyphenation`][] crate can be used.
+/// With the `hyphenation-en-us` or `hyphenation-embed-all` feature, the embedded dictionaries
+/// can be loaded directly, for example with [`enable_english_hyphenation`][].
 ///
 /// # Example
 ///
@@ -564,7 +776,9 @@ impl<T: Into<Mm>> From<T> for Margins {
 /// [`push`]: #method.push
 /// [`render`]: #method.render
 /// [`render_to_file`]: #method.render_to_file
-/// [`set_hyphenation`]: #method.set_hyphenation
+/// [`set_hyphenator`]: #method.set_hyphenator
+/// [`enable_english_hyphenation`]: #method.enable_english_hyphenation
+/// [`hyphenation`]: https://docs.rs/hyphenation
 /// [`set_page_decorator`]: #method.set_page_decorator
 /// [`PageDecorator`]: trait.PageDecorator.html
 /// [`SimplePageDecorator`]: struct.SimplePageDecorator.html
@@ -577,12 +791,19 @@ pub struct Document {
     paper_size: Size,
     decorator: Option<Box<dyn PageDecorator>>,
     conformance: Option<printpdf::PdfConformance>,
+    pdf_version: Option<render::PdfVersion>,
+    compression: Option<render::CompressionLevel>,
     creation_date: Option<printpdf::OffsetDateTime>,
     modification_date: Option<printpdf::OffsetDateTime>,
     margins: Option<Margins>,
+    first_page_margins: Option<Margins>,
     borders: Option<Borders>,
     has_header: Option<bool>,
     has_footer: Option<bool>,
+    page_break_callbacks: Vec<PageBreakCallback>,
+    element_positions: ElementPositions,
+    list_counter_resets: Vec<(usize, String)>,
+    min_page_remaining: Mm,
 }
 
 impl Document {
@@ -597,12 +818,19 @@ impl Document {
             paper_size: PaperSize::A4.into(),
             decorator: None,
             conformance: None,
+            pdf_version: None,
+            compression: None,
             creation_date: None,
             modification_date: None,
             margins: None,
+            first_page_margins: None,
             has_header: None,
             has_footer: None,
             borders: None,
+            page_break_callbacks: Vec::new(),
+            element_positions: ElementPositions::default(),
+            list_counter_resets: Vec::new(),
+            min_page_remaining: Mm(0.0),
         }
     }
 
@@ -632,10 +860,79 @@ impl Document {
 
     /// Activates hyphenation and sets the hyphentor to use.
     ///
-    /// *Only available if the `hyphenation` feature is enabled.*
-    #[cfg(feature = "hyphenation")]
-    pub fn set_hyphenator(&mut self, hyphenator: hyphenation::Standard) {
-        self.context.hyphenator = Some(hyphenator);
+    /// If the `hyphenation` feature is enabled, [`Hyphenator`][] is implemented for
+    /// `hyphenation::Standard`.
+    ///
+    /// [`Hyphenator`]: trait.Hyphenator.html
+    pub fn set_hyphenator(&mut self, hyphenator: impl Hyphenator + 'static) {
+        self.context.hyphenator = Some(Box::new(hyphenator));
+    }
+
+    /// Sets the tracer that receives the log messages that are generated while rendering this
+    /// document.
+    ///
+    /// Per default, the messages are printed to stdout, see [`StdoutTracer`][].  Use
+    /// [`NoopTracer`][] to discard them.  Messages that are not tied to a document, for example
+    /// when loading fonts, are passed to the tracer set with [`utils::set_tracer`][].
+    ///
+    /// [`StdoutTracer`]: utils/struct.StdoutTracer.html
+    /// [`NoopTracer`]: utils/struct.NoopTracer.html
+    /// [`utils::set_tracer`]: utils/fn.set_tracer.html
+    pub fn set_tracer(&mut self, tracer: impl Tracer + 'static) {
+        self.context.tracer = Box::new(tracer);
+    }
+
+    /// Activates hyphenation using the embedded US English dictionary of the [`hyphenation`][]
+    /// crate.
+    ///
+    /// *Only available if the `hyphenation-en-us` or the `hyphenation-embed-all` feature is
+    /// enabled.*
+    ///
+    /// [`hyphenation`]: https://docs.rs/hyphenation
+    #[cfg(any(feature = "hyphenation-en-us", feature = "hyphenation-embed-all"))]
+    pub fn enable_english_hyphenation(&mut self) -> Result<(), error::Error> {
+        self.enable_embedded_hyphenation(hyphenation::Language::EnglishUS)
+    }
+
+    /// Activates hyphenation using the embedded French dictionary of the [`hyphenation`][] crate.
+    ///
+    /// *Only available if the `hyphenation-embed-all` feature is enabled.*
+    ///
+    /// [`hyphenation`]: https://docs.rs/hyphenation
+    #[cfg(feature = "hyphenation-embed-all")]
+    pub fn enable_french_hyphenation(&mut self) -> Result<(), error::Error> {
+        self.enable_embedded_hyphenation(hyphenation::Language::French)
+    }
+
+    /// Activates hyphenation using the embedded German dictionary (1996 orthography) of the
+    /// [`hyphenation`][] crate.
+    ///
+    /// *Only available if the `hyphenation-embed-all` feature is enabled.*
+    ///
+    /// [`hyphenation`]: https://docs.rs/hyphenation
+    #[cfg(feature = "hyphenation-embed-all")]
+    pub fn enable_german_hyphenation(&mut self) -> Result<(), error::Error> {
+        self.enable_embedded_hyphenation(hyphenation::Language::German1996)
+    }
+
+    #[cfg(any(feature = "hyphenation-en-us", feature = "hyphenation-embed-all"))]
+    fn enable_embedded_hyphenation(
+        &mut self,
+        language: hyphenation::Language,
+    ) -> Result<(), error::Error> {
+        use hyphenation::Load as _;
+
+        let dict = hyphenation::Standard::from_embedded(language).map_err(|err| {
+            error::Error::new(
+                format!(
+                    "Failed to load the embedded {} dictionary: {}",
+                    language, err
+                ),
+                error::ErrorKind::Internal,
+            )
+        })?;
+        self.set_hyphenator(dict);
+        Ok(())
     }
 
     /// Sets the title of the PDF document.
@@ -652,6 +949,16 @@ impl Document {
         self.style.set_font_size(font_size);
     }
 
+    /// Sets the factor that all font sizes in this document are multiplied with.
+    ///
+    /// The factor is applied to all text, regardless of the font size set in the element styles.
+    /// This can be used to scale the text of a whole document, for example for accessibility.
+    ///
+    /// If this method is not called, the default value of 1 is used.
+    pub fn set_font_scale(&mut self, factor: f64) {
+        self.context.font_cache.set_font_scale(factor);
+    }
+
     /// Sets the default line spacing factor for this document.
     ///
     /// If this method is not called, the default value of 1 is used.
@@ -659,6 +966,55 @@ impl Document {
         self.style.set_line_spacing(line_spacing);
     }
 
+    /// Sets the minimum height that has to be left on a page before a new top-level element is
+    /// started.
+    ///
+    /// If less space is left on the current page, a page break is inserted before the next
+    /// element.  This prevents orphaned elements at the bottom of a page.  Elements with a
+    /// probable height of zero are not moved, and the check is skipped for the first element on
+    /// a page.
+    ///
+    /// If this method is not called, the default value of 0 is used.
+    pub fn set_min_page_remaining(&mut self, threshold: impl Into<Mm>) {
+        self.min_page_remaining = threshold.into();
+    }
+
+    /// Sets the number of the first page of this document.
+    ///
+    /// The page numbers passed to the page decorators and used for the `#{page}` placeholder start
+    /// with this number.  This is useful if the document is part of a larger document, for
+    /// example a single chapter.
+    ///
+    /// If this method is not called, the default value of 1 is used.
+    pub fn set_first_page_number(&mut self, first_page_number: usize) {
+        self.context.page_number_offset = first_page_number.saturating_sub(1);
+    }
+
+    /// Sets the total number of pages of this document.
+    ///
+    /// All `#{total_pages}` placeholders in paragraphs are replaced with the given value when the
+    /// document is rendered.  As the number of pages is only known after rendering, callers
+    /// typically render the document once to count the pages, for example using
+    /// [`render_with_stats`][] with [`std::io::sink`][], and then render a second copy of the
+    /// document with the total number of pages set.  If this method is not called, the
+    /// placeholders are replaced with an empty string.
+    ///
+    /// [`render_with_stats`]: #method.render_with_stats
+    /// [`std::io::sink`]: https://doc.rust-lang.org/std/io/fn.sink.html
+    pub fn set_total_pages(&mut self, total_pages: usize) {
+        self.context.total_pages = Some(total_pages);
+    }
+
+    /// Sets the value of a user-defined variable.
+    ///
+    /// All `#{key}` placeholders in paragraphs are replaced with the given value when the
+    /// document is rendered.  Placeholders for variables that are not set are replaced with an
+    /// empty string.  This is useful for template documents, for example for a footer with the
+    /// text `#{client_name} – Confidential`.
+    pub fn set_user_data(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.context.user_data.insert(key.into(), value.into());
+    }
+
     /// Sets the paper size for all pages of this document.
     ///
     /// If this method is not called, the default size [`A4`][] is used.
@@ -685,6 +1041,24 @@ impl Document {
         self.margins = Some(margins);
     }
 
+    /// Sets the margins for the first page of this document.
+    ///
+    /// The margins are passed to the page decorator (see
+    /// [`PageDecorator::set_first_page_margins`][]) and replace its regular margins on the first
+    /// page only.  If no page decorator is set, the
+    /// margins are applied directly to the first page.  This is useful for cover pages with
+    /// full-bleed content.
+    ///
+    /// [`PageDecorator::set_first_page_margins`]: trait.PageDecorator.html#method.set_first_page_margins
+    pub fn set_first_page_margins(&mut self, margins: Margins) {
+        self.first_page_margins = Some(margins);
+    }
+
+    /// Returns the margins for the first page of this document, if set.
+    pub fn get_first_page_margins(&self) -> Option<Margins> {
+        self.first_page_margins
+    }
+
     /// set borders
     pub fn set_borders(&mut self, borders: Borders) {
         self.borders = Some(borders);
@@ -730,6 +1104,18 @@ impl Document {
         self.borders
     }
 
+    /// Registers a callback that is called every time a page break occurs while rendering.
+    ///
+    /// A page break occurs if the content of the document does not fit on the current page and a
+    /// new page is added.  The callback receives a [`PageBreakEvent`][] with the page numbers and
+    /// the index of the top-level element that caused the page break.  Multiple callbacks can be
+    /// registered and are called in the order of registration.
+    ///
+    /// [`PageBreakEvent`]: struct.PageBreakEvent.html
+    pub fn on_page_break(&mut self, cb: impl Fn(PageBreakEvent) + 'static) {
+        self.page_break_callbacks.push(Box::new(cb));
+    }
+
     /// Sets the PDF conformance settings for this document.
     pub fn set_conformance(&mut self, conformance: printpdf::PdfConformance) {
         self.conformance = Some(conformance);
@@ -749,6 +1135,24 @@ impl Document {
         ));
     }
 
+    /// Sets the PDF version that is written to the header of the PDF file.
+    ///
+    /// See [`Renderer::with_pdf_version`][] for more information.
+    ///
+    /// [`Renderer::with_pdf_version`]: render/struct.Renderer.html#method.with_pdf_version
+    pub fn set_pdf_version(&mut self, version: render::PdfVersion) {
+        self.pdf_version = Some(version);
+    }
+
+    /// Sets the compression level for the streams of the PDF file.
+    ///
+    /// See [`Renderer::with_compression`][] for more information.
+    ///
+    /// [`Renderer::with_compression`]: render/struct.Renderer.html#method.with_compression
+    pub fn set_compression(&mut self, compression: render::CompressionLevel) {
+        self.compression = Some(compression);
+    }
+
     /// Sets the creation date of the PDF file.
     pub fn set_creation_date(&mut self, date: printpdf::OffsetDateTime) {
         self.creation_date = Some(date);
@@ -771,16 +1175,158 @@ impl Document {
         self.root.push(element);
     }
 
+    /// Adds the given element to the document and records the positions where it is rendered
+    /// under the given ID.
+    ///
+    /// The positions can be retrieved from the handle returned by [`element_positions`][] once
+    /// the document has been rendered.
+    ///
+    /// [`element_positions`]: #method.element_positions
+    pub fn push_with_id<E: Element + 'static>(&mut self, id: impl Into<String>, element: E) {
+        self.root.push(elements::IdTracker::new(
+            id,
+            element,
+            self.element_positions.clone(),
+        ));
+    }
+
+    /// Restarts the numbering of the list counter with the given name at this position of the
+    /// document.
+    ///
+    /// Ordered lists that use this counter and that are added after calling this method start
+    /// with 1, see [`OrderedList::with_named_counter`][].
+    ///
+    /// [`OrderedList::with_named_counter`]: elements/struct.OrderedList.html#method.with_named_counter
+    pub fn reset_list_counter(&mut self, name: &str) {
+        self.list_counter_resets
+            .push((self.root.len(), name.to_owned()));
+    }
+
+    /// Returns a handle to the positions of the elements that have been added with
+    /// [`push_with_id`][].
+    ///
+    /// As rendering consumes the document, the handle has to be retrieved before rendering.  It
+    /// is filled during the rendering process.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let font_family = genpdf::fonts::from_files("./fonts", "LiberationSans", None).unwrap();
+    /// let mut doc = genpdf::Document::new(font_family);
+    /// doc.push_with_id("intro", genpdf::elements::Paragraph::new("Introduction"));
+    /// let positions = doc.element_positions();
+    /// doc.render_to_file("output.pdf").expect("Failed to render document");
+    /// for bbox in positions.get("intro") {
+    ///     println!("Rendered on page {}", bbox.page_index + 1);
+    /// }
+    /// ```
+    ///
+    /// [`push_with_id`]: #method.push_with_id
+    pub fn element_positions(&self) -> ElementPositions {
+        self.element_positions.clone()
+    }
+
+    /// Returns a handle to the registry of the elements that have been rendered on each page.
+    ///
+    /// As rendering consumes the document, the handle has to be retrieved before rendering.  It
+    /// is filled during the rendering process, see [`PageElementRegistry`][].
+    ///
+    /// [`PageElementRegistry`]: struct.PageElementRegistry.html
+    pub fn get_page_element_registry(&self) -> PageElementRegistry {
+        self.context.page_element_registry.clone()
+    }
+
+    /// Returns the number of elements that have been added to this document with [`push`][].
+    ///
+    /// Only the top-level elements are counted, not their children.
+    ///
+    /// [`push`]: #method.push
+    pub fn element_count(&self) -> usize {
+        self.root.len()
+    }
+
+    /// Returns the number of top-level elements in this document.
+    ///
+    /// This is the same as [`element_count`][].
+    ///
+    /// [`element_count`]: #method.element_count
+    pub fn len(&self) -> usize {
+        self.element_count()
+    }
+
+    /// Returns whether no elements have been added to this document.
+    pub fn is_empty(&self) -> bool {
+        self.root.is_empty()
+    }
+
+    /// Returns the top-level elements that have been added to this document.
+    pub fn elements(&self) -> &[Box<dyn Element>] {
+        self.root.elements()
+    }
+
+    /// Returns the plain text content of all elements that have been added to this document.
+    ///
+    /// The text of the top-level elements is separated by newlines.  Elements that do not
+    /// contain text, for example images, are skipped.  See [`PlainTextExtractor`][] for more
+    /// information.
+    ///
+    /// [`PlainTextExtractor`]: elements/trait.PlainTextExtractor.html
+    pub fn extract_text(&self) -> String {
+        use elements::PlainTextExtractor as _;
+        self.root.extract()
+    }
+
     /// Renders this document into a PDF file and writes it to the given writer.
     ///
     /// The given writer is always wrapped in a buffered writer.  For details on the rendering
     /// process, see the [Rendering Process section of the crate
     /// documentation](index.html#rendering-process).
-    pub fn render(mut self, w: impl io::Write) -> Result<(), error::Error> {
+    pub fn render(self, w: impl io::Write) -> Result<(), error::Error> {
+        self.render_impl(w, None).map(|_| ())
+    }
+
+    /// Renders this document into a PDF file, writes it to the given writer and returns
+    /// statistics about the rendering process.
+    ///
+    /// See [`render`][] for more information.
+    ///
+    /// [`render`]: #method.render
+    pub fn render_with_stats(self, w: impl io::Write) -> Result<RenderStats, error::Error> {
+        self.render_impl(w, None)
+    }
+
+    /// Renders this document into a PDF file, writes it to the given writer and reports the
+    /// rendering progress.
+    ///
+    /// The given callback is called after every finished page.  Its arguments are the number of
+    /// pages rendered so far and the estimated total number of pages.  The estimate is calculated
+    /// from the probable height of the document content and the page height, so it may change
+    /// while rendering if the content needs more pages than expected.
+    pub fn render_with_progress(
+        self,
+        w: impl io::Write,
+        progress: impl Fn(usize, usize),
+    ) -> Result<(), error::Error> {
+        self.render_impl(w, Some(&progress)).map(|_| ())
+    }
+
+    fn render_impl(
+        mut self,
+        w: impl io::Write,
+        progress: Option<&dyn Fn(usize, usize)>,
+    ) -> Result<RenderStats, error::Error> {
+        let start = time::Instant::now();
+        let total_elements = self.root.len();
         let mut renderer = render::Renderer::new(self.paper_size, &self.title)?;
         if let Some(conformance) = self.conformance {
             renderer = renderer.with_conformance(conformance);
         }
+        if let Some(pdf_version) = self.pdf_version {
+            renderer = renderer.with_pdf_version(pdf_version);
+        }
+        if let Some(compression) = self.compression {
+            renderer = renderer.with_compression(compression);
+        }
         if let Some(creation_date) = self.creation_date {
             renderer = renderer.with_creation_date(creation_date);
         }
@@ -788,12 +1334,62 @@ impl Document {
             renderer = renderer.with_modification_date(modification_date);
         }
         self.context.font_cache.load_pdf_fonts(&renderer)?;
+        if let (Some(decorator), Some(margins)) = (&mut self.decorator, self.first_page_margins) {
+            decorator.set_first_page_margins(margins);
+        }
+        let mut estimated_pages = 0;
+        self.context.page_size = self.paper_size;
+        let mut next_reset = 0;
         loop {
             let mut area = renderer.last_page().last_layer().area();
+            self.context.page_margins = Margins::default();
+            self.context.page_index = renderer.page_count() - 1;
             if let Some(decorator) = &mut self.decorator {
                 area = decorator.decorate_page(&mut self.context, area, self.style)?;
+            } else if renderer.page_count() == 1 {
+                if let Some(margins) = self.first_page_margins {
+                    area.add_margins(margins);
+                    self.context.page_margins = margins;
+                }
+            }
+            self.context.page_body_height = area.size().height;
+            if progress.is_some() && estimated_pages == 0 {
+                // Estimate the number of pages from the body height of the decorated first page.
+                // Measuring the elements does not change the list counters.
+                let height = self
+                    .root
+                    .get_probable_height(self.style, &self.context, area.clone());
+                let body_height = area.size().height;
+                estimated_pages = (height.0 / body_height.0).ceil().max(1.0) as usize;
+            }
+            let (context, style, min_page_remaining) =
+                (&self.context, self.style, self.min_page_remaining);
+            let list_counter_resets = &self.list_counter_resets;
+            let result = self.root.render_with_break_check(
+                context,
+                area,
+                style,
+                |index, element, area, started| {
+                    // Apply the counter resets that have been added before this element
+                    while let Some((_, name)) = list_counter_resets
+                        .get(next_reset)
+                        .filter(|(reset_index, _)| *reset_index <= index)
+                    {
+                        context.set_list_counter(name, 1);
+                        next_reset += 1;
+                    }
+                    started
+                        && area.size().height < min_page_remaining
+                        && element.get_probable_height(style, context, area) > Mm(0.0)
+                },
+            )?;
+            if let Some(progress) = progress {
+                let page_count = renderer.page_count();
+                if result.has_more {
+                    estimated_pages = estimated_pages.max(page_count + 1);
+                }
+                progress(page_count, estimated_pages.max(page_count));
             }
-            let result = self.root.render(&self.context, area, self.style)?;
             if result.has_more {
                 if result.size == Size::new(0, 0) {
                     return Err(error::Error::new(
@@ -801,12 +1397,31 @@ impl Document {
                         error::ErrorKind::PageSizeExceeded,
                     ));
                 }
+                let from_page = renderer.page_count() + self.context.page_number_offset;
                 renderer.add_page(self.paper_size);
+                let event = PageBreakEvent {
+                    from_page,
+                    to_page: from_page + 1,
+                    element_index: self.root.render_index(),
+                };
+                for cb in &self.page_break_callbacks {
+                    cb(event);
+                }
             } else {
                 break;
             }
         }
-        renderer.write(w)
+        let stats = RenderStats {
+            page_count: renderer.page_count(),
+            total_elements,
+            render_duration: start.elapsed(),
+            fonts_embedded: self.context.font_cache.embedded_font_count(),
+            images_embedded: renderer.image_count(),
+            pages_with_overflow: 0,
+            truncated_words: 0,
+        };
+        renderer.write(w)?;
+        Ok(stats)
     }
 
     /// Renders this document into a PDF file at the given path.
@@ -821,6 +1436,40 @@ impl Document {
             .with_context(|| format!("Could not create file {}", path.display()))?;
         self.render(file)
     }
+
+    /// Renders this document into a PDF file at the given path and returns statistics about the
+    /// rendering process.
+    ///
+    /// See [`render_to_file`][] for more information.
+    ///
+    /// [`render_to_file`]: #method.render_to_file
+    pub fn render_to_file_with_stats(
+        self,
+        path: impl AsRef<path::Path>,
+    ) -> Result<RenderStats, error::Error> {
+        let path = path.as_ref();
+        let file = fs::File::create(path)
+            .with_context(|| format!("Could not create file {}", path.display()))?;
+        self.render_with_stats(file)
+    }
+
+    /// Renders this document into a PDF file at the given path and reports the rendering
+    /// progress.
+    ///
+    /// If the given file does not exist, it is created.  If it exists, it is overwritten.  See
+    /// [`render_with_progress`][] for details on the progress callback.
+    ///
+    /// [`render_with_progress`]: #method.render_with_progress
+    pub fn render_to_file_with_progress(
+        self,
+        path: impl AsRef<path::Path>,
+        progress: impl Fn(usize, usize),
+    ) -> Result<(), error::Error> {
+        let path = path.as_ref();
+        let file = fs::File::create(path)
+            .with_context(|| format!("Could not create file {}", path.display()))?;
+        self.render_with_progress(file, progress)
+    }
 }
 
 impl<E: elements::IntoBoxedElement> std::iter::Extend<E> for Document {
@@ -829,6 +1478,143 @@ impl<E: elements::IntoBoxedElement> std::iter::Extend<E> for Document {
     }
 }
 
+type PageBreakCallback = Box<dyn Fn(PageBreakEvent)>;
+
+/// The area on a page that an element has been rendered to, see [`ElementPositions`][].
+///
+/// The position is measured from the upper left corner of the page.
+///
+/// [`ElementPositions`]: struct.ElementPositions.html
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct PageBoundingBox {
+    /// The zero-based index of the page.
+    pub page_index: usize,
+    /// The horizontal position of the element.
+    pub x: Mm,
+    /// The vertical position of the element.
+    pub y: Mm,
+    /// The width of the element.
+    pub width: Mm,
+    /// The height of the element.
+    pub height: Mm,
+}
+
+/// The positions of the elements that have been added to a document with
+/// [`Document::push_with_id`][].
+///
+/// This is a shared handle, see [`Document::element_positions`][].  An element that is split
+/// across multiple pages has one [`PageBoundingBox`][] per page.
+///
+/// [`Document::push_with_id`]: struct.Document.html#method.push_with_id
+/// [`Document::element_positions`]: struct.Document.html#method.element_positions
+/// [`PageBoundingBox`]: struct.PageBoundingBox.html
+#[derive(Clone, Debug, Default)]
+pub struct ElementPositions {
+    positions: rc::Rc<cell::RefCell<collections::HashMap<String, Vec<PageBoundingBox>>>>,
+}
+
+impl ElementPositions {
+    /// Returns the positions of the element with the given ID.
+    pub fn get(&self, id: &str) -> Vec<PageBoundingBox> {
+        self.positions.borrow().get(id).cloned().unwrap_or_default()
+    }
+
+    /// Returns the positions of all elements, indexed by their ID.
+    pub fn to_map(&self) -> collections::HashMap<String, Vec<PageBoundingBox>> {
+        self.positions.borrow().clone()
+    }
+
+    pub(crate) fn record(&self, id: &str, bounding_box: PageBoundingBox) {
+        self.positions
+            .borrow_mut()
+            .entry(id.to_owned())
+            .or_default()
+            .push(bounding_box);
+    }
+}
+
+/// The elements that have been rendered on the pages of a document, in rendering order.
+///
+/// This is a shared handle, see [`Document::get_page_element_registry`][].  Elements are added
+/// with [`Context::record_element`][].  During rendering, [`LinearLayout`][] records all
+/// elements that have a [`display_name`][], for example the elements that have been added with
+/// [`Document::push_with_id`][].  The registry can be used to generate a table of contents.
+///
+/// The pages are identified by their zero-based index, like in [`PageBoundingBox`][].
+///
+/// [`Document::get_page_element_registry`]: struct.Document.html#method.get_page_element_registry
+/// [`Document::push_with_id`]: struct.Document.html#method.push_with_id
+/// [`Context::record_element`]: struct.Context.html#method.record_element
+/// [`LinearLayout`]: elements/struct.LinearLayout.html
+/// [`display_name`]: trait.Element.html#method.display_name
+/// [`PageBoundingBox`]: struct.PageBoundingBox.html
+#[derive(Clone, Debug, Default)]
+pub struct PageElementRegistry {
+    entries: rc::Rc<cell::RefCell<Vec<(usize, String)>>>,
+}
+
+impl PageElementRegistry {
+    /// Returns the recorded `(page_index, display_name)` pairs in rendering order.
+    pub fn entries(&self) -> Vec<(usize, String)> {
+        self.entries.borrow().clone()
+    }
+
+    fn record(&self, page_index: usize, display_name: &str) {
+        self.entries
+            .borrow_mut()
+            .push((page_index, display_name.to_owned()));
+    }
+}
+
+/// Statistics about the rendering process of a [`Document`][].
+///
+/// See [`Document::render_with_stats`][] and [`Document::render_to_file_with_stats`][].  As the
+/// rendering methods consume the document, the statistics are returned by these methods instead
+/// of being stored in the document.
+///
+/// [`Document`]: struct.Document.html
+/// [`Document::render_with_stats`]: struct.Document.html#method.render_with_stats
+/// [`Document::render_to_file_with_stats`]: struct.Document.html#method.render_to_file_with_stats
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct RenderStats {
+    /// The number of pages of the generated document.
+    pub page_count: usize,
+    /// The number of top-level elements of the document.
+    pub total_elements: usize,
+    /// The time it took to render the document, excluding writing the PDF file.
+    pub render_duration: time::Duration,
+    /// The number of fonts that have been embedded into the PDF file.
+    pub fonts_embedded: usize,
+    /// The number of images that have been added to the PDF file.
+    pub images_embedded: usize,
+    /// The number of pages with content that did not fit into the page.
+    ///
+    /// This is currently always zero:  If an element does not fit into a page, rendering fails
+    /// with an error.
+    pub pages_with_overflow: usize,
+    /// The number of words that were cut off because they did not fit into a line.
+    ///
+    /// This is currently always zero:  If a word does not fit into a line, rendering fails with
+    /// an error.
+    pub truncated_words: usize,
+}
+
+/// Information about a page break, see [`Document::on_page_break`][].
+///
+/// [`Document::on_page_break`]: struct.Document.html#method.on_page_break
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PageBreakEvent {
+    /// The number of the page that was filled.
+    pub from_page: usize,
+    /// The number of the new page.
+    pub to_page: usize,
+    /// The index of the top-level element that did not fit on the previous page.
+    pub element_index: usize,
+}
+
 /// The result of the rendering process.
 ///
 /// This struct is returned by implementations of the [`Element::render`][] method.  It contains
@@ -873,6 +1659,16 @@ pub trait PageDecorator {
         area: render::Area<'a>,
         style: style::Style,
     ) -> Result<render::Area<'a>, error::Error>;
+
+    /// Sets the margins for the first page that replace the regular margins of this decorator.
+    ///
+    /// This method is called by [`Document::set_first_page_margins`][] before the rendering
+    /// starts.  The default implementation ignores the margins.
+    ///
+    /// [`Document::set_first_page_margins`]: struct.Document.html#method.set_first_page_margins
+    fn set_first_page_margins(&mut self, margins: Margins) {
+        let _ = margins;
+    }
 }
 
 type HeaderCallback = Box<dyn Fn(usize) -> Box<dyn Element>>;
@@ -890,6 +1686,7 @@ type HeaderCallback = Box<dyn Fn(usize) -> Box<dyn Element>>;
 pub struct SimplePageDecorator {
     page: usize,
     margins: Option<Margins>,
+    first_page_margins: Option<Margins>,
     header_cb: Option<HeaderCallback>,
 }
 
@@ -906,11 +1703,22 @@ impl SimplePageDecorator {
         self.margins = Some(margins.into());
     }
 
+    fn page_margins(&self) -> Option<Margins> {
+        if self.page == 1 {
+            self.first_page_margins.or(self.margins)
+        } else {
+            self.margins
+        }
+    }
+
     /// Sets the header generator for this document.
     ///
     /// The given closure will be called once per page.  Its argument is the page number (starting
-    /// with 1), and its return value will be rendered at the top of the page.  The document
-    /// content will start directly after the element.
+    /// with 1 or the number set with [`Document::set_first_page_number`][]), and its return value
+    /// will be rendered at the top of the page.  The document content will start directly after
+    /// the element.
+    ///
+    /// [`Document::set_first_page_number`]: struct.Document.html#method.set_first_page_number
     pub fn set_header<F, E>(&mut self, cb: F)
     where
         F: Fn(usize) -> E + 'static,
@@ -929,64 +1737,36 @@ impl PageDecorator for SimplePageDecorator {
         style: style::Style,
     ) -> Result<render::Area<'a>, error::Error> {
         self.page += 1;
-        context.page_number = self.page;
-        if let Some(margins) = self.margins {
+        context.page_number = self.page + context.page_number_offset;
+        if let Some(margins) = self.page_margins() {
             area.add_margins(margins);
+            context.set_page_margins(margins);
         }
         if let Some(cb) = &self.header_cb {
-            let mut element = cb(self.page);
+            let mut element = cb(context.page_number);
             let result = element.render(context, area.clone(), style)?;
             area.add_offset(Position::new(0, result.size.height));
         }
         Ok(area)
     }
+
+    fn set_first_page_margins(&mut self, margins: Margins) {
+        self.first_page_margins = Some(margins);
+    }
 }
 
 type CustomHeaderCallback = Box<dyn Fn(usize) -> Result<Box<dyn Element>, error::Error>>;
 type CustomFooterCallback = Box<dyn Fn(usize) -> Result<Box<dyn Element>, error::Error>>;
-
-#[derive(Clone, Copy)]
-/// Prepares a page of a document with borders, a header and a footer.
-pub struct Borders {
-    /// The top margin of the area.
-    pub top: Option<LineStyle>,
-    /// The right margin of the area.
-    pub right: Option<LineStyle>,
-    /// The bottom margin of the area.
-    pub bottom: Option<LineStyle>,
-    /// The left margin of the area.
-    pub left: Option<LineStyle>,
-}
-
-impl Borders {
-    /// Creates a new `Borders` instance with all four borders set to the given value.
-    pub fn all(all: impl Into<Mm>) -> Borders {
-        let all = all.into();
-        Borders {
-            top: Some(all.into()),
-            right: Some(all.into()),
-            bottom: Some(all.into()),
-            left: Some(all.into()),
-        }
-    }
-    /// Creates a new `Borders` instance with all four line styles set to the given value.
-    pub fn all_styled(all: impl Into<LineStyle>) -> Borders {
-        let all = all.into();
-        Borders {
-            top: Some(all.into()),
-            right: Some(all.into()),
-            bottom: Some(all.into()),
-            left: Some(all.into()),
-        }
-    }
-}
+type CustomDrawCallback = Box<dyn Fn(usize, &mut render::Area<'_>) -> Result<(), error::Error>>;
 
 /// Custom header and footer along with margins.
 pub struct CustomPageDecorator {
     page: usize,
     margins: Option<Margins>,
+    first_page_margins: Option<Margins>,
     header_callback_fn: Option<CustomHeaderCallback>,
     footer_callback_fn: Option<CustomFooterCallback>,
+    draw_callback_fns: Vec<CustomDrawCallback>,
     borders: Option<Borders>,
 }
 
@@ -996,8 +1776,10 @@ impl CustomPageDecorator {
         CustomPageDecorator {
             page: 0,
             margins: None,
+            first_page_margins: None,
             header_callback_fn: None,
             footer_callback_fn: None,
+            draw_callback_fns: Vec::new(),
             borders: None,
         }
     }
@@ -1007,6 +1789,14 @@ impl CustomPageDecorator {
         self.margins = margins;
     }
 
+    fn page_margins(&self) -> Option<Margins> {
+        if self.page == 1 {
+            self.first_page_margins.or(self.margins)
+        } else {
+            self.margins
+        }
+    }
+
     /// set borders
     pub fn set_borders(&mut self, borders: Option<Borders>) {
         self.borders = borders;
@@ -1029,6 +1819,21 @@ impl CustomPageDecorator {
     {
         self.footer_callback_fn = Some(Box::new(move |page| cb(page).map(|e| Box::new(e) as _)));
     }
+
+    /// Registers a callback that draws directly on the page.
+    ///
+    /// The callback receives the page number and the page area after the margins have been
+    /// applied.  It can use all drawing methods of the [`Area`][], for example to add watermarks
+    /// or other graphic decorations.  Multiple callbacks can be registered.  They are called in
+    /// the order of registration after the header and the footer have been rendered.
+    ///
+    /// [`Area`]: render/struct.Area.html
+    pub fn register_draw_callback_fn<F>(&mut self, cb: F)
+    where
+        F: Fn(usize, &mut render::Area<'_>) -> Result<(), error::Error> + 'static,
+    {
+        self.draw_callback_fns.push(Box::new(cb));
+    }
 }
 
 impl PageDecorator for CustomPageDecorator {
@@ -1040,10 +1845,12 @@ impl PageDecorator for CustomPageDecorator {
     ) -> Result<render::Area<'a>, error::Error> {
         // log_msg(&format!("decorate_page:: area size: {:?}", area.size()));
         self.page += 1;
-        context.page_number = self.page;
-        if let Some(margins) = self.margins {
+        context.page_number = self.page + context.page_number_offset;
+        if let Some(margins) = self.page_margins() {
             area.add_margins(margins);
+            context.set_page_margins(margins);
         }
+        let page_area = area.clone();
 
         let mut space_left = 0.0;
         let mut space_right = 0.0;
@@ -1177,7 +1984,7 @@ impl PageDecorator for CustomPageDecorator {
 
         // Render Header
         if let Some(cb) = &self.header_callback_fn {
-            match cb(self.page) {
+            match cb(context.page_number) {
                 Ok(mut element) => {
                     let result = element.render(context, area.clone(), style)?;
                     area.add_offset(Position::new(0, result.size.height));
@@ -1189,7 +1996,7 @@ impl PageDecorator for CustomPageDecorator {
         // Render Footer
         let mut footer_area = area.next_layer();
         if let Some(cb) = &self.footer_callback_fn {
-            match cb(self.page) {
+            match cb(context.page_number) {
                 Ok(mut element) => {
                     let height = footer_area.size().height;
                     // log_msg(&format!("footer_area height: {:?}", height));
@@ -1213,7 +2020,7 @@ impl PageDecorator for CustomPageDecorator {
                     let footer_size = footer_el_result.size.height - height;
                     let height = footer_area.size().height - footer_size;
                     let mut remaining_area_height = height - footer_height;
-                    if let Some(mr) = self.margins {
+                    if let Some(mr) = self.page_margins() {
                         remaining_area_height -= mr.top;
                     }
                     area.set_height(remaining_area_height);
@@ -1221,8 +2028,16 @@ impl PageDecorator for CustomPageDecorator {
                 Err(e) => return Err(e),
             }
         }
+
+        for cb in &self.draw_callback_fns {
+            cb(context.page_number, &mut page_area.clone())?;
+        }
         Ok(area)
     }
+
+    fn set_first_page_margins(&mut self, margins: Margins) {
+        self.first_page_margins = Some(margins);
+    }
 }
 
 /// An element of a PDF document.
@@ -1258,7 +2073,8 @@ pub trait Element {
     ///   to this method is always the start of the rendering process, and subsequent calls are
     ///   always continuations of the same rendering process.  This means that the element does not
     ///   have to reset its state after it has processed all content, and it is allowed to drop
-    ///   content that has already been rendered.
+    ///   content that has already been rendered.  The only exception is a call to
+    ///   [`reset_render`][], which starts a new rendering process.
     /// - If a call to this method returns an `Err` value, it will not be called again.
     /// - After the first call, the method will only be called again if the `has_more` of the last
     ///   [`RenderResult`][] was set to true.
@@ -1270,6 +2086,7 @@ pub trait Element {
     /// [`render`]: struct.Document.html#method.render
     /// [`render_to_file`]: struct.Document.html#method.render_to_file
     /// [`RenderResult`]: struct.RenderResult.html
+    /// [`reset_render`]: #method.reset_render
     fn render(
         &mut self,
         context: &Context,
@@ -1285,6 +2102,94 @@ pub trait Element {
         area: render::Area<'_>,
     ) -> Mm;
 
+    /// Returns the minimum width that is required to render this element.
+    ///
+    /// For text, this is the width of the longest word.  Layouts can use this method to make sure
+    /// that their columns are wide enough for their content.  The default implementation returns
+    /// zero.
+    fn get_min_width(&mut self, style: style::Style, context: &Context) -> Mm {
+        let _ = (style, context);
+        Mm(0.0)
+    }
+
+    /// Returns the width that this element would use if it was rendered without any width
+    /// constraints.
+    ///
+    /// For text, this is the width of the text printed in a single line.  Layouts can use this
+    /// method to shrink their columns to the width of their content.  The default implementation
+    /// returns the width of the [`preferred_size`][] if set, or the [`get_min_width`][].
+    ///
+    /// [`preferred_size`]: #method.preferred_size
+    /// [`get_min_width`]: #method.get_min_width
+    fn get_natural_width(&mut self, style: style::Style, context: &Context) -> Mm {
+        match self.preferred_size() {
+            Some(size) => size.width,
+            None => self.get_min_width(style, context),
+        }
+    }
+
+    /// Returns the size that this element prefers to be rendered with, if it has a fixed size.
+    ///
+    /// In contrast to [`get_probable_height`][], this method does not depend on the area or the
+    /// style and can be used by layouts to allocate space to elements with a fixed size before
+    /// distributing the remaining space.  The default implementation returns `None`.
+    ///
+    /// [`get_probable_height`]: #tymethod.get_probable_height
+    fn preferred_size(&self) -> Option<Size> {
+        None
+    }
+
+    /// Returns the vertical space that this element adds before its content.
+    ///
+    /// [`LinearLayout`][] uses this value to collapse the space between two consecutive
+    /// elements.  The default implementation returns zero.
+    ///
+    /// [`LinearLayout`]: elements/struct.LinearLayout.html
+    fn space_before(&self) -> Mm {
+        Mm(0.0)
+    }
+
+    /// Returns the vertical space that this element adds after its content.
+    ///
+    /// See [`space_before`][] for more information.  The default implementation returns zero.
+    ///
+    /// [`space_before`]: #method.space_before
+    fn space_after(&self) -> Mm {
+        Mm(0.0)
+    }
+
+    /// Returns the name of this element for the [`PageElementRegistry`][].
+    ///
+    /// If this method returns a name, [`LinearLayout`][] records the element with
+    /// [`Context::record_element`][] on every page that it is rendered to.  The default
+    /// implementation returns `None`.
+    ///
+    /// [`PageElementRegistry`]: struct.PageElementRegistry.html
+    /// [`LinearLayout`]: elements/struct.LinearLayout.html
+    /// [`Context::record_element`]: struct.Context.html#method.record_element
+    fn display_name(&self) -> Option<&str> {
+        None
+    }
+
+    /// Returns this element as a [`PlainTextExtractor`][] if it contains text.
+    ///
+    /// The default implementation returns `None`.
+    ///
+    /// [`PlainTextExtractor`]: elements/trait.PlainTextExtractor.html
+    fn as_plain_text_extractor(&self) -> Option<&dyn elements::PlainTextExtractor> {
+        None
+    }
+
+    /// Resets the render state of this element so that the next call to [`render`][] starts a
+    /// new rendering process.
+    ///
+    /// This makes it possible to render an element speculatively, for example to find out whether
+    /// it fits into an area, and to render it again afterwards.  Elements that keep track of their
+    /// rendering progress should override this method.  The default implementation does nothing.
+    ///
+    /// [`render`]: #tymethod.render
+    fn reset_render(&mut self) {}
+
     /// Draws a frame around this element using the given line style.
     fn framed(self, line_style: impl Into<style::LineStyle>) -> elements::FramedElement<Self>
     where
@@ -1301,6 +2206,30 @@ pub trait Element {
         elements::PaddedElement::new(self, padding)
     }
 
+    /// Adds the same padding on all four sides of this element.
+    fn padded_uniform(self, padding: impl Into<Mm>) -> elements::PaddedElement<Self>
+    where
+        Self: Sized,
+    {
+        elements::PaddedElement::uniform(self, padding)
+    }
+
+    /// Adds a padding on the left and on the right side of this element.
+    fn padded_horizontal(self, padding: impl Into<Mm>) -> elements::PaddedElement<Self>
+    where
+        Self: Sized,
+    {
+        elements::PaddedElement::horizontal(self, padding)
+    }
+
+    /// Adds a padding on the top and on the bottom side of this element.
+    fn padded_vertical(self, padding: impl Into<Mm>) -> elements::PaddedElement<Self>
+    where
+        Self: Sized,
+    {
+        elements::PaddedElement::vertical(self, padding)
+    }
+
     /// Sets the default style for this element and its children.
     fn styled(self, style: impl Into<style::Style>) -> elements::StyledElement<Self>
     where
@@ -1313,37 +2242,207 @@ pub trait Element {
 /// The context for a rendering process.
 ///
 /// This struct stores data that is shared between all elements during the rendering process.
-#[derive(Debug)]
 #[non_exhaustive]
 pub struct Context {
     /// The page number of the current page.
+    ///
+    /// This number already includes the [`page_number_offset`](#structfield.page_number_offset).
     pub page_number: usize,
+    /// The offset that is added to the page numbers, see
+    /// [`Document::set_first_page_number`](struct.Document.html#method.set_first_page_number).
+    pub page_number_offset: usize,
     /// The font cache for this rendering process.
     pub font_cache: fonts::FontCache,
     /// The hyphenator to use for hyphenation.
     ///
-    /// *Only available if the `hyphenation` feature is enabled.*
-    ///
     /// If this field is `None`, hyphenation is disabled.
-    #[cfg(feature = "hyphenation")]
-    pub hyphenator: Option<hyphenation::Standard>,
+    pub hyphenator: Option<Box<dyn Hyphenator>>,
+    /// User-defined variables that replace `#{var_name}` placeholders in paragraphs, see
+    /// [`Document::set_user_data`](struct.Document.html#method.set_user_data).
+    pub user_data: collections::HashMap<String, String>,
+    /// The total number of pages that replaces `#{total_pages}` placeholders in paragraphs, see
+    /// [`Document::set_total_pages`](struct.Document.html#method.set_total_pages).
+    pub total_pages: Option<usize>,
+    tracer: Box<dyn Tracer>,
+    page_size: Size,
+    page_margins: Margins,
+    page_body_height: Mm,
+    page_index: usize,
+    list_counters: cell::RefCell<collections::HashMap<String, usize>>,
+    section_stack: cell::RefCell<Vec<String>>,
+    section_counters: cell::RefCell<Vec<usize>>,
+    page_element_registry: PageElementRegistry,
+}
+
+impl fmt::Debug for Context {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Context")
+            .field("page_number", &self.page_number)
+            .field("page_number_offset", &self.page_number_offset)
+            .field("font_cache", &self.font_cache)
+            .field("has_hyphenator", &self.hyphenator.is_some())
+            .field("user_data", &self.user_data)
+            .field("total_pages", &self.total_pages)
+            .field("tracer", &format_args!(".."))
+            .field("page_size", &self.page_size)
+            .field("page_margins", &self.page_margins)
+            .field("page_body_height", &self.page_body_height)
+            .field("page_index", &self.page_index)
+            .field("list_counters", &self.list_counters)
+            .field("section_stack", &self.section_stack)
+            .field("section_counters", &self.section_counters)
+            .field("page_element_registry", &self.page_element_registry)
+            .finish()
+    }
 }
 
 impl Context {
-    #[cfg(not(feature = "hyphenation"))]
     fn new(font_cache: fonts::FontCache) -> Context {
         Context {
             font_cache,
             page_number: 0,
+            page_number_offset: 0,
+            hyphenator: None,
+            user_data: collections::HashMap::new(),
+            total_pages: None,
+            tracer: Box::new(utils::StdoutTracer),
+            page_size: Size::default(),
+            page_margins: Margins::default(),
+            page_body_height: Mm(0.0),
+            page_index: 0,
+            list_counters: cell::RefCell::default(),
+            section_stack: cell::RefCell::default(),
+            section_counters: cell::RefCell::default(),
+            page_element_registry: PageElementRegistry::default(),
         }
     }
 
-    #[cfg(feature = "hyphenation")]
-    fn new(font_cache: fonts::FontCache) -> Context {
-        Context {
-            font_cache,
-            hyphenator: None,
-        }
+    /// Passes the given message to the tracer of this document, see
+    /// [`Document::set_tracer`][].
+    ///
+    /// [`Document::set_tracer`]: struct.Document.html#method.set_tracer
+    pub fn trace(&self, level: LogLevel, message: &str) {
+        self.tracer.trace(level, message);
+    }
+
+    /// Returns the next number of the list counter with the given name, or 1 if the counter has
+    /// not been used yet.
+    ///
+    /// See [`OrderedList::with_named_counter`][] for more information on list counters.
+    ///
+    /// [`OrderedList::with_named_counter`]: elements/struct.OrderedList.html#method.with_named_counter
+    pub fn list_counter(&self, name: &str) -> usize {
+        self.list_counters.borrow().get(name).copied().unwrap_or(1)
+    }
+
+    /// Sets the next number of the list counter with the given name.
+    pub fn set_list_counter(&self, name: &str, next_number: usize) {
+        self.list_counters
+            .borrow_mut()
+            .insert(name.to_owned(), next_number);
+    }
+
+    /// Enters a new section with the given title.
+    ///
+    /// The section is nested in the current section, if any, and its number is incremented.
+    /// Every call to this method should be paired with a call to [`pop_section`][].
+    ///
+    /// [`pop_section`]: #method.pop_section
+    pub fn push_section(&self, title: &str) {
+        let mut stack = self.section_stack.borrow_mut();
+        let mut counters = self.section_counters.borrow_mut();
+        let depth = stack.len();
+        counters.resize(depth + 1, 0);
+        counters[depth] += 1;
+        stack.push(title.to_owned());
+    }
+
+    /// Leaves the current section.
+    pub fn pop_section(&self) {
+        self.section_stack.borrow_mut().pop();
+    }
+
+    /// Returns the title of the current section, if any.
+    ///
+    /// This can be used by page decorators to display the current section title as a running
+    /// header.
+    pub fn current_section(&self) -> Option<String> {
+        self.section_stack.borrow().last().cloned()
+    }
+
+    /// Returns the number of the current section, for example `1.2.3`, or an empty string if
+    /// there is no current section.
+    pub fn section_number(&self) -> String {
+        let depth = self.section_stack.borrow().len();
+        self.section_counters.borrow()[..depth]
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
+    /// Records that the element with the given display name has been rendered on the page with
+    /// the given zero-based index, see [`PageElementRegistry`][] and [`page_index`][].
+    ///
+    /// [`PageElementRegistry`]: struct.PageElementRegistry.html
+    /// [`page_index`]: #method.page_index
+    pub fn record_element(&self, page_index: usize, display_name: &str) {
+        self.page_element_registry.record(page_index, display_name);
+    }
+
+    /// Returns the zero-based index of the current page in the generated document.
+    ///
+    /// In contrast to [`page_number`](#structfield.page_number), the index does not include the
+    /// page number offset.
+    pub fn page_index(&self) -> usize {
+        self.page_index
+    }
+
+    /// Returns the size of the current page.
+    pub fn page_size(&self) -> Size {
+        self.page_size
+    }
+
+    /// Returns the size of the current page minus the margins of the current page.
+    ///
+    /// The margins are set by the page decorator, see [`set_page_margins`][].
+    ///
+    /// [`set_page_margins`]: #method.set_page_margins
+    pub fn content_area_size(&self) -> Size {
+        let margins = self.page_margins;
+        Size::new(
+            self.page_size.width - margins.left - margins.right,
+            self.page_size.height - margins.top - margins.bottom,
+        )
+    }
+
+    /// Returns the height of the area that the page decorator provided for the document content
+    /// on the current page, or zero if it is not known.
+    pub(crate) fn page_body_height(&self) -> Mm {
+        self.page_body_height
+    }
+
+    /// Sets the margins of the current page.
+    ///
+    /// This method should be called by [`PageDecorator`][] implementations that add margins to
+    /// the page so that the [`content_area_size`][] is correct.  The margins are reset to zero
+    /// before every new page.
+    ///
+    /// [`PageDecorator`]: trait.PageDecorator.html
+    /// [`content_area_size`]: #method.content_area_size
+    pub fn set_page_margins(&mut self, margins: impl Into<Margins>) {
+        self.page_margins = margins.into();
+    }
+
+    /// Returns the value of the user-defined variable with the given name, if set.
+    pub fn get_var(&self, key: &str) -> Option<&str> {
+        self.user_data.get(key).map(String::as_str)
+    }
+
+    /// Returns the factor that all font sizes are multiplied with, see
+    /// [`Document::set_font_scale`](struct.Document.html#method.set_font_scale).
+    pub fn font_scale(&self) -> f64 {
+        self.font_cache.font_scale()
     }
 }
 
@@ -1375,6 +2474,545 @@ mod tests {
         }
     }
 
+    /// Draws on the first layer of an A4 page and returns the uncompressed PDF document.
+    fn render_content(f: impl FnOnce(super::render::Area<'_>)) -> String {
+        use super::render::{CompressionLevel, Renderer};
+
+        let renderer = Renderer::new(super::PaperSize::A4, "Test").unwrap();
+        f(renderer.first_page().first_layer().area());
+        let mut data = Vec::new();
+        renderer
+            .with_compression(CompressionLevel::None)
+            .write(&mut data)
+            .unwrap();
+        String::from_utf8_lossy(&data).into_owned()
+    }
+
+    #[test]
+    fn test_pdf_version() {
+        use super::render::{PdfVersion, Renderer};
+
+        let mut data = Vec::new();
+        Renderer::new(super::PaperSize::A4, "Test")
+            .unwrap()
+            .with_pdf_version(PdfVersion::Pdf20)
+            .write(&mut data)
+            .unwrap();
+        assert!(data.starts_with(b"%PDF-2.0"));
+    }
+
+    #[test]
+    fn test_compression() {
+        use super::render::{CompressionLevel, Renderer};
+
+        let render = |compression| {
+            let renderer = Renderer::new(super::PaperSize::A4, "Test").unwrap();
+            let area = renderer.first_page().first_layer().area();
+            area.draw_line(
+                vec![super::Position::new(10, 10), super::Position::new(100, 100)],
+                super::style::LineStyle::new(),
+            );
+            let mut data = Vec::new();
+            renderer
+                .with_compression(compression)
+                .write(&mut data)
+                .unwrap();
+            data
+        };
+        let uncompressed = render(CompressionLevel::None);
+        let compressed = render(CompressionLevel::Best);
+        assert!(!uncompressed.windows(11).any(|w| w == b"FlateDecode"));
+        assert!(compressed.windows(11).any(|w| w == b"FlateDecode"));
+    }
+
+    #[test]
+    fn test_font_family_variants() {
+        use super::elements::tests::get_font_family;
+        use super::fonts::{FontCache, FontFamily, FontVariant};
+
+        // All variants of the test font family are clones of the regular font.
+        let family = get_font_family();
+        assert!(!family.is_synthesized_from_regular(FontVariant::Regular));
+        assert!(family.is_synthesized_from_regular(FontVariant::Bold));
+        assert!(!family.has_italic());
+
+        // Fonts that have been loaded separately are not the same font.
+        let bold = get_font_family().regular;
+        assert!(!family.regular.is_same_font(&bold));
+        let family = FontFamily { bold, ..family };
+        assert!(!family.is_synthesized_from_regular(FontVariant::Bold));
+        assert!(family.is_synthesized_from_regular(FontVariant::BoldItalic));
+
+        let font_cache = FontCache::new(family);
+        assert_eq!(font_cache.embedded_font_count(), 2);
+        let info = &font_cache.list_families()[0];
+        assert!(info.has_bold);
+        assert!(!info.has_italic);
+        assert_eq!(info.family.italic, info.family.regular);
+    }
+
+    #[test]
+    fn test_measure_text() {
+        use super::elements::tests::get_font_family;
+        use super::fonts::FontCache;
+        use super::render::Renderer;
+        use super::style::Style;
+        use super::{Mm, Size};
+
+        let font_cache = FontCache::new(get_font_family());
+        let style = Style::new();
+        let renderer = Renderer::new(Size::new(100, 100), "Test").unwrap();
+        let mut area = renderer.first_page().first_layer().area();
+
+        let size = area.measure_text(&font_cache, "text", style);
+        assert_eq!(size.width, style.str_width(&font_cache, "text"));
+        assert_eq!(size.height, style.line_height(&font_cache));
+        assert!(area.would_fit_text(&font_cache, "text", style));
+
+        area.set_width(size.width);
+        assert!(area.would_fit_text(&font_cache, "text", style));
+        assert!(!area.would_fit_text(&font_cache, "texts", style));
+        area.set_height(size.height - Mm(0.1));
+        assert!(!area.would_fit_text(&font_cache, "text", style));
+    }
+
+    #[test]
+    fn test_split_with_gap() {
+        use super::elements::ColumnWidths;
+        use super::render::Renderer;
+        use super::{Position, Size};
+
+        let renderer = Renderer::new(Size::new(100, 100), "Test").unwrap();
+        let area = renderer.first_page().first_layer().area();
+
+        // The gaps take up 20 mm, and the remaining 80 mm are split by the weights.
+        let areas = area.split_horizontally_with_gap(&ColumnWidths::Weights(vec![1, 3, 1]), 10);
+        let origins: Vec<_> = areas.iter().map(|a| a.origin()).collect();
+        let sizes: Vec<_> = areas.iter().map(|a| a.size()).collect();
+        assert_eq!(
+            origins,
+            vec![
+                Position::new(0, 0),
+                Position::new(26, 0),
+                Position::new(84, 0)
+            ]
+        );
+        assert_eq!(
+            sizes,
+            vec![Size::new(16, 100), Size::new(48, 100), Size::new(16, 100)]
+        );
+
+        let areas = area.split_vertically_with_gap(&ColumnWidths::PixelWidths(vec![20.0, 30.0]), 5);
+        let origins: Vec<_> = areas.iter().map(|a| a.origin()).collect();
+        let sizes: Vec<_> = areas.iter().map(|a| a.size()).collect();
+        assert_eq!(origins, vec![Position::new(0, 0), Position::new(0, 25)]);
+        assert_eq!(sizes, vec![Size::new(100, 20), Size::new(100, 30)]);
+    }
+
+    #[test]
+    fn test_split_vertically() {
+        use super::render::Renderer;
+        use super::{Mm, Position, Size};
+
+        let renderer = Renderer::new(Size::new(100, 100), "Test").unwrap();
+        let area = renderer.first_page().first_layer().area();
+
+        // The last area gets the remaining height instead of the given height.
+        let areas = area.split_vertically(&[Mm(20.0), Mm(30.0), Mm(10.0)]);
+        let origins: Vec<_> = areas.iter().map(|a| a.origin()).collect();
+        let sizes: Vec<_> = areas.iter().map(|a| a.size()).collect();
+        assert_eq!(
+            origins,
+            vec![
+                Position::new(0, 0),
+                Position::new(0, 20),
+                Position::new(0, 50)
+            ]
+        );
+        assert_eq!(
+            sizes,
+            vec![Size::new(100, 20), Size::new(100, 30), Size::new(100, 50)]
+        );
+
+        // If the given heights exceed the height of the area, the last area is empty.
+        let areas = area.split_vertically(&[Mm(80.0), Mm(40.0), Mm(10.0)]);
+        let sizes: Vec<_> = areas.iter().map(|a| a.size()).collect();
+        assert_eq!(
+            sizes,
+            vec![Size::new(100, 80), Size::new(100, 40), Size::new(100, 0)]
+        );
+
+        assert!(area.split_vertically(&[]).is_empty());
+
+        let areas = area.split_vertically_equal(4);
+        let origins: Vec<_> = areas.iter().map(|a| a.origin()).collect();
+        let sizes: Vec<_> = areas.iter().map(|a| a.size()).collect();
+        assert_eq!(
+            origins,
+            vec![
+                Position::new(0, 0),
+                Position::new(0, 25),
+                Position::new(0, 50),
+                Position::new(0, 75)
+            ]
+        );
+        assert_eq!(sizes, vec![Size::new(100, 25); 4]);
+
+        assert!(area.split_vertically_equal(0).is_empty());
+    }
+
+    #[test]
+    fn test_ensure_page_count() {
+        use super::render::Renderer;
+
+        let mut renderer = Renderer::new(super::PaperSize::A4, "Test").unwrap();
+        assert_eq!(renderer.add_empty_page(), 1);
+        renderer.ensure_page_count(4);
+        assert_eq!(renderer.page_count(), 4);
+        renderer.ensure_page_count(2);
+        assert_eq!(renderer.page_count(), 4);
+    }
+
+    #[test]
+    fn test_push_graphics_state() {
+        use super::style::{Color, LineStyle};
+        use super::Position;
+
+        let red = LineStyle::new().with_color(Color::Rgb(255, 0, 0));
+        let points = vec![Position::new(1, 1), Position::new(2, 2)];
+        let content = render_content(|area| {
+            {
+                let guard = area.push_graphics_state();
+                guard.draw_line(points.clone(), red);
+            }
+            // The outline color has been restored, so it has to be set again.
+            area.draw_line(points.clone(), red);
+        });
+        assert_eq!(content.matches("1.00 0.00 0.00 RG").count(), 2);
+        assert!(content.contains("S\nQ\n0.28 w\n1.00 0.00 0.00 RG\n"));
+    }
+
+    #[test]
+    fn test_with_clip() {
+        use super::{Margins, Size};
+
+        let clip = "q\n0.00 841.89 m\n28.35 841.89 l\n28.35 785.20 l\n0.00 785.20 l\nW\nn\nQ\n";
+        let content = render_content(|area| {
+            let _clipped = area.with_clip(Size::new(10, 20));
+        });
+        assert!(content.contains(clip));
+
+        let content = render_content(|mut area| {
+            area.add_margins(Margins::trbl(0, 200, 277, 0));
+            let _clipped = area.clip_to_self();
+        });
+        assert!(content.contains(clip));
+    }
+
+    #[test]
+    fn test_fill_rect() {
+        use super::style::Color;
+        use super::{Position, Size};
+
+        let content = render_content(|area| {
+            area.fill_rect_at(
+                Position::new(0, 0),
+                Size::new(10, 20),
+                Color::Rgb(0, 0, 255),
+            );
+        });
+        assert!(content.contains("0.00 0.00 1.00 rg\n"));
+        assert!(
+            content.contains("0.00 841.89 m\n28.35 841.89 l\n28.35 785.20 l\n0.00 785.20 l\nb\n")
+        );
+
+        let content = render_content(|area| area.fill_rect(Color::Rgb(0, 0, 255)));
+        assert!(content.contains("0.00 841.89 m\n595.28 841.89 l\n595.28 0.00 l\n0.00 0.00 l\nb\n"));
+    }
+
+    #[test]
+    fn test_min_page_remaining() {
+        use super::elements::{tests::get_font_family, Paragraph};
+        use super::{Document, Element as _, Margins, Mm, Size};
+
+        let page_count = |min_page_remaining: Mm| {
+            let mut doc = Document::new(get_font_family());
+            doc.set_paper_size(Size::new(100, 100));
+            doc.set_min_page_remaining(min_page_remaining);
+            doc.push(Paragraph::new("first").padded(Margins::trbl(85, 0, 0, 0)));
+            doc.push(Paragraph::new("second"));
+            doc.render_with_stats(std::io::sink()).unwrap().page_count
+        };
+        assert_eq!(page_count(Mm(0.0)), 1);
+        assert_eq!(page_count(Mm(5.0)), 1);
+        assert_eq!(page_count(Mm(20.0)), 2);
+    }
+
+    #[test]
+    fn test_context_debug() {
+        use super::elements::tests::get_font_family;
+        use super::{fonts, Context};
+
+        let context = Context::new(fonts::FontCache::new(get_font_family()));
+        context.push_section("Introduction");
+        let debug = format!("{:?}", context);
+        for field in &[
+            "page_number:",
+            "tracer: ..",
+            "page_body_height:",
+            "section_stack: RefCell { value: [\"Introduction\"] }",
+            "section_counters: RefCell { value: [1] }",
+            "page_element_registry:",
+        ] {
+            assert!(debug.contains(field), "{} not in {}", field, debug);
+        }
+    }
+
+    #[test]
+    fn test_render_stats() {
+        use super::elements::{tests::get_font_family, Paragraph};
+        use super::Document;
+
+        let mut doc = Document::new(get_font_family());
+        doc.push(Paragraph::new("first"));
+        doc.push(Paragraph::new("second"));
+        let stats = doc.render_with_stats(std::io::sink()).unwrap();
+        assert_eq!(stats.page_count, 1);
+        assert_eq!(stats.total_elements, 2);
+        assert_eq!(stats.fonts_embedded, 1);
+        assert_eq!(stats.images_embedded, 0);
+        assert_eq!(stats.pages_with_overflow, 0);
+        assert_eq!(stats.truncated_words, 0);
+    }
+
+    #[test]
+    fn test_table_overflow() {
+        use super::elements::{tests::get_font_family, Break, ColumnWidths, Paragraph};
+        use super::elements::{TableLayout, TableOverflow};
+        use super::error::{Error, ErrorKind};
+        use super::{Document, Element as _, Margins, RenderStats, Size};
+
+        // Renders a 50 mm break, a table with the given number of 20 mm rows and a paragraph that
+        // is more than 45 mm high on pages with a height of 100 mm.
+        let render = |overflow, rows| -> Result<RenderStats, Error> {
+            let mut table = TableLayout::new(ColumnWidths::Weights(vec![1]));
+            table.set_overflow(overflow);
+            table.set_default_row_height(20);
+            for _ in 0..rows {
+                table.row().element(Paragraph::new("a")).push().unwrap();
+            }
+            let mut doc = Document::new(get_font_family());
+            doc.set_paper_size(Size::new(100, 100));
+            doc.push(Break::from_mm(50));
+            doc.push(table);
+            doc.push(Paragraph::new("b").padded(Margins::trbl(45, 0, 0, 0)));
+            doc.render_with_stats(std::io::sink())
+        };
+
+        // Two rows fit on the first page and the last row is rendered on the second page.
+        let stats = render(TableOverflow::ContinueOnNextPage, 3).unwrap();
+        assert_eq!(stats.page_count, 2);
+        // The table is moved to the second page, so the paragraph needs a third page.
+        let stats = render(TableOverflow::KeepOnOnePage, 3).unwrap();
+        assert_eq!(stats.page_count, 3);
+        let stats = render(TableOverflow::Error, 3).unwrap();
+        assert_eq!(stats.page_count, 3);
+
+        // Tables that do not fit on a new page are continued or rejected.
+        let stats = render(TableOverflow::KeepOnOnePage, 6).unwrap();
+        assert_eq!(stats.page_count, 3);
+        let err = render(TableOverflow::Error, 6).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::PageSizeExceeded));
+    }
+
+    #[test]
+    fn test_set_tracer() {
+        use super::elements::{tests::get_font_family, ColumnWidths, Paragraph, TableLayout};
+        use super::{Document, Element as _, LogLevel, Margins, Size, Tracer};
+        use std::{cell, rc};
+
+        struct RecordingTracer(rc::Rc<cell::RefCell<Vec<(LogLevel, String)>>>);
+
+        impl Tracer for RecordingTracer {
+            fn trace(&self, level: LogLevel, message: &str) {
+                self.0.borrow_mut().push((level, message.to_owned()));
+            }
+        }
+
+        let messages = rc::Rc::default();
+        let mut table = TableLayout::new(ColumnWidths::Weights(vec![1]));
+        table.register_header_row_callback_fn(|_| {
+            Ok(Paragraph::new("header").padded(Margins::trbl(150, 0, 0, 0)))
+        });
+        table.row().element(Paragraph::new("a")).push().unwrap();
+        let mut doc = Document::new(get_font_family());
+        doc.set_paper_size(Size::new(100, 100));
+        doc.set_tracer(RecordingTracer(rc::Rc::clone(&messages)));
+        doc.push(table);
+        doc.render(std::io::sink()).unwrap();
+
+        let messages = messages.borrow();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].0, LogLevel::Warn);
+        assert!(messages[0].1.starts_with("Skipping table header row"));
+    }
+
+    #[test]
+    fn test_render_progress() {
+        use super::elements::{tests::get_font_family, Break};
+        use super::{Document, SimplePageDecorator, Size};
+        use std::{cell, rc};
+
+        let mut doc = Document::new(get_font_family());
+        doc.set_paper_size(Size::new(100, 100));
+        let mut decorator = SimplePageDecorator::new();
+        decorator.set_margins(30);
+        doc.set_page_decorator(decorator);
+        for _ in 0..6 {
+            doc.push(Break::from_mm(20));
+        }
+
+        // The body of the decorated pages is 40 mm high, so the 120 mm of content need three pages.
+        let calls = rc::Rc::new(cell::RefCell::new(Vec::new()));
+        let progress_calls = calls.clone();
+        doc.render_with_progress(std::io::sink(), move |page, total| {
+            progress_calls.borrow_mut().push((page, total))
+        })
+        .unwrap();
+        assert_eq!(*calls.borrow(), vec![(1, 3), (2, 3), (3, 3)]);
+    }
+
+    #[test]
+    fn test_reset_list_counter() {
+        use super::elements::{tests::get_font_family, OrderedList, Paragraph};
+        use super::{render, style, Context, Document, Element, Mm, RenderResult};
+        use std::{cell, rc};
+
+        // Records the value of the counter when it is rendered.
+        struct Probe(rc::Rc<cell::RefCell<Vec<usize>>>);
+
+        impl Element for Probe {
+            fn render(
+                &mut self,
+                context: &Context,
+                _area: render::Area<'_>,
+                _style: style::Style,
+            ) -> Result<RenderResult, super::error::Error> {
+                self.0.borrow_mut().push(context.list_counter("steps"));
+                Ok(RenderResult::default())
+            }
+
+            fn get_probable_height(
+                &mut self,
+                _style: style::Style,
+                _context: &Context,
+                _area: render::Area<'_>,
+            ) -> Mm {
+                Mm(0.0)
+            }
+        }
+
+        let mut doc = Document::new(get_font_family());
+        doc.reset_list_counter("steps");
+        assert!(doc.is_empty());
+        assert_eq!(doc.len(), 0);
+
+        let values = rc::Rc::new(cell::RefCell::new(Vec::new()));
+        let list = |count: usize| {
+            let mut list = OrderedList::with_named_counter("steps");
+            for _ in 0..count {
+                list.push(Paragraph::new("item"));
+            }
+            list
+        };
+        doc.push(list(2));
+        doc.push(Probe(values.clone()));
+        doc.reset_list_counter("steps");
+        doc.push(Probe(values.clone()));
+        doc.push(list(1));
+        doc.push(Probe(values.clone()));
+        assert_eq!(doc.len(), 5);
+        doc.render_with_progress(std::io::sink(), |_, _| {})
+            .unwrap();
+        assert_eq!(*values.borrow(), vec![3, 1, 2]);
+    }
+
+    #[test]
+    fn test_element_count() {
+        use super::elements::{tests::get_font_family, Break, LinearLayout, Paragraph};
+        use super::Document;
+
+        let mut doc = Document::new(get_font_family());
+        assert_eq!(doc.element_count(), 0);
+        assert!(doc.is_empty());
+        assert!(doc.elements().is_empty());
+
+        doc.push(Paragraph::new("first"));
+        doc.push(
+            LinearLayout::vertical()
+                .element(Paragraph::new("second"))
+                .element(Break::new(1)),
+        );
+        // Only the top-level elements are counted.
+        assert_eq!(doc.element_count(), 2);
+        assert_eq!(doc.len(), 2);
+        assert!(!doc.is_empty());
+        assert_eq!(doc.elements().len(), 2);
+    }
+
+    #[test]
+    fn test_page_element_registry() {
+        use super::elements::{tests::get_font_family, Break, Paragraph};
+        use super::{Document, Size};
+
+        let mut doc = Document::new(get_font_family());
+        doc.set_paper_size(Size::new(100, 100));
+        doc.set_first_page_number(5);
+        doc.push_with_id("first", Paragraph::new("first"));
+        doc.push(Break::from_mm(95));
+        doc.push_with_id("second", Paragraph::new("second"));
+        let positions = doc.element_positions();
+        let registry = doc.get_page_element_registry();
+        doc.render(std::io::sink()).unwrap();
+
+        let entries = registry.entries();
+        assert_eq!(
+            entries,
+            vec![(0, "first".to_owned()), (1, "second".to_owned())]
+        );
+        for (page_index, id) in entries {
+            assert_eq!(positions.get(&id)[0].page_index, page_index);
+        }
+    }
+
+    #[test]
+    fn test_push_with_id() {
+        use super::elements::{tests::get_font_family, Break, IdTracker, Paragraph};
+        use super::{fonts, style, Document, Element as _, ElementPositions, Mm, Size};
+
+        let tracker = IdTracker::new("break", Break::from_mm(5), ElementPositions::default());
+        assert_eq!(tracker.preferred_size(), Some(Size::new(0, 5)));
+        let paragraph = Paragraph::new("p").with_space_before(5).with_space_after(3);
+        let tracker = IdTracker::new("p", paragraph, ElementPositions::default());
+        assert_eq!(tracker.space_before(), Mm(5.0));
+        assert_eq!(tracker.space_after(), Mm(3.0));
+
+        // The space before the tracked paragraph collapses with the space after the first one.
+        let font_cache = fonts::FontCache::new(get_font_family());
+        let line_height = style::Style::new().line_height(&font_cache);
+        let mut doc = Document::new(get_font_family());
+        doc.set_paper_size(Size::new(100, 100));
+        doc.push_with_id("first", Paragraph::new("first").with_space_after(5));
+        doc.push_with_id("second", Paragraph::new("second").with_space_before(5));
+        let positions = doc.element_positions();
+        doc.render(std::io::sink()).unwrap();
+        let first = positions.get("first")[0];
+        let second = positions.get("second")[0];
+        let approx_eq = |a: Mm, b: Mm| float_cmp::approx_eq!(Mm, a, b, ulps = 2);
+        assert!(approx_eq(first.height, line_height + Mm(5.0)));
+        assert!(approx_eq(second.y, first.y + line_height));
+    }
+
     #[test]
     fn test_rotation() {
         use super::Rotation;
@@ -1395,4 +3033,20 @@ mod tests {
         assert_eq!(Some(-90.0), Rotation::from(-450.0).degrees());
         assert_eq!(Some(-180.0), Rotation::from(-540.0).degrees());
     }
+
+    #[test]
+    fn test_mm_conversions() {
+        use super::Mm;
+
+        let epsilon = Mm::from(0.001);
+        assert!(Mm::from_inches(1.0).approx_eq(Mm::from(25.4), epsilon));
+        assert!(Mm::from_cm(2.5).approx_eq(Mm::from(25), epsilon));
+        assert!(Mm::from_pt(72.0).approx_eq(Mm::from_inches(1.0), epsilon));
+        assert!((Mm::from(25.4).to_pt() - 72.0).abs() < 0.001);
+        assert!((Mm::from(50.8).to_inches() - 2.0).abs() < 0.001);
+
+        assert_eq!(Mm::ZERO, Mm::from(-1).clamp(Mm::ZERO, Mm::from(10)));
+        assert_eq!(Mm::from(10), Mm::from(11).clamp(Mm::ZERO, Mm::from(10)));
+        assert_eq!("12.35 mm", Mm::from(12.345_1).to_string());
+    }
 }
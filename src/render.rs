@@ -30,8 +30,8 @@ use printpdf::ImageXObject;
 use crate::elements::ColumnWidths;
 use crate::error::{Context as _, Error, ErrorKind};
 use crate::fonts;
-use crate::style::{Color, LineStyle, Style};
-use crate::utils::log_msg;
+use crate::style::{Color, LineCap, LineJoin, LineStyle, Style, TextRenderMode};
+use crate::utils::{trace, LogLevel};
 use crate::{Margins, Mm, Position, Size};
 
 #[cfg(feature = "images")]
@@ -72,6 +72,67 @@ impl ops::Deref for UserSpacePosition {
     }
 }
 
+/// A PDF version that can be written to the header of the generated document.
+///
+/// See [`Renderer::with_pdf_version`][].
+///
+/// [`Renderer::with_pdf_version`]: struct.Renderer.html#method.with_pdf_version
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PdfVersion {
+    /// PDF 1.4, the minimum version that supports transparency.
+    Pdf14,
+    /// PDF 1.5.
+    Pdf15,
+    /// PDF 1.6.
+    Pdf16,
+    /// PDF 1.7 (ISO 32000-1), which supports Unicode bookmarks.
+    Pdf17,
+    /// PDF 2.0 (ISO 32000-2), which supports AES-256 encryption.
+    Pdf20,
+}
+
+impl Default for PdfVersion {
+    fn default() -> PdfVersion {
+        PdfVersion::Pdf17
+    }
+}
+
+impl PdfVersion {
+    /// Returns the version string used in the PDF header, for example `"1.7"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PdfVersion::Pdf14 => "1.4",
+            PdfVersion::Pdf15 => "1.5",
+            PdfVersion::Pdf16 => "1.6",
+            PdfVersion::Pdf17 => "1.7",
+            PdfVersion::Pdf20 => "2.0",
+        }
+    }
+}
+
+/// The compression level for the streams of a PDF document, see [`Renderer::with_compression`][].
+///
+/// [`Renderer::with_compression`]: struct.Renderer.html#method.with_compression
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CompressionLevel {
+    /// Do not compress the streams so that the content of the document is human-readable.
+    None,
+    /// Compress the streams with the default deflate compression level.
+    Default,
+    /// Compress the streams with the best deflate compression level.
+    Best,
+}
+
+impl CompressionLevel {
+    fn flate_compression(&self) -> Option<flate2::Compression> {
+        match self {
+            CompressionLevel::None => None,
+            CompressionLevel::Default => Some(flate2::Compression::default()),
+            CompressionLevel::Best => Some(flate2::Compression::best()),
+        }
+    }
+}
+
 /// Renders a PDF document with one or more pages.
 ///
 /// This is a wrapper around a [`printpdf::PdfDocumentReference`][].
@@ -81,6 +142,9 @@ pub struct Renderer {
     doc: printpdf::PdfDocumentReference,
     // invariant: pages.len() >= 1
     pages: Vec<Page>,
+    producer: Option<String>,
+    pdf_version: Option<PdfVersion>,
+    compression: Option<CompressionLevel>,
 }
 
 impl Renderer {
@@ -100,36 +164,131 @@ impl Renderer {
         Ok(Renderer {
             doc,
             pages: vec![page],
+            producer: None,
+            pdf_version: None,
+            compression: None,
         })
     }
 
+    /// Creates a new PDF document renderer for an existing `printpdf` document.
+    ///
+    /// A new page of the given size is added to the document and used as the first page of this
+    /// renderer.  Existing pages of the document are not accessible with this renderer.
+    pub fn from_printpdf(doc: printpdf::PdfDocumentReference, size: impl Into<Size>) -> Renderer {
+        let size = size.into();
+        let (page_idx, layer_idx) = doc.add_page(size.width.into(), size.height.into(), "Layer 1");
+        let page_ref = doc.get_page(page_idx);
+        let layer_ref = page_ref.get_layer(layer_idx);
+        let page = Page::new(page_ref, layer_ref, size);
+
+        Renderer {
+            doc,
+            pages: vec![page],
+            producer: None,
+            pdf_version: None,
+            compression: None,
+        }
+    }
+
+    /// Consumes this renderer and returns the underlying `printpdf` document.
+    ///
+    /// This can be used to access `printpdf` features that are not supported by `genpdf`.  Note
+    /// that the producer set with [`with_auto_producer`][] is only applied by [`write`][], so it
+    /// is not set for the returned document.
+    ///
+    /// [`with_auto_producer`]: #method.with_auto_producer
+    /// [`write`]: #method.write
+    pub fn into_printpdf(self) -> printpdf::PdfDocumentReference {
+        self.doc
+    }
+
     /// Sets the PDF conformance for the generated PDF document.
     pub fn with_conformance(mut self, conformance: printpdf::PdfConformance) -> Self {
         self.doc = self.doc.with_conformance(conformance);
         self
     }
 
+    /// Sets the PDF version that is written to the header of the generated PDF document.
+    ///
+    /// If this method is not called, the version chosen by `printpdf` is used.  Note that this
+    /// only changes the version header; it is up to the caller to make sure that the document
+    /// content and the conformance settings are compatible with the selected version.
+    pub fn with_pdf_version(mut self, version: PdfVersion) -> Self {
+        self.pdf_version = Some(version);
+        self
+    }
+
+    /// Sets the compression level for the streams of the generated PDF document.
+    ///
+    /// If this method is not called, the streams are compressed as chosen by `printpdf`.
+    /// [`CompressionLevel::None`][] is useful for debugging, while
+    /// [`CompressionLevel::Best`][] produces smaller files but takes longer.  Streams that
+    /// already use a different filter, for example JPEG images, are not changed.
+    ///
+    /// [`CompressionLevel::None`]: enum.CompressionLevel.html#variant.None
+    /// [`CompressionLevel::Best`]: enum.CompressionLevel.html#variant.Best
+    pub fn with_compression(mut self, compression: CompressionLevel) -> Self {
+        self.compression = Some(compression);
+        self
+    }
+
     /// Sets the creation date for the generated PDF document.
     pub fn with_creation_date(mut self, date: printpdf::OffsetDateTime) -> Self {
         self.doc = self.doc.with_creation_date(date);
         self
     }
 
+    /// Sets the creation date for the generated PDF document to the current time.
+    pub fn with_creation_date_now(self) -> Self {
+        let now = printpdf::OffsetDateTime::from_unix_timestamp(chrono::Utc::now().timestamp());
+        self.with_creation_date(now)
+    }
+
+    /// Sets the producer of the generated PDF document to `genpdf` and the version of this crate.
+    pub fn with_auto_producer(mut self) -> Self {
+        self.producer = Some(format!("genpdf {}", env!("CARGO_PKG_VERSION")));
+        self
+    }
+
     /// Sets the modification date for the generated PDF document.
     pub fn with_modification_date(mut self, date: printpdf::OffsetDateTime) -> Self {
         self.doc = self.doc.with_mod_date(date);
         self
     }
 
-    /// Adds a new page with the given size to the document.
-    pub fn add_page(&mut self, size: impl Into<Size>) {
+    /// Adds a new page with the given size to the document and returns its index.
+    ///
+    /// The index can be used to access the page with [`get_page`][] or [`get_page_mut`][].
+    ///
+    /// [`get_page`]: #method.get_page
+    /// [`get_page_mut`]: #method.get_page_mut
+    pub fn add_page(&mut self, size: impl Into<Size>) -> usize {
         let size = size.into();
         let (page_idx, layer_idx) =
             self.doc
                 .add_page(size.width.into(), size.height.into(), "Layer 1");
         let page_ref = self.doc.get_page(page_idx);
         let layer_ref = page_ref.get_layer(layer_idx);
-        self.pages.push(Page::new(page_ref, layer_ref, size))
+        self.pages.push(Page::new(page_ref, layer_ref, size));
+        self.pages.len() - 1
+    }
+
+    /// Adds a new empty page with the same size as the last page to the document and returns its
+    /// index.
+    pub fn add_empty_page(&mut self) -> usize {
+        let size = self.last_page().size;
+        self.add_page(size)
+    }
+
+    /// Adds empty pages with the same size as the last page until the document has at least the
+    /// given number of pages.
+    ///
+    /// This can be used to pad the document for booklet printing, where the number of pages must
+    /// be a multiple of four.
+    pub fn ensure_page_count(&mut self, count: usize) {
+        while self.pages.len() < count {
+            self.add_empty_page();
+        }
     }
 
     /// Returns the number of pages in this document.
@@ -137,6 +296,11 @@ impl Renderer {
         self.pages.len()
     }
 
+    /// Returns the number of images that have been added to all pages of this document.
+    pub fn image_count(&self) -> usize {
+        self.pages.iter().map(Page::image_count).sum()
+    }
+
     /// Returns a page of this document.
     pub fn get_page(&self, idx: usize) -> Option<&Page> {
         self.pages.get(idx)
@@ -197,12 +361,71 @@ impl Renderer {
 
     /// Writes this PDF document to a writer.
     pub fn write(self, w: impl io::Write) -> Result<(), Error> {
+        if self.producer.is_none() && self.pdf_version.is_none() && self.compression.is_none() {
+            return self
+                .doc
+                .save(&mut io::BufWriter::new(w))
+                .context("Failed to save document");
+        }
+
+        // printpdf does not support setting the producer, the PDF version or the compression
+        // level, so we have to post-process the document after generating it.
+        let mut data = Vec::new();
         self.doc
-            .save(&mut io::BufWriter::new(w))
+            .save(&mut io::BufWriter::new(&mut data))
+            .context("Failed to save document")?;
+        let mut doc = lopdf::Document::load_mem(&data).context("Failed to load document")?;
+        if let Some(producer) = self.producer {
+            let info_id = doc
+                .trailer
+                .get(b"Info")
+                .and_then(lopdf::Object::as_reference)
+                .context("Failed to find document information")?;
+            doc.get_object_mut(info_id)
+                .and_then(lopdf::Object::as_dict_mut)
+                .context("Failed to find document information")?
+                .set("Producer", lopdf::Object::string_literal(producer));
+        }
+        if let Some(pdf_version) = self.pdf_version {
+            doc.version = pdf_version.as_str().to_owned();
+        }
+        if let Some(compression) = self.compression {
+            compress_streams(&mut doc, compression)?;
+        }
+        doc.save_to(&mut io::BufWriter::new(w))
             .context("Failed to save document")
     }
 }
 
+/// Re-encodes all deflate-compressed or uncompressed streams of the given document with the
+/// given compression level.
+fn compress_streams(doc: &mut lopdf::Document, compression: CompressionLevel) -> Result<(), Error> {
+    use io::Write as _;
+
+    for object in doc.objects.values_mut() {
+        if let lopdf::Object::Stream(stream) = object {
+            stream.decompress();
+            let level = match compression.flate_compression() {
+                Some(level) => level,
+                None => continue,
+            };
+            if !stream.allows_compression || stream.dict.get(b"Filter").is_ok() {
+                continue;
+            }
+            let mut encoder = flate2::write::ZlibEncoder::new(Vec::new(), level);
+            encoder
+                .write_all(&stream.content)
+                .context("Failed to compress stream")?;
+            let compressed = encoder.finish().context("Failed to compress stream")?;
+            if compressed.len() < stream.content.len() {
+                stream.dict.set("Filter", "FlateDecode");
+                stream.set_content(compressed);
+            }
+        }
+    }
+    Ok(())
+}
+
 /// A page of a PDF document.
 ///
 /// This is a wrapper around a [`printpdf::PdfPageReference`][].
@@ -212,6 +435,7 @@ pub struct Page {
     page: printpdf::PdfPageReference,
     size: Size,
     layers: Layers,
+    image_count: cell::Cell<usize>,
 }
 
 impl Page {
@@ -224,6 +448,7 @@ impl Page {
             page,
             size,
             layers: Layers::new(layer),
+            image_count: cell::Cell::new(0),
         }
     }
 
@@ -233,6 +458,11 @@ impl Page {
         self.layers.push(layer);
     }
 
+    /// Returns the number of images that have been added to this page.
+    pub fn image_count(&self) -> usize {
+        self.image_count.get()
+    }
+
     /// Returns the number of layers on this page.
     pub fn layer_count(&self) -> usize {
         self.layers.len()
@@ -336,10 +566,16 @@ impl<'p> Layer<'p> {
     /// remove alpha channel from image x object
     pub fn remove_alpha_channel_from_image_x_object(image_x_object: ImageXObject) -> ImageXObject {
         if !matches!(image_x_object.color_space, ColorSpace::Rgba) {
-            log_msg("Color space is not RGBA, skipping alpha channel removal.");
+            trace(
+                LogLevel::Debug,
+                "Color space is not RGBA, skipping alpha channel removal.",
+            );
             return image_x_object;
         };
-        log_msg("Color space is RGBA, removing alpha channel.");
+        trace(
+            LogLevel::Debug,
+            "Color space is RGBA, removing alpha channel.",
+        );
         let ImageXObject {
             color_space,
             image_data,
@@ -398,6 +634,7 @@ impl<'p> Layer<'p> {
             Some(scale.y),
             dpi,
         );
+        self.page.image_count.set(self.page.image_count.get() + 1);
     }
 
     fn add_line_shape<I>(&self, points: I)
@@ -419,6 +656,24 @@ impl<'p> Layer<'p> {
         self.data.layer.add_shape(line);
     }
 
+    fn add_clipping_path<I>(&self, points: I)
+    where
+        I: IntoIterator<Item = LayerPosition>,
+    {
+        let line_points: Vec<_> = points
+            .into_iter()
+            .map(|pos| (self.transform_position(pos).into(), false))
+            .collect();
+        let line = printpdf::Line {
+            points: line_points,
+            is_closed: true,
+            has_fill: false,
+            has_stroke: false,
+            is_clipping_path: true,
+        };
+        self.data.layer.add_shape(line);
+    }
+
     fn draw_filled_shape<I>(&self, points: I, color: Option<Color>)
     where
         I: IntoIterator<Item = LayerPosition>,
@@ -465,6 +720,24 @@ impl<'p> Layer<'p> {
         }
     }
 
+    fn set_line_cap(&self, line_cap: LineCap) {
+        if self.data.update_line_cap(line_cap) {
+            self.data.layer.set_line_cap_style(line_cap.into());
+        }
+    }
+
+    fn set_line_join(&self, line_join: LineJoin) {
+        if self.data.update_line_join(line_join) {
+            self.data.layer.set_line_join_style(line_join.into());
+        }
+    }
+
+    fn set_text_render_mode(&self, mode: TextRenderMode) {
+        if self.data.update_text_render_mode(mode) {
+            self.data.layer.set_text_rendering_mode(mode.into());
+        }
+    }
+
     fn set_text_cursor(&self, cursor: LayerPosition) {
         let cursor = self.transform_position(cursor);
         self.data
@@ -488,8 +761,12 @@ impl<'p> Layer<'p> {
         self.data.layer.set_line_height(line_height.0);
     }
 
-    fn set_font(&self, font: &printpdf::IndirectFontRef, font_size: u8) {
-        self.data.layer.set_font(font, font_size.into());
+    fn set_font(&self, font: &printpdf::IndirectFontRef, font_size: f64) {
+        self.data.layer.set_font(font, font_size);
+    }
+
+    fn set_text_rise(&self, rise: Mm) {
+        self.data.layer.set_line_offset(printpdf::Pt::from(rise).0);
     }
 
     fn write_positioned_codepoints<P, C>(&self, positions: P, codepoints: C)
@@ -502,6 +779,30 @@ impl<'p> Layer<'p> {
             .write_positioned_codepoints(positions.into_iter().zip(codepoints.into_iter()));
     }
 
+    /// Discards the cached graphics state of this layer.
+    ///
+    /// Per default, the fill color, outline color, outline thickness, line cap, line join and
+    /// text render mode are only set if they differ from the current state of the layer.  After
+    /// calling this method, they are set again on the next drawing operation.  This is useful if
+    /// the graphics state has been changed directly on the underlying `printpdf` layer.
+    pub fn reset_graphics_state(&self) {
+        self.data.set_graphics_state(GraphicsState::default());
+    }
+
+    /// Saves the current graphics state on the PDF graphics state stack and returns the cached
+    /// state so that it can be restored later.
+    fn save_graphics_state(&self) -> GraphicsState {
+        self.data.layer.save_graphics_state();
+        self.data.graphics_state()
+    }
+
+    /// Restores the last saved graphics state from the PDF graphics state stack and updates the
+    /// cached state accordingly.
+    fn restore_graphics_state(&self, state: GraphicsState) {
+        self.data.layer.restore_graphics_state();
+        self.data.set_graphics_state(state);
+    }
+
     /// Transforms the given position that is relative to the upper left corner of the layer to a
     /// position that is relative to the lower left corner of the layer (as used by `printpdf`).
     fn transform_position(&self, position: LayerPosition) -> UserSpacePosition {
@@ -509,26 +810,75 @@ impl<'p> Layer<'p> {
     }
 }
 
+/// The cached graphics state of a layer, see [`LayerData`][].
+///
+/// A value of `None` means that the state is unknown, so the corresponding operator is always
+/// emitted on the next change.
+#[derive(Clone, Copy, Debug, Default)]
+struct GraphicsState {
+    fill_color: Option<Color>,
+    outline_color: Option<Color>,
+    outline_thickness: Option<Mm>,
+    text_render_mode: Option<TextRenderMode>,
+    line_cap: Option<LineCap>,
+    line_join: Option<LineJoin>,
+}
+
 #[derive(Debug)]
 struct LayerData {
     layer: printpdf::PdfLayerReference,
-    fill_color: cell::Cell<Color>,
-    outline_color: cell::Cell<Color>,
-    outline_thickness: cell::Cell<Mm>,
+    fill_color: cell::Cell<Option<Color>>,
+    outline_color: cell::Cell<Option<Color>>,
+    outline_thickness: cell::Cell<Option<Mm>>,
+    text_render_mode: cell::Cell<Option<TextRenderMode>>,
+    line_cap: cell::Cell<Option<LineCap>>,
+    line_join: cell::Cell<Option<LineJoin>>,
 }
 
 impl LayerData {
+    pub fn graphics_state(&self) -> GraphicsState {
+        GraphicsState {
+            fill_color: self.fill_color.get(),
+            outline_color: self.outline_color.get(),
+            outline_thickness: self.outline_thickness.get(),
+            text_render_mode: self.text_render_mode.get(),
+            line_cap: self.line_cap.get(),
+            line_join: self.line_join.get(),
+        }
+    }
+
+    pub fn set_graphics_state(&self, state: GraphicsState) {
+        self.fill_color.set(state.fill_color);
+        self.outline_color.set(state.outline_color);
+        self.outline_thickness.set(state.outline_thickness);
+        self.text_render_mode.set(state.text_render_mode);
+        self.line_cap.set(state.line_cap);
+        self.line_join.set(state.line_join);
+    }
+
     pub fn update_fill_color(&self, color: Option<Color>) -> bool {
-        let color = color.unwrap_or(Color::Rgb(0, 0, 0));
+        let color = Some(color.unwrap_or(Color::Rgb(0, 0, 0)));
         self.fill_color.replace(color) != color
     }
 
     pub fn update_outline_color(&self, color: Color) -> bool {
-        self.outline_color.replace(color) != color
+        self.outline_color.replace(Some(color)) != Some(color)
     }
 
     pub fn update_outline_thickness(&self, thickness: Mm) -> bool {
-        self.outline_thickness.replace(thickness) != thickness
+        self.outline_thickness.replace(Some(thickness)) != Some(thickness)
+    }
+
+    pub fn update_text_render_mode(&self, mode: TextRenderMode) -> bool {
+        self.text_render_mode.replace(Some(mode)) != Some(mode)
+    }
+
+    pub fn update_line_cap(&self, line_cap: LineCap) -> bool {
+        self.line_cap.replace(Some(line_cap)) != Some(line_cap)
+    }
+
+    pub fn update_line_join(&self, line_join: LineJoin) -> bool {
+        self.line_join.replace(Some(line_join)) != Some(line_join)
     }
 }
 
@@ -536,9 +886,12 @@ impl From<printpdf::PdfLayerReference> for LayerData {
     fn from(layer: printpdf::PdfLayerReference) -> Self {
         Self {
             layer,
-            fill_color: Color::Rgb(0, 0, 0).into(),
-            outline_color: Color::Rgb(0, 0, 0).into(),
-            outline_thickness: Mm::from(printpdf::Pt(1.0)).into(),
+            fill_color: Some(Color::Rgb(0, 0, 0)).into(),
+            outline_color: Some(Color::Rgb(0, 0, 0)).into(),
+            outline_thickness: Some(Mm::from(printpdf::Pt(1.0))).into(),
+            text_render_mode: Some(TextRenderMode::Fill).into(),
+            line_cap: Some(LineCap::Butt).into(),
+            line_join: Some(LineJoin::Miter).into(),
         }
     }
 }
@@ -592,6 +945,12 @@ impl<'p> Area<'p> {
         self.margin_top += margins.top;
     }
 
+    /// Returns the position of the upper left corner of this area, measured from the upper left
+    /// corner of the page.
+    pub fn origin(&self) -> Position {
+        self.origin
+    }
+
     /// Returns the size of this area.
     pub fn size(&self) -> Size {
         self.size
@@ -647,17 +1006,108 @@ impl<'p> Area<'p> {
         match weights {
             ColumnWidths::Weights(weights) => self.split_horizontally_by_weights(weights),
             ColumnWidths::PixelWidths(widths) => self.split_horizontally_by_pixels(widths),
+            ColumnWidths::Mixed(_)
+            | ColumnWidths::Constrained { .. }
+            | ColumnWidths::AutoFit(_) => {
+                let widths: Vec<f64> = weights
+                    .resolve(self.size.width)
+                    .into_iter()
+                    .map(|width| width.0)
+                    .collect();
+                self.split_horizontally_by_pixels(&widths)
+            }
+        }
+    }
+
+    /// Splits this area horizontally using the given column widths, leaving a gap of the given
+    /// width between the columns.
+    ///
+    /// The gaps take up *gap \* (num_columns - 1)* of the width of this area.  Weighted columns
+    /// share the remaining width, while pixel widths and fixed widths are used as given.
+    pub fn split_horizontally_with_gap(
+        &self,
+        weights: &ColumnWidths,
+        gap: impl Into<Mm>,
+    ) -> Vec<Area<'p>> {
+        let gap = gap.into();
+        let mut area = self.clone();
+        area.size.width -= gap * weights.len().saturating_sub(1) as f64;
+        let mut areas = area.split_horizontally(weights);
+        for (i, area) in areas.iter_mut().enumerate() {
+            area.origin.x += gap * i as f64;
+        }
+        areas
+    }
+
+    /// Splits this area vertically into areas with the given heights.
+    ///
+    /// The returned vector has the same number of elements as the provided slice.  The areas are
+    /// stacked from top to bottom.  The last area extends to the bottom of this area, i. e. its
+    /// height is the remaining height instead of the given height.
+    pub fn split_vertically(&self, heights: &[Mm]) -> Vec<Area<'p>> {
+        let mut offset = Mm(0.0);
+        let mut areas = Vec::new();
+        for (i, height) in heights.iter().enumerate() {
+            let mut area = self.clone();
+            area.origin.y += offset;
+            area.size.height = if i + 1 == heights.len() {
+                (self.size.height - offset).max(Mm(0.0))
+            } else {
+                *height
+            };
+            areas.push(area);
+            offset += *height;
+        }
+        areas
+    }
+
+    /// Splits this area vertically into `n` areas with the same height.
+    pub fn split_vertically_equal(&self, n: usize) -> Vec<Area<'p>> {
+        let height = self.size.height / n.max(1) as f64;
+        self.split_vertically(&vec![height; n])
+    }
+
+    /// Splits this area vertically using the given row heights, leaving a gap of the given height
+    /// between the rows.
+    ///
+    /// This is the vertical equivalent of [`split_horizontally_with_gap`][]:  the gaps take up
+    /// *gap \* (num_rows - 1)* of the height of this area.  Weighted rows share the remaining
+    /// height, while pixel heights and fixed heights are used as given.
+    ///
+    /// [`split_horizontally_with_gap`]: #method.split_horizontally_with_gap
+    pub fn split_vertically_with_gap(
+        &self,
+        weights: &ColumnWidths,
+        gap: impl Into<Mm>,
+    ) -> Vec<Area<'p>> {
+        let gap = gap.into();
+        let available = self.size.height - gap * weights.len().saturating_sub(1) as f64;
+        let heights = weights.resolve(available);
+        let mut offset = Mm(0.0);
+        let mut areas = Vec::new();
+        for height in heights {
+            let mut area = self.clone();
+            area.origin.y += offset;
+            area.size.height = height;
+            areas.push(area);
+            offset += height + gap;
         }
+        areas
     }
 
     /// Splits this area horizontally using the given weights.
     ///
     /// The returned vector has the same number of elements as the provided slice.  The width of
     /// the *i*-th area is *width \* weights[i] / total_weight*, where *width* is the width of this
-    /// area, and *total_weight* is the sum of all given weights.
+    /// area, and *total_weight* is the sum of all given weights.  If all weights are zero, the
+    /// areas have a width of zero.
     fn split_horizontally_by_weights(&self, weights: &[usize]) -> Vec<Area<'p>> {
         let total_weight: usize = weights.iter().sum();
-        let factor = self.size.width / total_weight as f64;
+        let factor = if total_weight == 0 {
+            Mm(0.0)
+        } else {
+            self.size.width / total_weight as f64
+        };
         let widths = weights.iter().map(|weight| factor * *weight as f64);
         let mut offset = Mm(0.0);
         let mut areas = Vec::new();
@@ -721,6 +1171,8 @@ impl<'p> Area<'p> {
     {
         self.layer.set_outline_thickness(line_style.thickness());
         self.layer.set_outline_color(line_style.color());
+        self.layer.set_line_cap(line_style.line_cap());
+        self.layer.set_line_join(line_style.line_join());
         self.layer
             .add_line_shape(points.into_iter().map(|pos| self.position(pos)));
     }
@@ -733,10 +1185,54 @@ impl<'p> Area<'p> {
         I: IntoIterator<Item = Position>,
     {
         self.layer.set_outline_thickness(line_style.thickness());
+        self.layer.set_line_join(line_style.line_join());
         self.layer
             .draw_filled_shape(points.into_iter().map(|pos| self.position(pos)), color);
     }
 
+    /// Fills the entire area with the given color.
+    ///
+    /// The rectangle is drawn without a border.
+    pub fn fill_rect(&self, color: Color) {
+        self.fill_rect_at(Position::default(), self.size, color);
+    }
+
+    /// Fills a rectangle with the given position and size with the given color.
+    ///
+    /// The position is relative to the upper left corner of the area.  The rectangle is drawn
+    /// without a border.
+    pub fn fill_rect_at(&self, position: Position, size: Size, color: Color) {
+        let top_left = position;
+        let top_right = Position::new(position.x + size.width, position.y);
+        let bottom_right = Position::new(position.x + size.width, position.y + size.height);
+        let bottom_left = Position::new(position.x, position.y + size.height);
+        self.draw_filled_shape(
+            vec![top_left, top_right, bottom_right, bottom_left],
+            Some(color),
+            LineStyle::new().with_thickness(0),
+        );
+    }
+
+    /// Returns the size of the given string with the given style without drawing it.
+    ///
+    /// The width is the width of the string and the height is the line height of the style.
+    pub fn measure_text(&self, font_cache: &fonts::FontCache, text: &str, style: Style) -> Size {
+        Size::new(
+            style.str_width(font_cache, text),
+            style.line_height(font_cache),
+        )
+    }
+
+    /// Returns `true` if the given string with the given style fits into this area.
+    ///
+    /// See [`measure_text`][] for details on how the size of the string is calculated.
+    ///
+    /// [`measure_text`]: #method.measure_text
+    pub fn would_fit_text(&self, font_cache: &fonts::FontCache, text: &str, style: Style) -> bool {
+        let size = self.measure_text(font_cache, text, style);
+        size.width <= self.size.width && size.height <= self.size.height
+    }
+
     /// Tries to draw the given string at the given position and returns `true` if the area was
     /// large enough to draw the string.
     ///
@@ -775,19 +1271,147 @@ impl<'p> Area<'p> {
         TextSection::new(font_cache, area, metrics)
     }
 
+    /// Returns a text section that uses the given line height instead of the line height of the
+    /// given metrics.
+    ///
+    /// See [`text_section`][] and [`TextSection::set_line_height_override`][].
+    ///
+    /// [`text_section`]: #method.text_section
+    /// [`TextSection::set_line_height_override`]: struct.TextSection.html#method.set_line_height_override
+    pub fn text_section_with_line_height<'f>(
+        &self,
+        font_cache: &'f fonts::FontCache,
+        position: Position,
+        metrics: fonts::Metrics,
+        line_height: impl Into<Mm>,
+    ) -> Option<TextSection<'f, 'p>> {
+        let mut section = self.text_section(font_cache, position, metrics)?;
+        section.set_line_height_override(line_height);
+        Some(section)
+    }
+
+    /// Saves the current graphics state and returns a guard that restores it when it is dropped.
+    ///
+    /// The guard dereferences to a copy of this area.  All changes to the graphics state made
+    /// while drawing on the guard, for example the fill color, the outline color or the line
+    /// thickness, are reverted once the guard is dropped.
+    ///
+    /// This method must not be called while a [`TextSection`][] is active.
+    ///
+    /// [`TextSection`]: struct.TextSection.html
+    pub fn push_graphics_state(&self) -> AreaStateGuard<'p> {
+        let state = self.layer.save_graphics_state();
+        AreaStateGuard {
+            area: self.clone(),
+            state,
+        }
+    }
+
+    /// Restricts all drawing operations to the rectangle with the given size at the upper left
+    /// corner of this area and returns the clipped area.
+    ///
+    /// The clipping path is removed when the returned [`ClippedArea`][] is dropped.  Like
+    /// [`push_graphics_state`][], this method must not be called while a [`TextSection`][] is
+    /// active.
+    ///
+    /// [`ClippedArea`]: struct.ClippedArea.html
+    /// [`push_graphics_state`]: #method.push_graphics_state
+    /// [`TextSection`]: struct.TextSection.html
+    pub fn with_clip(&self, size: impl Into<Size>) -> ClippedArea<'p> {
+        let size = size.into();
+        let guard = self.push_graphics_state();
+        let points = vec![
+            Position::new(0, 0),
+            Position::new(size.width, 0),
+            Position::new(size.width, size.height),
+            Position::new(0, size.height),
+        ];
+        self.layer
+            .add_clipping_path(points.into_iter().map(|pos| self.position(pos)));
+        ClippedArea { guard }
+    }
+
+    /// Restricts all drawing operations to this area and returns the clipped area.
+    ///
+    /// See [`with_clip`][] for more information.
+    ///
+    /// [`with_clip`]: #method.with_clip
+    pub fn clip_to_self(&self) -> ClippedArea<'p> {
+        self.with_clip(self.size)
+    }
+
     /// Returns a position relative to the top left corner of this area.
     fn position(&self, position: Position) -> LayerPosition {
         LayerPosition::from_area(self, position)
     }
 }
 
+/// A guard that restores the graphics state of an area when it is dropped.
+///
+/// This guard is created by [`Area::push_graphics_state`][] and dereferences to the [`Area`][].
+///
+/// [`Area`]: struct.Area.html
+/// [`Area::push_graphics_state`]: struct.Area.html#method.push_graphics_state
+pub struct AreaStateGuard<'p> {
+    area: Area<'p>,
+    state: GraphicsState,
+}
+
+impl<'p> ops::Deref for AreaStateGuard<'p> {
+    type Target = Area<'p>;
+
+    fn deref(&self) -> &Area<'p> {
+        &self.area
+    }
+}
+
+impl<'p> ops::DerefMut for AreaStateGuard<'p> {
+    fn deref_mut(&mut self) -> &mut Area<'p> {
+        &mut self.area
+    }
+}
+
+impl<'p> Drop for AreaStateGuard<'p> {
+    fn drop(&mut self) {
+        self.area.layer.restore_graphics_state(self.state);
+    }
+}
+
+/// An area with a rectangular clipping path.
+///
+/// This struct is created by [`Area::with_clip`][] and [`Area::clip_to_self`][] and dereferences
+/// to the [`Area`][].  Everything that is drawn outside of the clipping rectangle is hidden.  The
+/// clipping path is removed when this struct is dropped.
+///
+/// [`Area`]: struct.Area.html
+/// [`Area::with_clip`]: struct.Area.html#method.with_clip
+/// [`Area::clip_to_self`]: struct.Area.html#method.clip_to_self
+pub struct ClippedArea<'p> {
+    guard: AreaStateGuard<'p>,
+}
+
+impl<'p> ops::Deref for ClippedArea<'p> {
+    type Target = Area<'p>;
+
+    fn deref(&self) -> &Area<'p> {
+        &self.guard
+    }
+}
+
+impl<'p> ops::DerefMut for ClippedArea<'p> {
+    fn deref_mut(&mut self) -> &mut Area<'p> {
+        &mut self.guard
+    }
+}
+
 /// A text section that is drawn on an area of a PDF layer.
 pub struct TextSection<'f, 'p> {
     font_cache: &'f fonts::FontCache,
     area: Area<'p>,
     is_first: bool,
     metrics: fonts::Metrics,
-    font: Option<(printpdf::IndirectFontRef, u8)>,
+    font: Option<(printpdf::IndirectFontRef, f64)>,
+    rise: Mm,
 }
 
 impl<'f, 'p> TextSection<'f, 'p> {
@@ -809,9 +1433,23 @@ impl<'f, 'p> TextSection<'f, 'p> {
             is_first: true,
             metrics,
             font: None,
+            rise: Mm(0.0),
         })
     }
 
+    /// Sets the line height of this text section, overriding the line height of the font
+    /// metrics.
+    ///
+    /// The line height determines the distance between the lines that are added with
+    /// [`add_newline`][].
+    ///
+    /// [`add_newline`]: #method.add_newline
+    pub fn set_line_height_override(&mut self, line_height: impl Into<Mm>) {
+        let line_height = line_height.into();
+        self.metrics.line_height = line_height;
+        self.area.layer.set_line_height(line_height);
+    }
+
     fn set_text_cursor(&self, x_offset: Mm) {
         let cursor = self
             .area
@@ -819,7 +1457,7 @@ impl<'f, 'p> TextSection<'f, 'p> {
         self.area.layer.set_text_cursor(cursor);
     }
 
-    fn set_font(&mut self, font: &printpdf::IndirectFontRef, font_size: u8) {
+    fn set_font(&mut self, font: &printpdf::IndirectFontRef, font_size: f64) {
         let font_is_set = self
             .font
             .as_ref()
@@ -832,6 +1470,13 @@ impl<'f, 'p> TextSection<'f, 'p> {
         }
     }
 
+    fn set_text_rise(&mut self, rise: Mm) {
+        if self.rise != rise {
+            self.rise = rise;
+            self.area.layer.set_text_rise(rise);
+        }
+    }
+
     /// Tries to add a new line and returns `true` if the area was large enough to fit the new
     /// line.
     #[must_use]
@@ -862,9 +1507,18 @@ impl<'f, 'p> TextSection<'f, 'p> {
         }
         self.is_first = false;
 
+        // The letter spacing is inserted before every character but the first one
+        let font_size = style.scaled_font_size(self.font_cache);
+        let tracking = if font_size > 0.0 {
+            (printpdf::Pt::from(style.letter_spacing()).0 / font_size) as f32
+        } else {
+            0.0
+        };
         let positions = font
             .kerning(self.font_cache, s.chars())
             .into_iter()
+            .enumerate()
+            .map(|(i, pos)| if i > 0 { pos + tracking } else { pos })
             // Kerning is measured in 1/1000 em
             .map(|pos| pos * -1000.0)
             .map(|pos| pos as i64);
@@ -880,7 +1534,15 @@ impl<'f, 'p> TextSection<'f, 'p> {
             .get_pdf_font(font)
             .expect("Could not find PDF font in font cache");
         self.area.layer.set_fill_color(style.color());
-        self.set_font(font, style.font_size());
+        let text_render_mode = style.text_render_mode();
+        if text_render_mode.is_stroked() {
+            self.area
+                .layer
+                .set_outline_color(style.color().unwrap_or(Color::Rgb(0, 0, 0)));
+        }
+        self.area.layer.set_text_render_mode(text_render_mode);
+        self.set_font(font, font_size);
+        self.set_text_rise(style.script_rise(self.font_cache));
 
         // println!("codepoints: {:?}", codepoints);
 
@@ -893,6 +1555,8 @@ impl<'f, 'p> TextSection<'f, 'p> {
 
 impl<'f, 'p> Drop for TextSection<'f, 'p> {
     fn drop(&mut self) {
+        // The text rise is part of the text state, so it has to be reset for the next section.
+        self.set_text_rise(Mm(0.0));
         self.area.layer.end_text_section();
     }
 }
@@ -196,13 +196,108 @@ impl From<Color> for printpdf::Color {
     }
 }
 
-/// A text effect (bold or italic).
+/// A text effect (bold, italic, superscript or subscript).
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
 pub enum Effect {
     /// Bold text.
     Bold,
     /// Italic text.
     Italic,
+    /// Superscript text, see [`ScriptPosition::Superscript`][].
+    ///
+    /// [`ScriptPosition::Superscript`]: enum.ScriptPosition.html#variant.Superscript
+    Superscript,
+    /// Subscript text, see [`ScriptPosition::Subscript`][].
+    ///
+    /// [`ScriptPosition::Subscript`]: enum.ScriptPosition.html#variant.Subscript
+    Subscript,
+}
+
+/// The font size factor for superscript and subscript text.
+const SCRIPT_FONT_SCALE: f64 = 0.65;
+
+/// The vertical position of text relative to the baseline.
+///
+/// Superscript and subscript text is printed with 65 % of the font size and shifted by a fraction
+/// of the line height, see [`Style::set_script_offset`][].  The line height of the enclosing line
+/// is not changed.
+///
+/// [`Style::set_script_offset`]: struct.Style.html#method.set_script_offset
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ScriptPosition {
+    /// The text is printed on the baseline.
+    Normal,
+    /// The text is raised above the baseline.
+    Superscript,
+    /// The text is lowered below the baseline.
+    Subscript,
+}
+
+impl Default for ScriptPosition {
+    fn default() -> ScriptPosition {
+        ScriptPosition::Normal
+    }
+}
+
+/// The text rendering mode that determines how the glyphs of a text are drawn.
+///
+/// The default mode is `Fill`.  The stroke modes use the color of the [`Style`][] as the outline
+/// color.  The modes ending with `AddToClip` add the text to the clipping path.
+///
+/// [`Style`]: struct.Style.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TextRenderMode {
+    /// Fill the glyphs.
+    Fill,
+    /// Stroke the outline of the glyphs, resulting in hollow text.
+    Stroke,
+    /// Fill and stroke the glyphs.
+    FillStroke,
+    /// Neither fill nor stroke the glyphs, for example for hidden text layers of scanned pages.
+    Invisible,
+    /// Fill the glyphs and add them to the clipping path.
+    FillAddToClip,
+    /// Stroke the glyphs and add them to the clipping path.
+    StrokeAddToClip,
+    /// Fill and stroke the glyphs and add them to the clipping path.
+    FillStrokeAddToClip,
+    /// Add the glyphs to the clipping path without drawing them.
+    AddToClip,
+}
+
+impl Default for TextRenderMode {
+    fn default() -> TextRenderMode {
+        TextRenderMode::Fill
+    }
+}
+
+impl TextRenderMode {
+    /// Returns whether the outline of the glyphs is stroked in this mode.
+    pub fn is_stroked(&self) -> bool {
+        matches!(
+            self,
+            TextRenderMode::Stroke
+                | TextRenderMode::FillStroke
+                | TextRenderMode::StrokeAddToClip
+                | TextRenderMode::FillStrokeAddToClip
+        )
+    }
+}
+
+impl From<TextRenderMode> for printpdf::TextRenderingMode {
+    fn from(mode: TextRenderMode) -> printpdf::TextRenderingMode {
+        match mode {
+            TextRenderMode::Fill => printpdf::TextRenderingMode::Fill,
+            TextRenderMode::Stroke => printpdf::TextRenderingMode::Stroke,
+            TextRenderMode::FillStroke => printpdf::TextRenderingMode::FillStroke,
+            TextRenderMode::Invisible => printpdf::TextRenderingMode::Invisible,
+            TextRenderMode::FillAddToClip => printpdf::TextRenderingMode::FillClip,
+            TextRenderMode::StrokeAddToClip => printpdf::TextRenderingMode::StrokeClip,
+            TextRenderMode::FillStrokeAddToClip => printpdf::TextRenderingMode::FillStrokeClip,
+            TextRenderMode::AddToClip => printpdf::TextRenderingMode::Clip,
+        }
+    }
 }
 
 /// A style annotation for a string.
@@ -213,6 +308,7 @@ pub enum Effect {
 /// - a line spacing factor, with 1 meaning single line spacing (defaults to 1)
 /// - an outline color, see [`Color`][] (defaults to black)
 /// - a combination of text effects, see [`Effect`][] (defaults to none)
+/// - a text rendering mode, see [`TextRenderMode`][] (defaults to fill)
 ///
 /// All properties are optional.  If they are not set, they can be inferred from parent styles or
 /// from the defaults.
@@ -221,6 +317,7 @@ pub enum Effect {
 /// [`Effect`]: enum.Effect.html
 /// [`FontFamily`]: ../fonts/struct.FontFamily.html
 /// [`FontCache`]: ../fonts/struct.FontCache.html
+/// [`TextRenderMode`]: enum.TextRenderMode.html
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct Style {
     font_family: Option<fonts::FontFamily<fonts::Font>>,
@@ -230,6 +327,13 @@ pub struct Style {
     is_bold: Option<bool>,
     is_italic: Option<bool>,
     is_underline: Option<bool>,
+    is_strikethrough: Option<bool>,
+    text_render_mode: Option<TextRenderMode>,
+    underline_thickness: Option<Mm>,
+    underline_offset: Option<Mm>,
+    script_position: Option<ScriptPosition>,
+    script_offset: Option<f64>,
+    letter_spacing: Option<Mm>,
 }
 
 impl Style {
@@ -262,6 +366,27 @@ impl Style {
         if style.is_underline.is_some() {
             self.is_underline = style.is_underline;
         }
+        if style.is_strikethrough.is_some() {
+            self.is_strikethrough = style.is_strikethrough;
+        }
+        if style.text_render_mode.is_some() {
+            self.text_render_mode = style.text_render_mode;
+        }
+        if style.underline_thickness.is_some() {
+            self.underline_thickness = style.underline_thickness;
+        }
+        if style.underline_offset.is_some() {
+            self.underline_offset = style.underline_offset;
+        }
+        if style.script_position.is_some() {
+            self.script_position = style.script_position;
+        }
+        if style.script_offset.is_some() {
+            self.script_offset = style.script_offset;
+        }
+        if style.letter_spacing.is_some() {
+            self.letter_spacing = style.letter_spacing;
+        }
     }
 
     /// Combines this style and the given style and returns the result.
@@ -275,6 +400,73 @@ impl Style {
         left.into().and(right)
     }
 
+    /// Returns the differences between this style and the given style.
+    ///
+    /// The fields of the returned [`StyleDiff`][] are set to the value of the other style if the
+    /// values differ.  The values are compared after applying the defaults, for example an unset
+    /// font size is treated as 12 points.
+    ///
+    /// [`StyleDiff`]: struct.StyleDiff.html
+    pub fn diff(&self, other: &Style) -> StyleDiff {
+        fn diff<T: PartialEq>(a: T, b: T) -> Option<T> {
+            if a == b {
+                None
+            } else {
+                Some(b)
+            }
+        }
+
+        StyleDiff {
+            font_size: diff(self.font_size(), other.font_size()),
+            bold: diff(self.is_bold(), other.is_bold()),
+            italic: diff(self.is_italic(), other.is_italic()),
+            underline: diff(self.is_underline(), other.is_underline()),
+            strikethrough: diff(self.is_strikethrough(), other.is_strikethrough()),
+            color: diff(self.color, other.color).flatten(),
+            line_spacing: diff(self.line_spacing(), other.line_spacing()),
+            letter_spacing: diff(self.letter_spacing(), other.letter_spacing()),
+            script_position: diff(self.script_position(), other.script_position()),
+        }
+    }
+
+    /// Returns a copy of this style with the values of the given diff applied.
+    pub fn apply_diff(&self, diff: &StyleDiff) -> Style {
+        let mut style = *self;
+        if let Some(font_size) = diff.font_size {
+            style.set_font_size(font_size);
+        }
+        if let Some(bold) = diff.bold {
+            style.set_bold(bold);
+        }
+        if let Some(italic) = diff.italic {
+            style.set_italic(italic);
+        }
+        if let Some(underline) = diff.underline {
+            style.set_underline(underline);
+        }
+        if let Some(strikethrough) = diff.strikethrough {
+            style.set_strikethrough(strikethrough);
+        }
+        if let Some(color) = diff.color {
+            style.set_color(color);
+        }
+        if let Some(line_spacing) = diff.line_spacing {
+            style.set_line_spacing(line_spacing);
+        }
+        if let Some(letter_spacing) = diff.letter_spacing {
+            style.set_letter_spacing(letter_spacing);
+        }
+        if let Some(script_position) = diff.script_position {
+            style.set_script_position(script_position);
+        }
+        style
+    }
+
+    /// Returns true if no property of this style is set.
+    pub fn is_default(&self) -> bool {
+        *self == Style::default()
+    }
+
     /// Returns the outline color for this style, if set.
     pub fn color(&self) -> Option<Color> {
         self.color
@@ -298,6 +490,53 @@ impl Style {
         self.is_underline.unwrap_or(false)
     }
 
+    /// Returns whether the strikethrough text effect is set.
+    pub fn is_strikethrough(&self) -> bool {
+        self.is_strikethrough.unwrap_or(false)
+    }
+
+    /// Returns the thickness of the underline for this style, if set.
+    pub fn underline_thickness(&self) -> Option<Mm> {
+        self.underline_thickness
+    }
+
+    /// Returns the offset of the underline below the baseline for this style, if set.
+    ///
+    /// If no offset is set, the underline is drawn at the bottom of the line.
+    pub fn underline_offset(&self) -> Option<Mm> {
+        self.underline_offset
+    }
+
+    /// Returns the vertical position of the text relative to the baseline, or `Normal` if no
+    /// position is set.
+    pub fn script_position(&self) -> ScriptPosition {
+        self.script_position.unwrap_or_default()
+    }
+
+    /// Returns the fraction of the line height that superscript and subscript text is shifted by,
+    /// or 0.4 if no offset is set.
+    pub fn script_offset(&self) -> f64 {
+        self.script_offset.unwrap_or(0.4)
+    }
+
+    /// Returns the additional space between two characters, or zero if no letter spacing is set.
+    pub fn letter_spacing(&self) -> Mm {
+        self.letter_spacing.unwrap_or_default()
+    }
+
+    /// Returns the factor that the font size is multiplied with for the script position.
+    fn script_scale(&self) -> f64 {
+        match self.script_position() {
+            ScriptPosition::Normal => 1.0,
+            ScriptPosition::Superscript | ScriptPosition::Subscript => SCRIPT_FONT_SCALE,
+        }
+    }
+
+    /// Returns the text rendering mode for this style, or `Fill` if no mode is set.
+    pub fn text_render_mode(&self) -> TextRenderMode {
+        self.text_render_mode.unwrap_or_default()
+    }
+
     /// Returns the font size for this style in points, or 12 if no font size is set.
     pub fn font_size(&self) -> u8 {
         self.font_size.unwrap_or(12)
@@ -329,6 +568,11 @@ impl Style {
         self.is_underline = Some(underline);
     }
 
+    /// Sets the strikethrough effect for this style.
+    pub fn set_strikethrough(&mut self, strikethrough: bool) {
+        self.is_strikethrough = Some(strikethrough);
+    }
+
     /// Sets the italic effect for this style and returns it.
     pub fn italic(mut self) -> Style {
         self.set_italic(true);
@@ -336,6 +580,27 @@ impl Style {
     }
 
     /// Sets the font family for this style.
+    ///
+    /// The font family overrides the default font family of the document.  As the style is
+    /// applied per [`StyledString`][], a paragraph can mix multiple font families, for example a
+    /// text font and a symbol font:
+    ///
+    /// ```no_run
+    /// use genpdf::{elements, fonts, style};
+    /// let font_family = fonts::from_files("./fonts", "LiberationSans", None)
+    ///     .expect("Failed to load font family");
+    /// let symbol_family = fonts::from_files("./fonts", "Symbols", None)
+    ///     .expect("Failed to load font family");
+    /// let mut doc = genpdf::Document::new(font_family);
+    /// let symbols = doc.add_font_family(symbol_family);
+    ///
+    /// let mut p = elements::Paragraph::default();
+    /// p.push("Status: ");
+    /// p.push_styled("✓", style::Style::new().with_font_family(symbols));
+    /// doc.push(p);
+    /// ```
+    ///
+    /// [`StyledString`]: struct.StyledString.html
     pub fn set_font_family(&mut self, font_family: fonts::FontFamily<fonts::Font>) {
         self.font_family = Some(font_family);
     }
@@ -346,6 +611,16 @@ impl Style {
         self
     }
 
+    /// Returns the font family that has been set for this style, or `None` if the default font
+    /// family of the font cache should be used.
+    ///
+    /// Use [`font_family`][] to get the effective font family.
+    ///
+    /// [`font_family`]: #method.font_family
+    pub fn font_family_override(&self) -> Option<fonts::FontFamily<fonts::Font>> {
+        self.font_family
+    }
+
     /// Sets the line spacing factor for this style.
     pub fn set_line_spacing(&mut self, line_spacing: f64) {
         self.line_spacing = Some(line_spacing);
@@ -379,6 +654,69 @@ impl Style {
         self
     }
 
+    /// Sets the thickness of the underline for this style.
+    pub fn set_underline_thickness(&mut self, thickness: impl Into<Mm>) {
+        self.underline_thickness = Some(thickness.into());
+    }
+
+    /// Sets the thickness of the underline for this style and returns it.
+    pub fn with_underline_thickness(mut self, thickness: impl Into<Mm>) -> Self {
+        self.set_underline_thickness(thickness);
+        self
+    }
+
+    /// Sets the offset of the underline below the baseline for this style.
+    pub fn set_underline_offset(&mut self, offset: impl Into<Mm>) {
+        self.underline_offset = Some(offset.into());
+    }
+
+    /// Sets the offset of the underline below the baseline for this style and returns it.
+    pub fn with_underline_offset(mut self, offset: impl Into<Mm>) -> Self {
+        self.set_underline_offset(offset);
+        self
+    }
+
+    /// Sets the vertical position of the text relative to the baseline.
+    pub fn set_script_position(&mut self, position: ScriptPosition) {
+        self.script_position = Some(position);
+    }
+
+    /// Sets the vertical position of the text relative to the baseline and returns the style.
+    pub fn with_script_position(mut self, position: ScriptPosition) -> Self {
+        self.set_script_position(position);
+        self
+    }
+
+    /// Sets the fraction of the line height that superscript and subscript text is shifted by.
+    pub fn set_script_offset(&mut self, offset: f64) {
+        self.script_offset = Some(offset);
+    }
+
+    /// Sets the additional space between two characters for this style.
+    ///
+    /// The letter spacing is inserted between all characters of a string.  Negative values
+    /// compress the text.
+    pub fn set_letter_spacing(&mut self, spacing: impl Into<Mm>) {
+        self.letter_spacing = Some(spacing.into());
+    }
+
+    /// Sets the additional space between two characters for this style and returns it.
+    pub fn with_letter_spacing(mut self, spacing: impl Into<Mm>) -> Self {
+        self.set_letter_spacing(spacing);
+        self
+    }
+
+    /// Sets the text rendering mode for this style.
+    pub fn set_text_render_mode(&mut self, mode: TextRenderMode) {
+        self.text_render_mode = Some(mode);
+    }
+
+    /// Sets the text rendering mode for this style and returns it.
+    pub fn with_text_render_mode(mut self, mode: TextRenderMode) -> Self {
+        self.set_text_render_mode(mode);
+        self
+    }
+
     /// Calculates the width of the given character with this style using the data in the given
     /// font cache.
     ///
@@ -388,6 +726,8 @@ impl Style {
     pub fn char_width(&self, font_cache: &fonts::FontCache, c: char) -> Mm {
         self.font(font_cache)
             .char_width(font_cache, c, self.font_size())
+            * font_cache.font_scale()
+            * self.script_scale()
     }
 
     /// Returns the width of the empty space between the origin of the glyph bounding
@@ -399,6 +739,8 @@ impl Style {
     pub fn char_left_side_bearing(&self, font_cache: &fonts::FontCache, c: char) -> Mm {
         self.font(font_cache)
             .char_left_side_bearing(font_cache, c, self.font_size())
+            * font_cache.font_scale()
+            * self.script_scale()
     }
 
     /// Calculates the width of the given string with this style using the data in the given font
@@ -409,7 +751,11 @@ impl Style {
     /// [`FontCache`]: ../fonts/struct.FontCache.html
     pub fn str_width(&self, font_cache: &fonts::FontCache, s: &str) -> Mm {
         let font = self.font(font_cache);
-        font.str_width(font_cache, s, self.font_size())
+        let str_width = font.str_width(font_cache, s, self.font_size())
+            * font_cache.font_scale()
+            * self.script_scale();
+        let gaps = s.chars().count().saturating_sub(1);
+        str_width + self.letter_spacing() * gaps as f64
     }
 
     /// Returns the font family for this style or the default font family using the given font
@@ -439,7 +785,9 @@ impl Style {
     ///
     /// [`FontCache`]: ../fonts/struct.FontCache.html
     pub fn line_height(&self, font_cache: &fonts::FontCache) -> Mm {
-        self.font(font_cache).get_line_height(self.font_size()) * self.line_spacing()
+        self.font(font_cache).get_line_height(self.font_size())
+            * font_cache.font_scale()
+            * self.line_spacing()
     }
 
     /// Calculate the metrics of the font for this style using the data in the given font cache.
@@ -448,10 +796,78 @@ impl Style {
     ///
     /// [`FontCache`]: ../fonts/struct.FontCache.html
     pub fn metrics(&self, font_cache: &fonts::FontCache) -> fonts::Metrics {
+        let font_scale = font_cache.font_scale();
         let mut metrics = self.font(font_cache).metrics(self.font_size());
-        metrics.line_height *= self.line_spacing();
+        metrics.line_height *= font_scale * self.line_spacing();
+        metrics.glyph_height *= font_scale;
+        metrics.ascent *= font_scale;
         metrics
     }
+
+    /// Returns the font size for this style in points multiplied with the font scale of the given
+    /// font cache.
+    ///
+    /// This is the font size that is actually used when rendering text with this style, see
+    /// [`FontCache::set_font_scale`][].  For superscript and subscript text, the font size is
+    /// reduced, see [`ScriptPosition`][].
+    ///
+    /// [`FontCache::set_font_scale`]: ../fonts/struct.FontCache.html#method.set_font_scale
+    /// [`ScriptPosition`]: enum.ScriptPosition.html
+    pub fn scaled_font_size(&self, font_cache: &fonts::FontCache) -> f64 {
+        f64::from(self.font_size()) * font_cache.font_scale() * self.script_scale()
+    }
+
+    /// Returns the distance that text with this style is shifted above the baseline.
+    ///
+    /// The distance is positive for superscript text, negative for subscript text and zero
+    /// otherwise.  It is calculated from the line height of the font without the line spacing,
+    /// see [`set_script_offset`][].
+    ///
+    /// [`set_script_offset`]: #method.set_script_offset
+    pub fn script_rise(&self, font_cache: &fonts::FontCache) -> Mm {
+        let line_height =
+            self.font(font_cache).get_line_height(self.font_size()) * font_cache.font_scale();
+        match self.script_position() {
+            ScriptPosition::Normal => Mm(0.0),
+            ScriptPosition::Superscript => line_height * self.script_offset(),
+            ScriptPosition::Subscript => line_height * -self.script_offset(),
+        }
+    }
+}
+
+/// The differences between two styles, see [`Style::diff`][].
+///
+/// All fields that are set contain the value of the second style.
+///
+/// [`Style::diff`]: struct.Style.html#method.diff
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub struct StyleDiff {
+    /// The font size in points.
+    pub font_size: Option<u8>,
+    /// The bold effect.
+    pub bold: Option<bool>,
+    /// The italic effect.
+    pub italic: Option<bool>,
+    /// The underline effect.
+    pub underline: Option<bool>,
+    /// The strikethrough effect.
+    pub strikethrough: Option<bool>,
+    /// The color.
+    pub color: Option<Color>,
+    /// The line spacing factor.
+    pub line_spacing: Option<f64>,
+    /// The additional space between characters.
+    pub letter_spacing: Option<Mm>,
+    /// The vertical position relative to the baseline.
+    pub script_position: Option<ScriptPosition>,
+}
+
+impl StyleDiff {
+    /// Returns true if the compared styles do not differ.
+    pub fn is_empty(&self) -> bool {
+        *self == StyleDiff::default()
+    }
 }
 
 impl From<Color> for Style {
@@ -466,6 +882,8 @@ impl From<Effect> for Style {
         match effect {
             Effect::Bold => style.bold(),
             Effect::Italic => style.italic(),
+            Effect::Superscript => style.with_script_position(ScriptPosition::Superscript),
+            Effect::Subscript => style.with_script_position(ScriptPosition::Subscript),
         }
     }
 }
@@ -510,6 +928,7 @@ pub struct StyledString {
     pub s: String,
     /// The style annotation.
     pub style: Style,
+    non_breaking: bool,
 }
 
 impl StyledString {
@@ -518,9 +937,26 @@ impl StyledString {
         StyledString {
             s: s.into(),
             style: style.into(),
+            non_breaking: false,
         }
     }
 
+    /// Marks this string as non-breaking and returns it.
+    ///
+    /// Text wrapping never inserts a line break within a non-breaking string or between
+    /// consecutive non-breaking strings, for example to keep “100 km” on one line.
+    pub fn non_breaking(mut self) -> Self {
+        self.non_breaking = true;
+        self
+    }
+
+    /// Returns whether this string is non-breaking, see [`non_breaking`][].
+    ///
+    /// [`non_breaking`]: #method.non_breaking
+    pub fn is_non_breaking(&self) -> bool {
+        self.non_breaking
+    }
+
     /// Calculates the width of the this string with this style using the data in the given font
     /// cache.
     ///
@@ -569,6 +1005,7 @@ pub struct StyledStr<'s> {
     pub s: &'s str,
     /// The style annotation.
     pub style: Style,
+    non_breaking: bool,
 }
 
 impl<'s> StyledStr<'s> {
@@ -577,9 +1014,27 @@ impl<'s> StyledStr<'s> {
         StyledStr {
             s,
             style: style.into(),
+            non_breaking: false,
         }
     }
 
+    /// Marks this string as non-breaking and returns it.
+    ///
+    /// See [`StyledString::non_breaking`][] for more information.
+    ///
+    /// [`StyledString::non_breaking`]: struct.StyledString.html#method.non_breaking
+    pub fn non_breaking(mut self) -> Self {
+        self.non_breaking = true;
+        self
+    }
+
+    /// Returns whether this string is non-breaking, see [`non_breaking`][].
+    ///
+    /// [`non_breaking`]: #method.non_breaking
+    pub fn is_non_breaking(&self) -> bool {
+        self.non_breaking
+    }
+
     /// Calculates the width of the this string with this style using the data in the given font
     /// cache.
     ///
@@ -606,7 +1061,11 @@ impl<'s> From<&'s String> for StyledStr<'s> {
 
 impl<'s> From<&'s StyledString> for StyledStr<'s> {
     fn from(s: &'s StyledString) -> StyledStr<'s> {
-        StyledStr::new(&s.s, s.style)
+        StyledStr {
+            s: &s.s,
+            style: s.style,
+            non_breaking: s.non_breaking,
+        }
     }
 }
 
@@ -687,20 +1146,80 @@ impl<'s> From<StyledString> for StyledCow<'s> {
     }
 }
 
+/// The shape at the end points of a line.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LineCap {
+    /// The line is squared off at the end point.
+    Butt,
+    /// The line ends with a semicircle around the end point.
+    Round,
+    /// The line is extended by half the line thickness beyond the end point and squared off.
+    Square,
+}
+
+impl Default for LineCap {
+    fn default() -> LineCap {
+        LineCap::Butt
+    }
+}
+
+impl From<LineCap> for printpdf::LineCapStyle {
+    fn from(cap: LineCap) -> printpdf::LineCapStyle {
+        match cap {
+            LineCap::Butt => printpdf::LineCapStyle::Butt,
+            LineCap::Round => printpdf::LineCapStyle::Round,
+            LineCap::Square => printpdf::LineCapStyle::ProjectingSquare,
+        }
+    }
+}
+
+/// The shape at the corners where two line segments meet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LineJoin {
+    /// The outer edges of the segments are extended until they meet.
+    Miter,
+    /// The corner is rounded.
+    Round,
+    /// The corner is cut off.
+    Bevel,
+}
+
+impl Default for LineJoin {
+    fn default() -> LineJoin {
+        LineJoin::Miter
+    }
+}
+
+impl From<LineJoin> for printpdf::LineJoinStyle {
+    fn from(join: LineJoin) -> printpdf::LineJoinStyle {
+        match join {
+            LineJoin::Miter => printpdf::LineJoinStyle::Miter,
+            LineJoin::Round => printpdf::LineJoinStyle::Round,
+            LineJoin::Bevel => printpdf::LineJoinStyle::Limit,
+        }
+    }
+}
+
 /// A style for a line, used in styling borders and shapes.
 ///
 /// The style consists of:
 /// - the line thickness in millimeters (defaults to 0.1)
 /// - the color of the line, see [`Color`][] (defaults to black)
+/// - the line cap, see [`LineCap`][] (defaults to butt)
+/// - the line join, see [`LineJoin`][] (defaults to miter)
 ///
 /// Note that a line thickness of 0.0 does not make the line disappear, but rather makes it appear
 /// 1px wide across all devices and resolutions.
 ///
 /// [`Color`]: enum.Color.html
+/// [`LineCap`]: enum.LineCap.html
+/// [`LineJoin`]: enum.LineJoin.html
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct LineStyle {
     thickness: Mm,
     color: Color,
+    line_cap: LineCap,
+    line_join: LineJoin,
 }
 
 impl Default for LineStyle {
@@ -708,6 +1227,8 @@ impl Default for LineStyle {
         LineStyle {
             thickness: Mm::from(0.1),
             color: Color::Rgb(0, 0, 0),
+            line_cap: LineCap::default(),
+            line_join: LineJoin::default(),
         }
     }
 }
@@ -721,10 +1242,10 @@ impl From<Color> for LineStyle {
     }
 }
 
-impl From<Mm> for LineStyle {
-    fn from(mm: Mm) -> LineStyle {
+impl<T: Into<Mm>> From<T> for LineStyle {
+    fn from(thickness: T) -> LineStyle {
         LineStyle {
-            thickness: mm,
+            thickness: thickness.into(),
             ..LineStyle::default()
         }
     }
@@ -736,6 +1257,11 @@ impl LineStyle {
         LineStyle::default()
     }
 
+    /// Creates a new line style with the given color and the default thickness of 0.1 mm.
+    pub fn from_color(color: Color) -> LineStyle {
+        LineStyle::from(color)
+    }
+
     /// Sets the line thickness.
     ///
     /// Setting this to 0.0 will not hide the line, rather it’s a special value that tells PDF
@@ -773,4 +1299,36 @@ impl LineStyle {
     pub fn color(&self) -> Color {
         self.color
     }
+
+    /// Sets the line cap.
+    pub fn set_line_cap(&mut self, line_cap: LineCap) {
+        self.line_cap = line_cap;
+    }
+
+    /// Sets the line cap and returns the line style.
+    pub fn with_line_cap(mut self, line_cap: LineCap) -> Self {
+        self.set_line_cap(line_cap);
+        self
+    }
+
+    /// Returns the line cap.
+    pub fn line_cap(&self) -> LineCap {
+        self.line_cap
+    }
+
+    /// Sets the line join.
+    pub fn set_line_join(&mut self, line_join: LineJoin) {
+        self.line_join = line_join;
+    }
+
+    /// Sets the line join and returns the line style.
+    pub fn with_line_join(mut self, line_join: LineJoin) -> Self {
+        self.set_line_join(line_join);
+        self
+    }
+
+    /// Returns the line join.
+    pub fn line_join(&self) -> LineJoin {
+        self.line_join
+    }
 }
@@ -1,13 +1,98 @@
+use std::cell;
+use std::fmt;
+
 use chrono::Utc;
 
 const FORMAT: &str = "%a %b %e %T %Y";
 
-/// log prints a message to stdout with a timestamp
+/// The severity of a message that is passed to a [`Tracer`][].
+///
+/// [`Tracer`]: trait.Tracer.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LogLevel {
+    /// Detailed information for debugging.
+    Debug,
+    /// Information about the progress of the document generation.
+    Info,
+    /// A problem that does not prevent the document from being generated.
+    Warn,
+    /// A problem that prevents (parts of) the document from being generated.
+    Error,
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            LogLevel::Debug => "DEBUG",
+            LogLevel::Info => "INFO",
+            LogLevel::Warn => "WARN",
+            LogLevel::Error => "ERROR",
+        };
+        f.pad(s)
+    }
+}
+
+/// Receives the log messages of `genpdf`.
+///
+/// The messages that are generated while rendering a document are passed to the tracer of the
+/// document, see [`Document::set_tracer`][].  All other messages, for example when loading fonts,
+/// are passed to the tracer of the current thread, see [`set_tracer`][].
+///
+/// [`Document::set_tracer`]: ../struct.Document.html#method.set_tracer
+/// [`set_tracer`]: fn.set_tracer.html
+pub trait Tracer {
+    /// Handles the given message.
+    fn trace(&self, level: LogLevel, message: &str);
+}
+
+/// A tracer that prints all messages to stdout with a timestamp.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StdoutTracer;
+
+impl Tracer for StdoutTracer {
+    fn trace(&self, level: LogLevel, message: &str) {
+        println!("{} {:>5} {}", Utc::now().format(FORMAT), level, message);
+    }
+}
+
+/// A tracer that discards all messages.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoopTracer;
+
+impl Tracer for NoopTracer {
+    fn trace(&self, _level: LogLevel, _message: &str) {}
+}
+
+thread_local! {
+    static TRACER: cell::RefCell<Box<dyn Tracer>> = cell::RefCell::new(Box::new(NoopTracer));
+}
+
+/// Sets the tracer that receives the log messages of `genpdf` on the current thread that are not
+/// tied to a document, for example when loading fonts.
+///
+/// Per default, these messages are discarded, see [`NoopTracer`][].  The messages that are
+/// generated while rendering a document are passed to the tracer of the document instead, see
+/// [`Document::set_tracer`][].
+///
+/// [`NoopTracer`]: struct.NoopTracer.html
+/// [`Document::set_tracer`]: ../struct.Document.html#method.set_tracer
+pub fn set_tracer(tracer: impl Tracer + 'static) {
+    TRACER.with(|t| *t.borrow_mut() = Box::new(tracer));
+}
+
+/// Passes the given message to the tracer of the current thread, see [`set_tracer`][].
+///
+/// [`set_tracer`]: fn.set_tracer.html
+pub fn trace(level: LogLevel, message: &str) {
+    TRACER.with(|t| t.borrow().trace(level, message));
+}
+
+/// log passes a message with a title to the tracer
 pub fn log(title: &str, msg: &str) {
-    println!("{} {:>12} {}", Utc::now().format(FORMAT), title, msg);
+    trace(LogLevel::Info, &format!("{:>12} {}", title, msg));
 }
 
-/// log_msg prints a message to stdout with a timestamp
+/// log_msg passes a message to the tracer
 pub fn log_msg(msg: &str) {
-    println!("{:>12} {}", Utc::now().format(FORMAT), msg);
+    trace(LogLevel::Info, msg);
 }
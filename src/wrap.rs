@@ -3,6 +3,7 @@
 
 //! Utilities for text wrapping.
 
+use std::iter;
 use std::mem;
 
 use crate::style;
@@ -12,8 +13,9 @@ use crate::Mm;
 /// Combines a sequence of styled words into lines with a maximum width.
 ///
 /// If a word does not fit into a line, the wrapper tries to split it using the `split` function.
+/// Consecutive non-breaking words are kept together and are never split.
 pub struct Wrapper<'c, 's, I: Iterator<Item = style::StyledStr<'s>>> {
-    iter: I,
+    iter: iter::Peekable<I>,
     context: &'c Context,
     width: Mm,
     x: Mm,
@@ -25,7 +27,7 @@ impl<'c, 's, I: Iterator<Item = style::StyledStr<'s>>> Wrapper<'c, 's, I> {
     /// Creates a new wrapper for the given word sequence and with the given maximum width.
     pub fn new(iter: I, context: &'c Context, width: Mm) -> Wrapper<'c, 's, I> {
         Wrapper {
-            iter,
+            iter: iter.peekable(),
             context,
             width,
             x: Mm(0.0),
@@ -34,6 +36,13 @@ impl<'c, 's, I: Iterator<Item = style::StyledStr<'s>>> Wrapper<'c, 's, I> {
         }
     }
 
+    /// Reserves the given width at the start of the first line, for example for a first-line
+    /// indent, and returns the wrapper.
+    pub fn with_indent(mut self, indent: Mm) -> Self {
+        self.x = indent;
+        self
+    }
+
     /// Returns true if this wrapper has overflowed, i. e. if it encountered a word that it could
     /// not split so that it would fit into a line.
     pub fn has_overflowed(&self) -> bool {
@@ -49,6 +58,38 @@ impl<'c, 's, I: Iterator<Item = style::StyledStr<'s>>> Iterator for Wrapper<'c,
     fn next(&mut self) -> Option<(Vec<style::StyledCow<'s>>, usize)> {
         // Append words to self.buf until the maximum line length is reached
         while let Some(s) = self.iter.next() {
+            if s.is_non_breaking() {
+                // Keep all consecutive non-breaking words together
+                let mut group = vec![s];
+                while self.iter.peek().map_or(false, |s| s.is_non_breaking()) {
+                    group.extend(self.iter.next());
+                }
+                let width: Mm = group
+                    .iter()
+                    .map(|s| s.width(&self.context.font_cache))
+                    .sum();
+
+                if width > self.width {
+                    // The group is longer than the current page – we will never be able to render
+                    // it completely.
+                    self.has_overflowed = true;
+                    return None;
+                }
+
+                let group = group.into_iter().map(style::StyledCow::from);
+                if self.x + width > self.width {
+                    // Return the current line and add the group to the next line
+                    let v = std::mem::take(&mut self.buf);
+                    self.buf.extend(group);
+                    self.x = width;
+                    return Some((v, 0));
+                } else {
+                    self.buf.extend(group);
+                    self.x += width;
+                    continue;
+                }
+            }
+
             let mut width = s.width(&self.context.font_cache);
 
             if self.x + width > self.width {
@@ -95,58 +136,83 @@ impl<'c, 's, I: Iterator<Item = style::StyledStr<'s>>> Iterator for Wrapper<'c,
     }
 }
 
-#[cfg(not(feature = "hyphenation"))]
-fn split<'s>(
-    _context: &Context,
-    _s: style::StyledStr<'s>,
-    _len: Mm,
-) -> Option<(style::StyledCow<'s>, style::StyledCow<'s>)> {
-    None
+/// Finds the valid break points in a word for hyphenation.
+///
+/// Set a hyphenator for a document with [`Document::set_hyphenator`][].  If the `hyphenation`
+/// feature is enabled, this trait is implemented for [`hyphenation::Standard`][].
+///
+/// [`Document::set_hyphenator`]: struct.Document.html#method.set_hyphenator
+/// [`hyphenation::Standard`]: https://docs.rs/hyphenation/latest/hyphenation/type.Standard.html
+pub trait Hyphenator {
+    /// Returns the byte positions of the valid break points in the given word in ascending order.
+    ///
+    /// A break point `i` means that the word can be split into `word[..i]` and `word[i..]`.
+    fn hyphenate(&self, word: &str) -> Vec<usize>;
+}
+
+#[cfg(feature = "hyphenation")]
+impl Hyphenator for hyphenation::Standard {
+    fn hyphenate(&self, word: &str) -> Vec<usize> {
+        hyphenation::Hyphenator::hyphenate(self, word).breaks
+    }
 }
 
 /// Tries to split the given string into two parts so that the first part is shorter than the given
 /// width.
-#[cfg(feature = "hyphenation")]
 fn split<'s>(
     context: &Context,
     s: style::StyledStr<'s>,
     width: Mm,
 ) -> Option<(style::StyledCow<'s>, style::StyledCow<'s>)> {
-    use hyphenation::{Hyphenator, Iter};
-
-    let hyphenator = if let Some(hyphenator) = &context.hyphenator {
-        hyphenator
-    } else {
-        return None;
-    };
+    let hyphenator = context.hyphenator.as_ref()?;
 
     let mark = "-";
     let mark_width = s.style.str_width(&context.font_cache, mark);
 
-    let hyphenated = hyphenator.hyphenate(s.s);
-    let segments: Vec<_> = hyphenated.iter().segments().collect();
-
     // Find the hyphenation with the longest first part so that the first part (and the hyphen) are
     // shorter than or equals to the required width.
-    let idx = segments
-        .iter()
-        .scan(Mm(0.0), |acc, t| {
-            *acc += s.style.str_width(&context.font_cache, t);
-            Some(*acc)
+    let idx = hyphenator
+        .hyphenate(s.s)
+        .into_iter()
+        .filter(|&idx| idx > 0 && idx < s.s.len() && s.s.is_char_boundary(idx))
+        .take_while(|&idx| {
+            s.style.str_width(&context.font_cache, &s.s[..idx]) + mark_width <= width
         })
-        .position(|w| w + mark_width > width)
-        .unwrap_or_default();
-    if idx > 0 {
-        let idx = hyphenated.breaks[idx - 1];
-        let start = s.s[..idx].to_owned() + mark;
-        let end = &s.s[idx..];
-        Some((
-            style::StyledCow::new(start, s.style),
-            style::StyledCow::new(end, s.style),
-        ))
-    } else {
-        None
-    }
+        .last()?;
+    let start = s.s[..idx].to_owned() + mark;
+    let end = &s.s[idx..];
+    Some((
+        style::StyledCow::new(start, s.style),
+        style::StyledCow::new(end, s.style),
+    ))
+}
+
+/// Returns the number of space-separated words in the given text.
+///
+/// Words that span multiple styled strings are counted once.
+pub fn count_words(text: &[style::StyledString]) -> usize {
+    text.iter()
+        .map(|s| s.s.as_str())
+        .collect::<String>()
+        .split_whitespace()
+        .count()
+}
+
+/// Returns the number of lines that are needed to print the given text with the given style in
+/// an area with the given width.
+pub fn count_lines(
+    text: &[style::StyledString],
+    context: &Context,
+    style: style::Style,
+    width: Mm,
+) -> usize {
+    let words: Vec<_> = Words::new(text.iter().map(|s| {
+        let mut s = s.clone();
+        s.style = style.and(s.style);
+        s
+    }))
+    .collect();
+    Wrapper::new(words.iter().map(Into::into), context, width).count()
 }
 
 /// Splits a sequence of styled strings into words.
@@ -176,6 +242,10 @@ impl<I: Iterator<Item = style::StyledString>> Iterator for Words<I> {
         }
 
         if let Some(s) = &mut self.s {
+            // Non-breaking strings are not split into words
+            if s.is_non_breaking() {
+                return Some(mem::take(s));
+            }
             // Split at the first space or use the complete string
             let n = s.s.find(' ').map(|i| i + 1).unwrap_or_else(|| s.s.len());
             let mut tmp = s.s.split_off(n);